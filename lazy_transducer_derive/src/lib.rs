@@ -0,0 +1,162 @@
+//! # Introduction
+//!
+//! `#[derive(LazyParse)]` makes the common "table of fixed-size records" case of
+//! [lazy_transducer](https://docs.rs/lazy_transducer) one derive plus one call.
+//!
+//! For a `#[repr(C)]` struct with named fields, it generates:
+//!
+//! 1. `SIZE` - the size in bytes of a record, i.e., the sum of the sizes of its fields
+//! 2. `transducer` - the fallible transducer function, which parses the `i`th record out of a `(bytes, endian)` input
+//! 3. `lazy_table(bytes, count, endian)` - a bounds-checked constructor for a fallible lazy transducer over `count` records
+//!
+//! Records are parsed field by field, in declaration order, without any padding between fields,
+//! so every field type must be parseable by [scroll](https://docs.rs/scroll) with an `Endian`
+//! context, e.g., integers, floats, or arrays of them. Since the record would not match its
+//! `#[repr(C)]` layout otherwise, a struct with padding between or after its fields is rejected
+//! at compile time; make the padding an explicit field instead:
+//!
+//! ```compile_fail
+//! #[macro_use]
+//! extern crate lazy_transducer_derive;
+//! extern crate lazy_transducer;
+//!
+//! #[derive(LazyParse)]
+//! #[repr(C)]
+//! pub struct Padded {
+//!     pub tag: u8,
+//!     // 3 bytes of padding
+//!     pub value: u32,
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! A field which fails to parse is reported as a `Parse` error, with the index of the record and
+//! the offset of the field.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[macro_use]
+//! extern crate lazy_transducer_derive;
+//! extern crate lazy_transducer;
+//! extern crate rayon;
+//!
+//! use lazy_transducer::Endian;
+//! use rayon::prelude::*;
+//!
+//! #[derive(Debug, LazyParse)]
+//! #[repr(C)]
+//! pub struct Rel {
+//!     pub r_offset: u32,
+//!     pub r_info: u32,
+//! }
+//!
+//! # fn main() {
+//! let bytes = vec![4, 0, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0];
+//! assert_eq!(Rel::SIZE, 8);
+//! let lt = Rel::lazy_table(&bytes, 2, Endian::Little).unwrap();
+//! lt.into_par_iter().for_each(|reloc| {
+//!   assert_eq!(reloc.unwrap().r_info, 5);
+//! });
+//! # }
+//! ```
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Data, DeriveInput, Fields};
+
+/// Derive `SIZE`, `transducer`, and `lazy_table` for a `#[repr(C)]` struct of fixed-size fields;
+/// see the [crate documentation](index.html).
+#[proc_macro_derive(LazyParse)]
+pub fn derive_lazy_parse(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+    match impl_lazy_parse(&ast) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn is_repr_c(ast: &DeriveInput) -> bool {
+    let mut repr_c = false;
+    for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("C") {
+                repr_c = true;
+            }
+            Ok(())
+        });
+    }
+    repr_c
+}
+
+fn impl_lazy_parse(ast: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &ast.ident;
+    if !ast.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&ast.generics, "LazyParse does not support generic structs"));
+    }
+    if !is_repr_c(ast) {
+        return Err(syn::Error::new_spanned(name, "LazyParse requires #[repr(C)]"));
+    }
+    let fields = match ast.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => return Err(syn::Error::new_spanned(name, "LazyParse requires a struct with named fields")),
+        },
+        _ => return Err(syn::Error::new_spanned(name, "LazyParse can only be derived for structs")),
+    };
+    let sizes = fields.iter().map(|field| {
+        let ty = &field.ty;
+        quote! { ::std::mem::size_of::<#ty>() }
+    });
+    let reads = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let ty = &field.ty;
+        quote! {
+            #ident: {
+                let field_offset = *offset;
+                bytes.gread_with::<#ty>(offset, endian)
+                    .map_err(|err| TransducerError::Parse{ idx, offset: field_offset, source: Box::new(err) })?
+            }
+        }
+    });
+    let padded = format!("LazyParse does not support padding in {}; add the padding as an explicit field", name);
+    Ok(quote! {
+        const _: () = assert!(#name::SIZE == ::std::mem::size_of::<#name>(), #padded);
+
+        impl #name {
+            /// The size in bytes of a record
+            pub const SIZE: usize = 0 #(+ #sizes)*;
+            /// Parse the `idx`th record out of the input, returning a `Parse` error with the index
+            /// of the record and the offset of the field which is malformed or does not fit
+            pub fn transducer((bytes, endian): (&[u8], ::lazy_transducer::Endian), idx: usize) -> Result<Self, ::lazy_transducer::TransducerError> {
+                use ::lazy_transducer::__private::Pread;
+                use ::lazy_transducer::TransducerError;
+                let offset = &mut idx.checked_mul(Self::SIZE)
+                    .ok_or(TransducerError::ArithmeticOverflow{ nelements: idx, sizeof_element: Self::SIZE, stride: Self::SIZE })?;
+                Ok(#name {
+                    #(#reads,)*
+                })
+            }
+            /// Create a fallible lazy transducer over `count` records in `bytes`, returning an
+            /// error if they do not fit
+            pub fn lazy_table<'a>(bytes: &'a [u8], count: usize, endian: ::lazy_transducer::Endian)
+                -> Result<::lazy_transducer::TryLazyTransducer<'a, (&'a [u8], ::lazy_transducer::Endian), Self, ::lazy_transducer::TransducerError>, ::lazy_transducer::TransducerError>
+            {
+                use ::lazy_transducer::TransducerError;
+                match count.checked_mul(Self::SIZE) {
+                    None => Err(TransducerError::ArithmeticOverflow{ nelements: count, sizeof_element: Self::SIZE, stride: Self::SIZE }),
+                    Some(total_size) if total_size > bytes.len() => {
+                        Err(TransducerError::ElementOverflow{ nelements: count, sizeof_element: Self::SIZE, stride: Self::SIZE, src_size: bytes.len() })
+                    },
+                    Some(_) => Ok(::lazy_transducer::TryLazyTransducer::new((bytes, endian), count, Self::transducer)),
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,38 @@
+#[macro_use]
+extern crate lazy_transducer_derive;
+extern crate lazy_transducer;
+
+use lazy_transducer::{Endian, TransducerError};
+
+#[derive(Debug, PartialEq, LazyParse)]
+#[repr(C)]
+struct Sym {
+    st_name: u32,
+    st_info: u8,
+    st_other: u8,
+    st_shndx: u16,
+    st_value: u64,
+    st_size: u64,
+}
+
+#[test]
+fn lazy_table() {
+    let mut bytes = Vec::new();
+    for i in 0..3u8 {
+        bytes.extend_from_slice(&[0, 0, 0, i, i, 0, 0, 1]);
+        bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0x10, i]);
+        bytes.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 8]);
+    }
+    assert_eq!(Sym::SIZE, 24);
+    let lt = Sym::lazy_table(&bytes, 3, Endian::Big).unwrap();
+    assert_eq!(lt.get(2).unwrap().unwrap(), Sym { st_name: 2, st_info: 2, st_other: 0, st_shndx: 1, st_value: 0x1002, st_size: 8 });
+    match Sym::lazy_table(&bytes, 4, Endian::Big) {
+        Err(TransducerError::ElementOverflow{ nelements: 4, sizeof_element: 24, stride: 24, src_size: 72 }) => (),
+        res => panic!("expected an element overflow, got {:?}", res.map(|lt| lt.len())),
+    }
+    // a record which does not fit reports the field which failed to parse
+    match Sym::transducer((&bytes[..30], Endian::Big), 1) {
+        Err(TransducerError::Parse{ idx: 1, offset: 30, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+}
@@ -0,0 +1,271 @@
+use std::convert::TryFrom;
+use std::error;
+use std::io;
+use std::sync::{Arc, OnceLock};
+
+use arrow_array::{Array, ArrowPrimitiveType, PrimitiveArray, RecordBatch};
+use arrow_buffer::Buffer;
+use arrow_ipc::{self, Block, Message, MessageHeader, MetadataVersion};
+use arrow_ipc::convert::fb_to_schema;
+use arrow_ipc::reader::{read_footer_length, FileDecoder};
+use arrow_schema::{ArrowError, DataType, Schema};
+
+use TransducerError;
+use lazy_transducer::range_containing;
+use source::overflow;
+
+/// The magic bytes which start an Arrow IPC file, as opposed to a stream
+const FILE_MAGIC: &[u8] = b"ARROW1";
+
+/// The marker which precedes the length of an encapsulated message
+const CONTINUATION: [u8; 4] = [0xff; 4];
+
+/// The length of the trailer of an Arrow IPC file, i.e., the length of the footer and the magic bytes
+const TRAILER_LEN: usize = 10;
+
+fn invalid<E: Into<Box<dyn error::Error + Send + Sync>>>(err: E) -> TransducerError {
+    io::Error::new(io::ErrorKind::InvalidData, err).into()
+}
+
+fn index_of(schema: &Schema, column: &str) -> Result<usize, TransducerError> {
+    schema.index_of(column).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err).into())
+}
+
+fn mismatch<T: ArrowPrimitiveType>(column: &str, data_type: &DataType) -> TransducerError {
+    let msg = format!("column {} has type {}, not {}", column, data_type, T::DATA_TYPE);
+    io::Error::new(io::ErrorKind::InvalidInput, msg).into()
+}
+
+/// The offset, metadata length, and body length of `block`, returning an error if they are
+/// negative, or the block is not in `bytes`
+fn extent(bytes: &[u8], block: &Block) -> Result<(usize, usize, usize), TransducerError> {
+    let offset = usize::try_from(block.offset()).map_err(invalid)?;
+    let metadata_len = usize::try_from(block.metaDataLength()).map_err(invalid)?;
+    let body_len = usize::try_from(block.bodyLength()).map_err(invalid)?;
+    let len = metadata_len.checked_add(body_len).ok_or_else(|| invalid("the block is too long"))?;
+    if offset.checked_add(len).filter(|&end| end <= bytes.len()).is_none() {
+        return Err(overflow(offset, len, bytes.len()))
+    }
+    Ok((offset, metadata_len, body_len))
+}
+
+/// Parse the encapsulated message at the start of `metadata`, which is preceded by its length, and
+/// the continuation marker unless it was written by an old version of Arrow
+fn message(metadata: &[u8]) -> Result<Message<'_>, TransducerError> {
+    let prefix = if metadata.starts_with(&CONTINUATION) { 8 } else { 4 };
+    let flatbuffer = metadata.get(prefix..).ok_or_else(|| overflow(0, prefix, metadata.len()))?;
+    arrow_ipc::root_as_message(flatbuffer).map_err(|err| invalid(err.to_string()))
+}
+
+/// The schema, metadata version, and record batch blocks of the Arrow IPC file in `bytes`, from its footer
+fn file_blocks(bytes: &[u8]) -> Result<(Schema, MetadataVersion, Vec<Block>), TransducerError> {
+    let trailer = bytes.len().checked_sub(TRAILER_LEN).ok_or_else(|| overflow(0, TRAILER_LEN, bytes.len()))?;
+    let mut footer_len = [0; TRAILER_LEN];
+    footer_len.copy_from_slice(&bytes[trailer..]);
+    let footer_len = read_footer_length(footer_len).map_err(invalid)?;
+    let start = trailer.checked_sub(footer_len).ok_or_else(|| overflow(0, footer_len, trailer))?;
+    let footer = arrow_ipc::root_as_footer(&bytes[start..trailer]).map_err(|err| invalid(err.to_string()))?;
+    let schema = footer.schema().map(fb_to_schema).ok_or_else(|| invalid("the footer has no schema"))?;
+    let blocks = footer.recordBatches().map(|blocks| blocks.iter().copied().collect()).unwrap_or_default();
+    Ok((schema, footer.version(), blocks))
+}
+
+/// The schema, metadata version, and record batch blocks of the Arrow IPC stream in `bytes`, by
+/// walking the metadata of its messages, without reading their bodies
+fn stream_blocks(bytes: &[u8]) -> Result<(Schema, MetadataVersion, Vec<Block>), TransducerError> {
+    let mut schema = None;
+    let mut blocks = Vec::new();
+    let mut offset = 0;
+    // the stream ends at a zero length, or just ends
+    while offset < bytes.len() {
+        let prefix = if bytes[offset..].starts_with(&CONTINUATION) { 8 } else { 4 };
+        let mut len = [0; 4];
+        len.copy_from_slice(bytes.get(offset + prefix - 4..offset + prefix).ok_or_else(|| overflow(offset, prefix, bytes.len()))?);
+        let len = usize::try_from(i32::from_le_bytes(len)).map_err(invalid)?;
+        if len == 0 {
+            break
+        }
+        let metadata_len = prefix + len;
+        let metadata = bytes.get(offset..offset + metadata_len).ok_or_else(|| overflow(offset, metadata_len, bytes.len()))?;
+        let message = message(metadata)?;
+        let block = Block::new(offset as i64, metadata_len as i32, message.bodyLength());
+        let (_, _, body_len) = extent(bytes, &block)?;
+        match message.header_type() {
+            MessageHeader::Schema if schema.is_none() => {
+                schema = message.header_as_schema().map(|schema| (fb_to_schema(schema), message.version()));
+            },
+            MessageHeader::RecordBatch => blocks.push(block),
+            // dictionaries are never needed, since a primitive column is not dictionary encoded
+            _ => (),
+        }
+        offset += metadata_len + body_len;
+    }
+    let (schema, version) = schema.ok_or_else(|| invalid("the stream has no schema"))?;
+    Ok((schema, version, blocks))
+}
+
+/// The bytes of an Arrow IPC file or stream, and where its record batches are
+#[derive(Debug)]
+struct Ipc {
+    bytes: Buffer,
+    blocks: Vec<Block>,
+    decoder: FileDecoder,
+}
+
+/// A lazy transducer over a column of primitive values, e.g., `Int64Type` or `Float64Type`, of the
+/// record batches in an [Arrow](https://arrow.apache.org) IPC file or stream, which yields the
+/// value of a row, or `None` if its validity bitmap marks it as null.
+///
+/// The file or stream is read once, and only the metadata of its record batches is parsed up
+/// front; the chosen column of a record batch is decoded, via [arrow-ipc](https://docs.rs/arrow-ipc),
+/// when one of its rows is first accessed, and is cached afterwards, so that accessing a row is
+/// O(log b), where b is the number of record batches. A record batch which fails to decode is a
+/// `Parse` error, with the index of its first row, and its offset.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate arrow_array;
+/// extern crate arrow_ipc;
+/// extern crate rayon;
+/// use std::sync::Arc;
+/// use arrow_array::{ArrayRef, Int32Array, RecordBatch, StringArray};
+/// use arrow_array::types::Int32Type;
+/// use arrow_ipc::writer::StreamWriter;
+/// use lazy_transducer::ArrowColumn;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let ids: ArrayRef = Arc::new(Int32Array::from(vec![Some(1), None, Some(3)]));
+/// let names: ArrayRef = Arc::new(StringArray::from(vec!["a", "b", "c"]));
+/// let batch = RecordBatch::try_from_iter(vec![("id", ids), ("name", names)]).unwrap();
+/// let mut bytes = Vec::new();
+/// {
+///     let mut writer = StreamWriter::try_new(&mut bytes, &batch.schema()).unwrap();
+///     writer.write(&batch).unwrap();
+///     writer.write(&batch).unwrap();
+///     writer.finish().unwrap();
+/// }
+///
+/// let lt: ArrowColumn<Int32Type> = ArrowColumn::from_ipc(&bytes, "id").unwrap();
+/// assert_eq!(lt.len(), 6);
+/// assert_eq!(lt.get(5).unwrap().unwrap(), Some(3));
+/// // only the second record batch was decoded
+/// assert_eq!(lt.decoded_batches(), 1);
+/// assert_eq!(lt.null_count().unwrap(), 2);
+/// assert_eq!(lt.get(1).unwrap().unwrap(), None);
+/// let sum: i32 = lt.into_par_iter().map(|id| id.unwrap().unwrap_or(0)).sum();
+/// assert_eq!(sum, 8);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ArrowColumn<T: ArrowPrimitiveType> {
+    /// The file or stream which the batches are decoded out of, unless they were decoded up front
+    ipc: Option<Arc<Ipc>>,
+    batches: Vec<OnceLock<PrimitiveArray<T>>>,
+    /// The first row of every batch, plus the number of rows as a final entry
+    starts: Vec<usize>,
+}
+
+impl<T: ArrowPrimitiveType> Clone for ArrowColumn<T> {
+    fn clone(&self) -> Self {
+        ArrowColumn { ipc: self.ipc.clone(), batches: self.batches.clone(), starts: self.starts.clone() }
+    }
+}
+
+impl<T: ArrowPrimitiveType> ArrowColumn<T> {
+    /// Create a new lazy transducer over the `column` of every record batch in the Arrow IPC file
+    /// or stream in `bytes`, returning an `InvalidData` I/O error or an `ElementOverflow` error if
+    /// its metadata is malformed, and an `InvalidInput` I/O error if there is no such column, or it
+    /// does not have type `T`.
+    pub fn from_ipc(bytes: &[u8], column: &str) -> Result<Self, TransducerError> {
+        let (schema, version, blocks) = if bytes.starts_with(FILE_MAGIC) { file_blocks(bytes)? } else { stream_blocks(bytes)? };
+        let idx = index_of(&schema, column)?;
+        let data_type = schema.field(idx).data_type();
+        if *data_type != T::DATA_TYPE {
+            return Err(mismatch::<T>(column, data_type))
+        }
+        let mut starts = vec![0];
+        for block in &blocks {
+            let (offset, metadata_len, _) = extent(bytes, block)?;
+            let message = message(&bytes[offset..offset + metadata_len])?;
+            let batch = message.header_as_record_batch().ok_or_else(|| invalid("a record batch block is not a record batch"))?;
+            let rows = usize::try_from(batch.length()).map_err(invalid)?;
+            starts.push(starts[starts.len() - 1] + rows);
+        }
+        let decoder = FileDecoder::new(Arc::new(schema), version).with_projection(vec![idx]);
+        let batches = blocks.iter().map(|_| OnceLock::new()).collect();
+        let ipc = Ipc { bytes: Buffer::from(bytes), blocks, decoder };
+        Ok(ArrowColumn { ipc: Some(Arc::new(ipc)), batches, starts })
+    }
+    /// Create a new lazy transducer over the `column` of every one of the already decoded
+    /// `batches`, returning an `InvalidInput` I/O error if there is no such column, or it does not
+    /// have type `T`.
+    pub fn from_batches(batches: &[RecordBatch], column: &str) -> Result<Self, TransducerError> {
+        let mut arrays = Vec::with_capacity(batches.len());
+        let mut starts = vec![0];
+        for batch in batches {
+            let array = batch.column(index_of(&batch.schema(), column)?);
+            let array = array.as_any().downcast_ref::<PrimitiveArray<T>>().ok_or_else(|| mismatch::<T>(column, array.data_type()))?;
+            starts.push(starts[starts.len() - 1] + array.len());
+            arrays.push(OnceLock::from(array.clone()));
+        }
+        Ok(ArrowColumn { ipc: None, batches: arrays, starts })
+    }
+    /// How many rows are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.starts[self.starts.len() - 1]
+    }
+    /// Whether this lazy transducer has no rows
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// How many record batches have been decoded so far
+    pub fn decoded_batches(&self) -> usize {
+        self.batches.iter().filter(|batch| batch.get().is_some()).count()
+    }
+    /// How many rows are null, decoding every record batch, and returning the error of the first
+    /// one which fails to decode.
+    pub fn null_count(&self) -> Result<usize, TransducerError> {
+        (0..self.batches.len()).map(|batch| self.batch(batch).map(|array| array.null_count())).sum()
+    }
+    /// The column of the `batch`th record batch, decoding it if it hasn't been decoded yet; the
+    /// first copy to be decoded is cached if several threads decode it at once.
+    fn batch(&self, batch: usize) -> Result<&PrimitiveArray<T>, TransducerError> {
+        if let Some(array) = self.batches[batch].get() {
+            return Ok(array)
+        }
+        // we unwrap because batches which aren't decoded up front come from an IPC file or stream
+        let ipc = self.ipc.as_ref().unwrap();
+        let block = &ipc.blocks[batch];
+        // the blocks were bounds checked on creation
+        let (offset, metadata_len, body_len) = extent(&ipc.bytes, block)?;
+        let parse = |err: ArrowError| TransducerError::Parse{ idx: self.starts[batch], offset, source: Box::new(err) };
+        let record = ipc.decoder.read_record_batch(block, &ipc.bytes.slice_with_length(offset, metadata_len + body_len))
+            .map_err(parse)?
+            .ok_or_else(|| parse(ArrowError::IpcError("the record batch is empty".to_string())))?;
+        let array = record.column(0).as_any().downcast_ref::<PrimitiveArray<T>>()
+            .ok_or_else(|| parse(ArrowError::IpcError(format!("the column does not have type {}", T::DATA_TYPE))))?;
+        Ok(self.batches[batch].get_or_init(|| array.clone()))
+    }
+    /// Get the value of a row out of the lazy transducer, returning `None` if the index is greater
+    /// than the number of rows in this lazy transducer, `Some(Ok(None))` if the row is null, and a
+    /// `Parse` error if its record batch fails to decode.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Result<Option<T::Native>, TransducerError>> {
+        if idx >= self.len() {
+            return None
+        }
+        let batch = range_containing(&self.starts, idx, |&start| start)?;
+        let row = idx - self.starts[batch];
+        Some(self.batch(batch).map(|array| if array.is_null(row) { None } else { Some(array.value(row)) }))
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the values, borrowing this one.
+    impl[T] ArrowColumn<T> => <'b> Result<Option<T::Native>, TransducerError>
+    where [T: ArrowPrimitiveType]
+    parallel where [T: ArrowPrimitiveType]
+}
@@ -0,0 +1,110 @@
+use std::borrow::Cow;
+use std::io;
+
+use {Source, TransducerError};
+use source::overflow;
+
+/// The value of a base64 character, in either the standard or the URL-safe alphabet
+fn sextet(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' | b'-' => Some(62),
+        b'/' | b'_' => Some(63),
+        _ => None,
+    }
+}
+
+/// An input source over base64 encoded bytes, e.g., a binary blob embedded in JSON or YAML, which
+/// decodes bytes on demand.
+///
+/// Every group of 4 base64 characters decodes to 3 bytes, so reading some bytes only decodes the
+/// groups containing them, and the encoded text is never decoded up front. Both the standard and
+/// the URL-safe alphabets are accepted, with or without padding; whitespace is not.
+///
+/// An invalid character is only detected when a group containing it is read, which is an `Io`
+/// error with kind `InvalidData`.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{Base64Source, ScrollTransducer, Endian};
+///
+/// # fn main() {
+/// // the little endian u32s 1, 2, 3
+/// let source = Base64Source::new(b"AQAAAAIAAAADAAAA").unwrap();
+/// assert_eq!(source.len(), 12);
+/// let lt: ScrollTransducer<u32, _, _> = ScrollTransducer::parse_with(&source, 3, Endian::Little).unwrap();
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Base64Source<'a> {
+    encoded: &'a [u8],
+    len: usize,
+}
+
+impl<'a> Base64Source<'a> {
+    /// Create a new base64 source over `encoded`, returning an error if its length is not that of
+    /// base64 text.
+    pub fn new(encoded: &'a [u8]) -> Result<Self, TransducerError> {
+        let mut end = encoded.len();
+        while end > 0 && encoded.len() - end < 2 && encoded[end - 1] == b'=' {
+            end -= 1;
+        }
+        let encoded = &encoded[..end];
+        let len = match encoded.len() % 4 {
+            0 => encoded.len() / 4 * 3,
+            1 => return Err(io::Error::new(io::ErrorKind::InvalidData, "truncated base64 text").into()),
+            rem => encoded.len() / 4 * 3 + rem - 1,
+        };
+        Ok(Base64Source { encoded, len })
+    }
+    /// The decoded size in bytes of the input source
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Whether the input source is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns the `len` decoded bytes starting at `offset`, returning an error if they are not all
+    /// in the input source, or are not valid base64.
+    pub fn read(&self, offset: usize, len: usize) -> Result<Vec<u8>, TransducerError> {
+        let end = offset.checked_add(len)
+            .filter(|end| *end <= self.len)
+            .ok_or_else(|| overflow(offset, len, self.len))?;
+        if len == 0 {
+            return Ok(Vec::new())
+        }
+        let (first, last) = (offset / 3, (end - 1) / 3);
+        let mut bytes = Vec::with_capacity((last - first + 1) * 3);
+        for (group, chars) in self.encoded[first * 4..].chunks(4).take(last - first + 1).enumerate() {
+            let mut bits: u32 = 0;
+            for (i, c) in chars.iter().enumerate() {
+                let sextet = sextet(*c).ok_or_else(|| {
+                    let msg = format!("invalid base64 character {:?} at offset {}", *c as char, (first + group) * 4 + i);
+                    TransducerError::from(io::Error::new(io::ErrorKind::InvalidData, msg))
+                })?;
+                bits |= (sextet as u32) << (18 - 6 * i);
+            }
+            bytes.extend_from_slice(&[(bits >> 16) as u8, (bits >> 8) as u8, bits as u8][..chars.len() - 1]);
+        }
+        let start = offset - first * 3;
+        bytes.truncate(start + len);
+        bytes.drain(..start);
+        Ok(bytes)
+    }
+}
+
+impl<'a> Source for Base64Source<'a> {
+    fn len(&self) -> usize {
+        Base64Source::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self.read(offset, len).map(Cow::Owned)
+    }
+}
@@ -0,0 +1,274 @@
+use std::borrow::Cow;
+use std::io::{self, Read};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use flate2::{bufread, read};
+
+use {Source, TransducerError};
+use cache::BlockCache;
+use source::overflow;
+use lazy_transducer::{capacity_for, range_containing};
+
+/// How many decompressed blocks are cached
+const CACHED_BLOCKS: usize = 8;
+
+fn invalid(msg: &str) -> TransducerError {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_owned()).into()
+}
+
+fn le16(bytes: &[u8]) -> usize {
+    bytes[0] as usize | (bytes[1] as usize) << 8
+}
+
+fn le32(bytes: &[u8]) -> usize {
+    le16(bytes) | le16(&bytes[2..]) << 16
+}
+
+/// The total size of the gzip member at the start of `bytes`, if its header has a BGZF `BC`
+/// subfield
+fn bgzf_block_size(bytes: &[u8]) -> Option<usize> {
+    // magic, deflate, and FEXTRA
+    if bytes.len() < 12 || bytes[0] != 31 || bytes[1] != 139 || bytes[2] != 8 || bytes[3] & 4 == 0 {
+        return None
+    }
+    let extra = bytes.get(12..12 + le16(&bytes[10..]))?;
+    let mut subfield = 0;
+    while subfield + 4 <= extra.len() {
+        let len = le16(&extra[subfield + 2..]);
+        if &extra[subfield..subfield + 2] == b"BC" && len == 2 {
+            return extra.get(subfield + 4..subfield + 6).map(|bsize| le16(bsize) + 1)
+        }
+        subfield += 4 + len;
+    }
+    None
+}
+
+/// A compressed input source over concatenated gzip members, e.g., BGZF files as used for
+/// genomic data, or rotated logs, where reading an element only decompresses the block (member)
+/// containing it.
+///
+/// The blocks are indexed at construction: BGZF blocks record their size in their header, so
+/// indexing them only reads the headers and trailers; other gzip members are decompressed once to
+/// find where they end. Alternatively, an existing BGZF index (`.gzi`) can be used with
+/// [with_index](#method.with_index).
+///
+/// The most recently used decompressed blocks are cached, and elements may straddle blocks.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// extern crate flate2;
+/// extern crate rayon;
+/// use lazy_transducer::{BgzfSource, ScrollTransducer, Endian};
+/// use rayon::prelude::*;
+/// use std::io::Write;
+///
+/// # fn main() {
+/// // three gzip members, each containing 100 little endian u32s
+/// let mut bytes = Vec::new();
+/// for block in 0..3u32 {
+///     let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+///     for n in block * 100..(block + 1) * 100 {
+///         encoder.write_all(&n.to_le_bytes()).unwrap();
+///     }
+///     bytes.extend(encoder.finish().unwrap());
+/// }
+///
+/// let source = BgzfSource::new(&bytes).unwrap();
+/// assert_eq!(source.len(), 1200);
+/// assert_eq!(source.blocks(), 3);
+///
+/// let lt: ScrollTransducer<u32, _, _> = ScrollTransducer::parse_with(&source, 300, Endian::Little).unwrap();
+/// assert_eq!(lt.get(250), Some(250));
+/// assert_eq!(source.decompressions(), 1);
+///
+/// let sum: u32 = lt.into_par_iter().sum();
+/// assert_eq!(sum, 44850);
+/// # }
+/// ```
+pub struct BgzfSource<'a> {
+    bytes: &'a [u8],
+    /// The compressed offset of each block, plus the end of the last block as a final entry
+    blocks: Vec<usize>,
+    /// The uncompressed offset of each block, plus the total uncompressed size as a final entry
+    starts: Vec<usize>,
+    /// The most recently used decompressed blocks
+    cache: Mutex<BlockCache>,
+    decompressions: AtomicUsize,
+}
+
+impl<'a> ::std::fmt::Debug for BgzfSource<'a> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("BgzfSource")
+            .field("len", &self.len())
+            .field("blocks", &self.blocks())
+            .field("memory_budget", &self.memory_budget())
+            .field("resident_bytes", &self.resident_bytes())
+            .field("decompressions", &self.decompressions())
+            .finish()
+    }
+}
+
+impl<'a> BgzfSource<'a> {
+    fn from_blocks(bytes: &'a [u8], blocks: Vec<usize>, starts: Vec<usize>) -> Self {
+        BgzfSource { bytes, blocks, starts, cache: Mutex::new(BlockCache::new(CACHED_BLOCKS)), decompressions: AtomicUsize::new(0) }
+    }
+    /// Create a new compressed source over the concatenated gzip members in `bytes`, returning an
+    /// error if they are malformed.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        let mut blocks = vec![0];
+        let mut starts = vec![0];
+        let mut offset = 0;
+        let mut start: usize = 0;
+        while offset < bytes.len() {
+            let rest = &bytes[offset..];
+            let (size, isize) = match bgzf_block_size(rest) {
+                Some(size) if size >= 18 && size <= rest.len() => (size, le32(&rest[size - 4..size])),
+                Some(_) => return Err(invalid("truncated BGZF block")),
+                None => {
+                    let mut decoder = bufread::GzDecoder::new(rest);
+                    let isize = io::copy(&mut decoder, &mut io::sink())? as usize;
+                    (rest.len() - decoder.into_inner().len(), isize)
+                }
+            };
+            offset += size;
+            start = start.checked_add(isize).ok_or(invalid("uncompressed size overflows"))?;
+            blocks.push(offset);
+            starts.push(start);
+        }
+        Ok(Self::from_blocks(bytes, blocks, starts))
+    }
+    /// Create a new compressed source over the BGZF blocks in `bytes`, using the BGZF index `gzi`
+    /// instead of reading every block header, returning an error if the index is malformed.
+    pub fn with_index(bytes: &'a [u8], gzi: &[u8]) -> Result<Self, TransducerError> {
+        let u64_at = |offset: usize| -> Result<usize, TransducerError> {
+            let field = gzi.get(offset..offset + 8).ok_or(invalid("truncated BGZF index"))?;
+            let value = field.iter().rev().fold(0u64, |value, byte| value << 8 | *byte as u64);
+            if value > usize::MAX as u64 {
+                return Err(invalid("BGZF index offset overflows a usize"))
+            }
+            Ok(value as usize)
+        };
+        let count = u64_at(0)?;
+        // the first block is implicit
+        let capacity = capacity_for(count, gzi.len(), 16) + 2;
+        let (mut blocks, mut starts) = (Vec::with_capacity(capacity), Vec::with_capacity(capacity));
+        blocks.push(0);
+        starts.push(0);
+        for entry in 0..count {
+            let (offset, start) = (u64_at(8 + entry * 16)?, u64_at(16 + entry * 16)?);
+            if offset <= blocks[blocks.len() - 1] || offset > bytes.len() || start < starts[starts.len() - 1] {
+                return Err(invalid("BGZF index is not sorted, or is past the end of the input source"))
+            }
+            blocks.push(offset);
+            starts.push(start);
+        }
+        // the uncompressed size of the last block is in its trailer
+        let last = blocks[blocks.len() - 1];
+        if bytes.len() - last < 18 {
+            return Err(invalid("truncated BGZF block"))
+        }
+        let total = starts[starts.len() - 1] + le32(&bytes[bytes.len() - 4..]);
+        blocks.push(bytes.len());
+        starts.push(total);
+        Ok(Self::from_blocks(bytes, blocks, starts))
+    }
+    /// Limit the cached decompressed blocks to `budget` bytes in total, evicting the least
+    /// recently used blocks first, so that walking a huge input source stays within a fixed amount
+    /// of memory; a block larger than the budget is decompressed every time it is accessed.
+    pub fn with_memory_budget(mut self, budget: usize) -> Self {
+        self.cache.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner()).set_budget(budget);
+        self
+    }
+    /// The maximum number of bytes of cached decompressed blocks, which is unlimited unless set
+    /// with [with_memory_budget](#method.with_memory_budget)
+    pub fn memory_budget(&self) -> usize {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).budget()
+    }
+    /// The number of bytes of decompressed blocks currently cached
+    pub fn resident_bytes(&self) -> usize {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).resident()
+    }
+    /// The uncompressed size in bytes of the input source
+    pub fn len(&self) -> usize {
+        self.starts[self.starts.len() - 1]
+    }
+    /// Whether the input source is empty when uncompressed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// How many blocks the input source contains
+    pub fn blocks(&self) -> usize {
+        self.blocks.len() - 1
+    }
+    /// How many blocks have been decompressed so far
+    pub fn decompressions(&self) -> usize {
+        self.decompressions.load(Ordering::Relaxed)
+    }
+    /// Returns the `n`th block, decompressed, decompressing it if it isn't cached.
+    pub fn block(&self, n: usize) -> Result<Arc<Vec<u8>>, TransducerError> {
+        if n >= self.blocks() {
+            return Err(TransducerError::OutOfBounds{ idx: n, len: self.blocks() })
+        }
+        if let Some(block) = self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(n as u64) {
+            return Ok(block)
+        }
+        // the cache isn't locked while decompressing, so other blocks can be accessed meanwhile
+        let size = self.starts[n + 1] - self.starts[n];
+        let mut block = Vec::with_capacity(size);
+        read::GzDecoder::new(&self.bytes[self.blocks[n]..self.blocks[n + 1]]).read_to_end(&mut block)?;
+        if block.len() != size {
+            return Err(invalid("block size does not match the index"))
+        }
+        self.decompressions.fetch_add(1, Ordering::Relaxed);
+        let block = Arc::new(block);
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(n as u64, block.clone());
+        Ok(block)
+    }
+    /// The block containing the uncompressed byte at `offset`, which must be in bounds
+    fn block_of(&self, offset: usize) -> usize {
+        // this skips over any empty blocks, e.g., the BGZF end of file marker
+        range_containing(&self.starts, offset, |&start| start).expect("the offset is in bounds")
+    }
+    /// Call `f` with the `len` uncompressed bytes starting at `offset`, returning an error if they
+    /// are not all in the input source.
+    ///
+    /// The bytes are borrowed straight out of the cache if they are in a single block, and are
+    /// otherwise copied out of every block they span.
+    pub fn with_bytes<T, F>(&self, offset: usize, len: usize, f: F) -> Result<T, TransducerError>
+        where F: FnOnce(&[u8]) -> T
+    {
+        let end = offset.checked_add(len)
+            .filter(|end| *end <= self.len())
+            .ok_or_else(|| overflow(offset, len, self.len()))?;
+        if len == 0 {
+            return Ok(f(&[]))
+        }
+        let (first, last) = (self.block_of(offset), self.block_of(end - 1));
+        if first == last {
+            let start = offset - self.starts[first];
+            let block = self.block(first)?;
+            return Ok(f(&block[start..start + len]))
+        }
+        let mut bytes = Vec::with_capacity(len);
+        for n in first..=last {
+            let block = self.block(n)?;
+            let from = offset.saturating_sub(self.starts[n]);
+            let to = ::std::cmp::min(block.len(), end - self.starts[n]);
+            bytes.extend_from_slice(&block[from..to]);
+        }
+        Ok(f(&bytes))
+    }
+}
+
+impl<'a> Source for BgzfSource<'a> {
+    fn len(&self) -> usize {
+        BgzfSource::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self.with_bytes(offset, len, |bytes| Cow::Owned(bytes.to_vec()))
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+
+use bincode;
+use serde::de::DeserializeOwned;
+
+use source::overflow;
+use TransducerError;
+
+/// Where the elements of a bincode transducer lie
+#[derive(Debug, Clone)]
+enum Layout {
+    /// Every element is encoded in exactly `size` bytes
+    Fixed{ size: usize, count: usize },
+    /// The offset of every element, plus the end of the last element as a final entry
+    Offsets(Vec<usize>),
+}
+
+/// A lazy transducer over a table of [bincode](https://docs.rs/bincode) serialized values, which
+/// deserializes an element only when it is accessed.
+///
+/// If every element has the same encoded size, e.g., a struct of integers, floats, and bools, the
+/// size is probed by deserializing the first element; otherwise, e.g., for elements containing
+/// strings or vectors, the offset of every element must be provided, typically from a table
+/// written alongside the elements.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate bincode;
+/// extern crate rayon;
+/// #[macro_use]
+/// extern crate serde_derive;
+/// use lazy_transducer::BincodeTransducer;
+/// use bincode::serialize;
+/// use rayon::prelude::*;
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Foo {
+///     x: u64,
+///     y: f32,
+///     z: bool,
+/// }
+///
+/// # fn main() {
+/// let mut data = serialize(&Foo { x: 0xcafed00d, y: 0.75, z: false }).unwrap();
+/// data.extend_from_slice(&serialize(&Foo { x: 0xdeadbeef, y: 0.50, z: true }).unwrap());
+///
+/// let lt: BincodeTransducer<Foo> = BincodeTransducer::fixed(&data).unwrap();
+/// assert_eq!(lt.len(), 2);
+/// assert_eq!(lt.get(1).unwrap().unwrap(), Foo { x: 0xdeadbeef, y: 0.50, z: true });
+/// let zs = lt.into_par_iter().filter(|foo| foo.as_ref().unwrap().z).count();
+/// assert_eq!(zs, 1);
+///
+/// // strings have a variable size, so their offsets are provided
+/// let mut data = serialize(&"hello".to_string()).unwrap();
+/// let offsets = vec![0, data.len()];
+/// data.extend_from_slice(&serialize(&"world!".to_string()).unwrap());
+/// let lt: BincodeTransducer<String> = BincodeTransducer::with_offsets(&data, offsets).unwrap();
+/// assert_eq!(lt.get(1).unwrap().unwrap(), "world!");
+/// # }
+/// ```
+pub struct BincodeTransducer<'a, T> {
+    bytes: &'a [u8],
+    layout: Layout,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> fmt::Debug for BincodeTransducer<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BincodeTransducer")
+            .field("bytes", &self.bytes)
+            .field("layout", &self.layout)
+            .finish()
+    }
+}
+
+impl<'a, T> Clone for BincodeTransducer<'a, T> {
+    fn clone(&self) -> Self {
+        BincodeTransducer { bytes: self.bytes, layout: self.layout.clone(), _marker: PhantomData }
+    }
+}
+
+impl<'a, T: DeserializeOwned> BincodeTransducer<'a, T> {
+    /// Create a new lazy transducer over the elements of `bytes`, every one of which is encoded in
+    /// the same number of bytes as the first; any trailing bytes are ignored. Returns a `Parse`
+    /// error if the first element fails to deserialize, and an `InvalidInput` I/O error if it is
+    /// encoded in zero bytes.
+    pub fn fixed(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        if bytes.is_empty() {
+            return Ok(BincodeTransducer { bytes, layout: Layout::Fixed{ size: 0, count: 0 }, _marker: PhantomData })
+        }
+        let mut rest = bytes;
+        let _: T = bincode::deserialize_from(&mut rest)
+            .map_err(|err| TransducerError::Parse{ idx: 0, offset: 0, source: Box::new(err) })?;
+        let size = bytes.len() - rest.len();
+        if size == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "elements are encoded in zero bytes").into())
+        }
+        Ok(BincodeTransducer { bytes, layout: Layout::Fixed{ size, count: bytes.len() / size }, _marker: PhantomData })
+    }
+    /// Create a new lazy transducer over the elements of `bytes` starting at `offsets`, each of
+    /// which ends where the next one starts, and the last of which ends at the end of `bytes`.
+    /// Returns an `InvalidInput` I/O error if the offsets are not sorted, and an `ElementOverflow`
+    /// error if one is past the end of `bytes`.
+    pub fn with_offsets(bytes: &'a [u8], mut offsets: Vec<usize>) -> Result<Self, TransducerError> {
+        if offsets.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "offsets are not sorted").into())
+        }
+        if let Some(&last) = offsets.last() {
+            if last > bytes.len() {
+                return Err(overflow(last, 1, bytes.len()))
+            }
+        }
+        offsets.push(bytes.len());
+        Ok(BincodeTransducer { bytes, layout: Layout::Offsets(offsets), _marker: PhantomData })
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        match self.layout {
+            Layout::Fixed{ count, .. } => count,
+            Layout::Offsets(ref offsets) => offsets.len() - 1,
+        }
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th element, or `None` if the index is greater than the
+    /// number of elements.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len() {
+            return None
+        }
+        match self.layout {
+            Layout::Fixed{ size, .. } => Some(idx * size),
+            Layout::Offsets(ref offsets) => Some(offsets[idx]),
+        }
+    }
+    /// Returns the serialized bytes of the `idx`th element, or `None` if the index is greater than
+    /// the number of elements.
+    pub fn element(&self, idx: usize) -> Option<&'a [u8]> {
+        let offset = self.offset_of(idx)?;
+        match self.layout {
+            Layout::Fixed{ size, .. } => Some(&self.bytes[offset..offset + size]),
+            Layout::Offsets(ref offsets) => Some(&self.bytes[offset..offsets[idx + 1]]),
+        }
+    }
+    /// Deserialize an element out of the lazy transducer, returning `None` if the index is greater
+    /// than the number of elements in this lazy transducer, and a `Parse` error if it fails to
+    /// deserialize.
+    pub fn get(&self, idx: usize) -> Option<Result<T, TransducerError>> {
+        self.element(idx).map(|element| {
+            bincode::deserialize(element).map_err(|err| TransducerError::Parse{ idx, offset: self.offset_of(idx).unwrap(), source: Box::new(err) })
+        })
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the deserialized elements, borrowing this one.
+    impl['a, T] BincodeTransducer<'a, T> => <'b> Result<T, TransducerError>
+    where [T: DeserializeOwned]
+    parallel where [T: DeserializeOwned + Send + Sync]
+}
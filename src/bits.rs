@@ -0,0 +1,119 @@
+use std::io;
+
+use rayon::prelude::*;
+
+use {IntoIter, IntoParIter, LazyTransducer, TransducerError};
+
+/// The order in which the bits of a [BitTransducer](struct.BitTransducer.html) are packed into its
+/// bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Fields are packed from the most significant bit of every byte, and their first bit is their
+    /// most significant, as in most codec bitstreams, e.g., H.264 or MPEG audio
+    MsbFirst,
+    /// Fields are packed from the least significant bit of every byte, and their first bit is their
+    /// least significant, as in DEFLATE or most packed sensor data
+    LsbFirst,
+}
+
+/// A lazy transducer over an array of `width`-bit fields packed back to back, e.g., 12-bit sensor
+/// samples, which yields every field as a `u64`, no matter which bytes it straddles.
+///
+/// Every complete field in the bytes is an element; any trailing bits which are too few for
+/// another field are ignored.
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::{BitTransducer, BitOrder};
+///
+/// // the 12-bit samples 0xabc and 0x123
+/// let bytes = [0xab, 0xc1, 0x23];
+/// let lt = BitTransducer::new(&bytes, 12, BitOrder::MsbFirst).unwrap();
+/// assert_eq!(lt.len(), 2);
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![0xabc, 0x123]);
+///
+/// // the 3-bit fields 5, 1, 7, 0, and 0, with 1 bit left over
+/// let lt = BitTransducer::new(&[0b1100_1101, 0b0000_0001], 3, BitOrder::LsbFirst).unwrap();
+/// assert_eq!(lt.len(), 5);
+/// assert_eq!(lt.get(2), Some(0b111));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BitTransducer<'a> {
+    bytes: &'a [u8],
+    width: usize,
+    order: BitOrder,
+}
+
+impl<'a> BitTransducer<'a> {
+    /// Create a new lazy transducer over the `width`-bit fields in `bytes`, packed in `order`,
+    /// returning an `InvalidInput` I/O error if `width` is not from 1 to 64.
+    pub fn new(bytes: &'a [u8], width: usize, order: BitOrder) -> Result<Self, TransducerError> {
+        if width == 0 || width > 64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "bit width must be from 1 to 64").into())
+        }
+        Ok(BitTransducer { bytes, width, order })
+    }
+    /// The size in bits of every field
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        // the number of bits in a slice may not fit in a usize
+        (self.bytes.len() as u128 * 8 / self.width as u128) as usize
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the bit offset of the `idx`th field, or `None` if the index is greater than the
+    /// number of elements.
+    pub fn bit_offset_of(&self, idx: usize) -> Option<u64> {
+        if idx >= self.len() { None } else { Some(idx as u64 * self.width as u64) }
+    }
+    /// Get an element out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of elements in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<u64> {
+        let start = self.bit_offset_of(idx)?;
+        let shift = (start % 8) as usize;
+        let first = (start / 8) as usize;
+        // a field of at most 64 bits straddles at most 9 bytes
+        let bytes = &self.bytes[first..first + (shift + self.width).div_ceil(8)];
+        let mask = (1u128 << self.width) - 1;
+        let field = match self.order {
+            BitOrder::MsbFirst => {
+                let bits = bytes.iter().fold(0u128, |bits, byte| bits << 8 | *byte as u128);
+                bits >> (bytes.len() * 8 - shift - self.width)
+            },
+            BitOrder::LsbFirst => {
+                let bits = bytes.iter().rev().fold(0u128, |bits, byte| bits << 8 | *byte as u128);
+                bits >> shift
+            },
+        };
+        Some((field & mask) as u64)
+    }
+    /// Returns a lazy transducer over the elements.
+    pub fn transducer(&self) -> LazyTransducer<'a, Self, u64> {
+        LazyTransducer::new(*self, self.len(), |lt, idx| lt.get(idx).unwrap())
+    }
+}
+
+impl<'a> IntoIterator for BitTransducer<'a> {
+    type Item = u64;
+    type IntoIter = IntoIter<'a, BitTransducer<'a>, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.transducer().into_iter()
+    }
+}
+
+impl<'a> IntoParallelIterator for BitTransducer<'a> {
+    type Iter = IntoParIter<'a, BitTransducer<'a>, u64>;
+    type Item = u64;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.transducer().into_par_iter()
+    }
+}
@@ -0,0 +1,93 @@
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+/// A lazy transducer whose transducer is a boxed closure, which unlike a `fn` can capture its
+/// environment, e.g., configuration which is only known at runtime.
+///
+/// The closure is reference counted, so cloning a boxed transducer is cheap.
+///
+/// Iteration (sequential and parallel) goes through a lazy transducer borrowing this one; see
+/// [transducer](#method.transducer).
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::BoxedTransducer;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let data = [1u32, 2, 3, 4];
+/// let scale = 10;
+/// let lt = BoxedTransducer::new(&data[..], data.len(), move |input, idx| input[idx] * scale);
+/// assert_eq!(lt.get(3), Some(40));
+///
+/// let sum: u32 = lt.into_par_iter().sum();
+/// assert_eq!(sum, 100);
+/// # }
+/// ```
+pub struct BoxedTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    contents: Input,
+    count: usize,
+    transducer: Arc<dyn Fn(Input, usize) -> Output + Send + Sync + 'a>,
+}
+
+impl<'a, Input, Output> BoxedTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    /// Create a new boxed lazy transducer, with `count` elements in `contents`, using `transducer`
+    /// to extract them.
+    pub fn new<F>(contents: Input, count: usize, transducer: F) -> Self
+        where F: Fn(Input, usize) -> Output + Send + Sync + 'a
+    {
+        BoxedTransducer { contents, count, transducer: Arc::new(transducer) }
+    }
+    pub(crate) fn from_shared(contents: Input, count: usize, transducer: Arc<dyn Fn(Input, usize) -> Output + Send + Sync + 'a>) -> Self {
+        BoxedTransducer { contents, count, transducer }
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.count
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// Get an element out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of elements in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Output> {
+        if idx >= self.count {
+            None
+        } else {
+            Some((self.transducer)(self.contents, idx))
+        }
+    }
+}
+
+impl<'a, Input: Copy, Output> Clone for BoxedTransducer<'a, Input, Output> {
+    fn clone(&self) -> Self {
+        BoxedTransducer { contents: self.contents, count: self.count, transducer: self.transducer.clone() }
+    }
+}
+
+impl<'a, Input: Copy + Debug, Output> Debug for BoxedTransducer<'a, Input, Output> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoxedTransducer")
+            .field("contents", &self.contents)
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the elements, borrowing this boxed transducer.
+    impl['a, Input, Output] BoxedTransducer<'a, Input, Output> => <'b> Output
+    where [Input: Copy]
+    parallel where [Input: Sync + Send + Copy, Output: Send + Sync]
+}
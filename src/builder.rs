@@ -1,15 +1,79 @@
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
+#[cfg(feature = "scroll")]
 use scroll::{self, ctx};
+#[cfg(feature = "scroll")]
 use scroll::ctx::SizeWith;
-use failure::Error;
 
-use {LazyTransducer, ScrollTransducer, TransducerError};
+use {BoxedTransducer, LazyTransducer, Source, TransducerError, TruncationPolicy, TryLazyTransducer};
+#[cfg(feature = "scroll")]
+use ScrollTransducer;
+use lazy_transducer::{strided_count, strided_size};
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::NeedsInput {}
+    impl Sealed for super::NeedsTransducer {}
+    impl Sealed for super::Ready {}
+    impl Sealed for super::ReadyBoxed {}
+}
+
+/// The state of a [Builder](struct.Builder.html), which determines what it holds: the input source
+/// and the transducer are only present in the states where they have been given.
+pub trait BuilderState<'a, Input, Output>: sealed::Sealed {
+    /// The input source, or `()` if it has not been given yet
+    type Input: Clone;
+    /// The transducer, or `()` if it has not been given yet
+    type Transducer: Clone;
+}
+
+/// The builder state before an input source has been given
+#[derive(Debug, Clone, Copy)]
+pub struct NeedsInput;
+
+/// The builder state once the input source is given, but before the transducer has been given
+#[derive(Debug, Clone, Copy)]
+pub struct NeedsTransducer;
+
+/// The builder state once both the input source and the transducer are given; only now can the
+/// builder be finished
+#[derive(Debug, Clone, Copy)]
+pub struct Ready;
+
+/// The builder state once both the input source and a boxed transducer are given; finishing the
+/// builder returns a [BoxedTransducer](struct.BoxedTransducer.html)
+#[derive(Debug, Clone, Copy)]
+pub struct ReadyBoxed;
+
+impl<'a, Input, Output> BuilderState<'a, Input, Output> for NeedsInput {
+    type Input = ();
+    type Transducer = ();
+}
+
+impl<'a, Input: Copy, Output> BuilderState<'a, Input, Output> for NeedsTransducer {
+    type Input = Input;
+    type Transducer = ();
+}
+
+impl<'a, Input: Copy, Output> BuilderState<'a, Input, Output> for Ready {
+    type Input = Input;
+    type Transducer = fn(Input, usize) -> Output;
+}
+
+impl<'a, Input: Copy, Output> BuilderState<'a, Input, Output> for ReadyBoxed {
+    type Input = Input;
+    type Transducer = Arc<dyn Fn(Input, usize) -> Output + Send + Sync + 'a>;
+}
 
 /// A builder is useful for when the transducer needs to be constructed incrementally, i.e.,
 /// certain information is present later on, or is optional, etc.
 ///
+/// The builder's `State` tracks whether the input source and the transducer have been given, so
+/// forgetting either is a compile time error instead of a runtime one: `finish` only exists once
+/// the builder is [Ready](struct.Ready.html).
+///
 /// # Example
 ///
 /// ```rust
@@ -29,85 +93,414 @@ use {LazyTransducer, ScrollTransducer, TransducerError};
 ///     let start = size_of::<u16>() * index;
 ///     unsafe { *transmute::<_, &u16>(&input[start]) }
 ///   })
-///   .finish()
-///   .unwrap();
+///   .finish();
 ///
 ///  // note: the data will be 1, 2, 3, 4, for little-endian machines, but not for big-endian
 /// for (i, n) in lt.into_iter().enumerate() {
 ///   println!("{}: {}", i, n);
 /// }
 /// ```
-pub struct Builder<'a, Input, Output>
+///
+/// Builders are `Clone`, so a configured builder can be used as a template to stamp out many
+/// transducers over different inputs:
+///
+/// ```rust
+/// use lazy_transducer::{LazyTransducer, Builder};
+///
+/// let (a, b) = ([1u8, 2], [3u8, 4, 5]);
+/// let template = Builder::new(&a[..]).transducer(|input, index| input[index] * 2);
+/// let lt_a: LazyTransducer<_, u8> = template.clone().count(2).finish();
+/// let lt_b: LazyTransducer<_, u8> = template.clone().input(&b[..]).count(3).finish();
+/// assert_eq!(lt_a.get(1), Some(4));
+/// assert_eq!(lt_b.get(2), Some(10));
+/// ```
+///
+/// Finishing a builder which is missing its transducer does not compile:
+///
+/// ```compile_fail
+/// use lazy_transducer::{LazyTransducer, Builder};
+///
+/// let bytes: Vec<u8> = vec![1u8, 0, 2, 0, 3, 0, 4, 0];
+/// let lt: LazyTransducer<_, u8> = Builder::new(&bytes).count(8).finish();
+/// ```
+pub struct Builder<'a, Input, Output, State>
     where Input: 'a + Copy,
-          Output: 'a {
-    input: Option<Input>,
+          Output: 'a,
+          State: BuilderState<'a, Input, Output> {
+    input: State::Input,
     count: usize,
-    transducer: Option<fn(Input, usize) -> Output>,
-    _marker: PhantomData<&'a (Input, Output)>,
+    offset: usize,
+    sizeof_element: usize,
+    stride: usize,
+    transducer: State::Transducer,
+    policy: TruncationPolicy,
+    _marker: PhantomData<&'a (Input, Output, State)>,
+}
+
+impl<'a, Input, Output, State> Clone for Builder<'a, Input, Output, State>
+    where Input: 'a + Copy,
+          Output: 'a,
+          State: BuilderState<'a, Input, Output> {
+    fn clone(&self) -> Self {
+        Builder {
+            input: self.input.clone(),
+            count: self.count,
+            offset: self.offset,
+            sizeof_element: self.sizeof_element,
+            stride: self.stride,
+            transducer: self.transducer.clone(),
+            policy: self.policy,
+            _marker: PhantomData,
+        }
+    }
 }
 
-impl<'a, Input, Output> Builder<'a, Input, Output>
+impl<'a, Input, Output> Builder<'a, Input, Output, NeedsInput>
     where Input: 'a + Copy,
           Output: 'a {
-    /// Creates an empty builder; you must set the input and transducer before calling `finish`
-    /// otherwise this is a runtime error.
+    /// Creates an empty builder; you must set the input and transducer before you can call `finish`.
     pub fn empty() -> Self {
         Builder {
-            input: None,
+            input: (),
             count: 0,
-            transducer: None,
-            _marker: PhantomData::default(),
+            offset: 0,
+            sizeof_element: 0,
+            stride: 0,
+            transducer: (),
+            policy: TruncationPolicy::default(),
+            _marker: PhantomData,
         }
     }
-    /// Create a new builder with the given `input`; you must set the transducer before calling `finish`
-    /// otherwise this is a runtime error.
+    /// Set the input.
+    pub fn input(self, input: Input) -> Builder<'a, Input, Output, NeedsTransducer> {
+        self.into_state(input, ())
+    }
+}
+
+impl<'a, Input, Output> Builder<'a, Input, Output, NeedsTransducer>
+    where Input: 'a + Copy,
+          Output: 'a {
+    /// Create a new builder with the given `input`; you must set the transducer before you can call `finish`.
     pub fn new(input: Input) -> Self {
-        Builder {
-            input: Some(input),
-            count: 0,
-            transducer: None,
-            _marker: PhantomData::default(),
+        Builder::empty().input(input)
+    }
+    /// Reset the input.
+    pub fn input(mut self, input: Input) -> Self {
+        self.input = input;
+        self
+    }
+    /// Set the transducer from input source to output elements.
+    pub fn transducer(self, transducer: fn(Input, usize) -> Output) -> Builder<'a, Input, Output, Ready> {
+        let input = self.input;
+        self.into_state(input, transducer)
+    }
+    /// Set a boxed transducer from input source to output elements, which unlike a `fn` can
+    /// capture its environment; finishing the builder then returns a
+    /// [BoxedTransducer](struct.BoxedTransducer.html).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{BoxedTransducer, Builder};
+    ///
+    /// let data = [1u32, 2, 3, 4];
+    /// let bias = 100;
+    /// let lt: BoxedTransducer<_, u32> = Builder::new(&data)
+    ///   .count(4)
+    ///   .transducer_boxed(move |input, index| input[index] + bias)
+    ///   .finish();
+    /// assert_eq!(lt.get(0), Some(101));
+    /// ```
+    pub fn transducer_boxed<F>(self, transducer: F) -> Builder<'a, Input, Output, ReadyBoxed>
+        where F: Fn(Input, usize) -> Output + Send + Sync + 'a
+    {
+        let input = self.input;
+        let transducer: Arc<dyn Fn(Input, usize) -> Output + Send + Sync + 'a> = Arc::new(transducer);
+        self.into_state(input, transducer)
+    }
+}
+
+impl<'a, Input, Output> Builder<'a, Input, Output, Ready>
+    where Input: 'a + Copy,
+          Output: 'a {
+    /// Reset the input.
+    pub fn input(mut self, input: Input) -> Self {
+        self.input = input;
+        self
+    }
+    /// Reset the transducer from input source to output elements.
+    pub fn transducer(mut self, transducer: fn(Input, usize) -> Output) -> Self {
+        self.transducer = transducer;
+        self
+    }
+    /// Finish building the lazy transducer, and return it.
+    pub fn finish(self) -> LazyTransducer<'a, Input, Output> {
+        LazyTransducer {
+                contents: self.input,
+                count: self.count,
+                transducer: self.transducer,
+                _marker: PhantomData,
+        }
+    }
+    /// Finish building the lazy transducer like [finish](#method.finish), but first check that the
+    /// elements fit in the input [source](trait.Source.html), i.e., that `offset + (count - 1) * stride + sizeof_element`
+    /// does not exceed its length, returning an `ElementOverflow` error otherwise.
+    ///
+    /// No check is performed if the element size has not been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{LazyTransducer, Builder};
+    ///
+    /// let bytes = [1u8, 0, 2, 0, 3, 0];
+    /// let builder = Builder::new(&bytes).sizeof_element(2).transducer(|input, index| input[index * 2]);
+    /// assert!(builder.count(3).finish_checked().is_ok());
+    ///
+    /// let builder = Builder::new(&bytes).sizeof_element(2).transducer(|input, index| input[index * 2]);
+    /// assert!(builder.offset(2).count(3).finish_checked().is_err());
+    /// ```
+    pub fn finish_checked(self) -> Result<LazyTransducer<'a, Input, Output>, TransducerError>
+        where Input: Source
+    {
+        if self.sizeof_element != 0 {
+            let src_size = self.input.len().saturating_sub(self.offset);
+            let stride = self.stride_or(self.sizeof_element);
+            if strided_size(self.count, stride, self.sizeof_element)? > src_size {
+                return Err(TransducerError::ElementOverflow{ nelements: self.count, sizeof_element: self.sizeof_element, stride, src_size })
+            }
         }
+        Ok(self.finish())
     }
-    /// Set (or reset) the input.
+}
+
+impl<'a, Input, Output, Error> Builder<'a, Input, Result<Output, Error>, Ready>
+    where Input: 'a + Copy,
+          Output: 'a,
+          Error: 'a {
+    /// Finish building a fallible lazy transducer, but first do a dry run by extracting the first
+    /// and last elements, returning the index and error of the first of them which fails, e.g.,
+    /// because the count or stride is wrong and an index is out of bounds.
+    ///
+    /// This catches obviously wrong configurations at construction time, rather than deep inside an
+    /// iteration.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::Builder;
+    ///
+    /// let data = [1u32, 2, 3];
+    /// let builder = Builder::new(&data).transducer(|input, index| input.get(index).cloned().ok_or("out of bounds"));
+    /// assert!(builder.clone().count(3).finish_validated().is_ok());
+    /// assert_eq!(builder.count(4).finish_validated().err(), Some((3, "out of bounds")));
+    /// ```
+    pub fn finish_validated(self) -> Result<TryLazyTransducer<'a, Input, Output, Error>, (usize, Error)> {
+        let lt = TryLazyTransducer::new(self.input, self.count, self.transducer);
+        if !lt.is_empty() {
+            for &idx in &[0, lt.len() - 1] {
+                if let Some(Err(err)) = lt.get(idx) {
+                    return Err((idx, err))
+                }
+            }
+        }
+        Ok(lt)
+    }
+}
+
+impl<'a, Input, Output> Builder<'a, Input, Output, ReadyBoxed>
+    where Input: 'a + Copy,
+          Output: 'a {
+    /// Reset the input.
     pub fn input(mut self, input: Input) -> Self {
-        self.input = Some(input);
+        self.input = input;
         self
     }
+    /// Finish building the boxed lazy transducer, and return it.
+    pub fn finish(self) -> BoxedTransducer<'a, Input, Output> {
+        BoxedTransducer::from_shared(self.input, self.count, self.transducer)
+    }
+}
+
+impl<'a, Input, Output, State> Builder<'a, Input, Output, State>
+    where Input: 'a + Copy,
+          Output: 'a,
+          State: BuilderState<'a, Input, Output> {
+    /// Move the settings into a builder in `NewState`, which holds the given input and transducer
+    fn into_state<NewInput, NewState>(self, input: NewState::Input, transducer: NewState::Transducer) -> Builder<'a, NewInput, Output, NewState>
+        where NewInput: 'a + Copy,
+              NewState: BuilderState<'a, NewInput, Output>
+    {
+        Builder {
+            input,
+            count: self.count,
+            offset: self.offset,
+            sizeof_element: self.sizeof_element,
+            stride: self.stride,
+            transducer,
+            policy: self.policy,
+            _marker: PhantomData,
+        }
+    }
     /// Set the number of output elements in the input source.
     pub fn count(mut self, count: usize) -> Self {
         self.count = count;
         self
     }
-    /// Set the transducer from input source to output elements.
-    pub fn transducer(mut self, transducer: fn(Input, usize) -> Output) -> Self {
-        self.transducer = Some(transducer);
+    /// Set the byte offset in the input source at which the table of output elements starts; this
+    /// is 0 by default.
+    ///
+    /// The offset is used when parsing with scroll, where index 0 is parsed at `offset`, and when
+    /// inferring the count; a custom transducer still receives the whole input source.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
         self
     }
-    /// Finish building the lazy transducer, and return it; if the input source or the transducer is missing
-    /// this is a runtime error.
-    pub fn finish(self) -> Result<LazyTransducer<'a, Input, Output>, Error> {
-        let contents = self.input.ok_or(TransducerError::BuilderError("No input given".to_string()))?;
-        let transducer = self.transducer.ok_or(TransducerError::BuilderError("No transducer given".to_string()))?;
-        Ok(LazyTransducer {
-                contents,
-                count: self.count,
-                transducer,
-                _marker: PhantomData::default(),
-        })
+    /// Set the size in bytes of every output element in the input source, for use with
+    /// `infer_count`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sizeof_element` is zero.
+    pub fn sizeof_element(mut self, sizeof_element: usize) -> Self {
+        assert!(sizeof_element != 0, "element size must be non-zero");
+        self.sizeof_element = sizeof_element;
+        self
+    }
+    /// Set the distance in bytes between the starts of consecutive records in the input source,
+    /// for records with trailing padding or interleaved layouts; the `i`th element then starts at
+    /// `offset + i * stride`. By default, this is the element size.
+    ///
+    /// The stride is used when parsing with scroll and when inferring the count; a custom
+    /// transducer must account for it itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn stride(mut self, stride: usize) -> Self {
+        assert!(stride != 0, "stride must be non-zero");
+        self.stride = stride;
+        self
+    }
+    fn stride_or(&self, sizeof_element: usize) -> usize {
+        if self.stride == 0 { sizeof_element } else { self.stride }
+    }
+    /// Set the policy for when the count exceeds what fits in the input source; this is `Strict` by default.
+    pub fn policy(mut self, policy: TruncationPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+impl<'a, Input, Output, State> Builder<'a, Input, Output, State>
+    where Input: 'a + Copy + Source,
+          Output: 'a,
+          State: BuilderState<'a, Input, Output, Input = Input> {
+    /// Set the number of output elements to however many elements of `sizeof_element` bytes
+    /// completely fit in the input after the offset, taking the stride into account; any trailing
+    /// bytes are ignored.
+    ///
+    /// Returns a `BuilderError` if `sizeof_element` has not been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{LazyTransducer, Builder};
+    ///
+    /// // 3 complete `u16`s, and a trailing byte
+    /// let bytes: Vec<u8> = vec![1u8, 0, 2, 0, 3, 0, 4];
+    /// let lt: LazyTransducer<_, u16> = Builder::new(&bytes)
+    ///   .sizeof_element(2)
+    ///   .infer_count().unwrap()
+    ///   .transducer(|input, index| input[index * 2] as u16 | (input[index * 2 + 1] as u16) << 8)
+    ///   .finish();
+    /// assert_eq!(lt.len(), 3);
+    ///
+    /// assert!(Builder::<_, u16, _>::new(&bytes).infer_count().is_err());
+    /// ```
+    pub fn infer_count(mut self) -> Result<Self, TransducerError> {
+        if self.sizeof_element == 0 {
+            return Err(TransducerError::BuilderError("the element size must be set before inferring the count".to_string()))
+        }
+        let src_size = self.input.len().saturating_sub(self.offset);
+        self.count = strided_count(src_size, self.stride_or(self.sizeof_element), self.sizeof_element);
+        Ok(self)
+    }
+}
+
+impl<'a, Output> Builder<'a, &'a [u8], Output, NeedsTransducer>
+{
+    /// Create a new builder for the given byte slice; this is [new](#method.new), but usable as
+    /// the first step of building a `(data, context)` input with [context](#method.context).
+    pub fn data(data: &'a [u8]) -> Self {
+        Builder::new(data)
+    }
+    /// Pair the byte slice input with a parsing `ctx`, so that the input becomes the tuple
+    /// `(data, ctx)`, which is handed to a custom transducer, or parsed with scroll via
+    /// [parse](#method.parse).
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "scroll", doc = "```rust")]
+    #[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+    /// use lazy_transducer::{LazyTransducer, Builder, Endian};
+    ///
+    /// let bytes = [0u8, 1, 0, 2, 0, 3];
+    /// let lt: LazyTransducer<_, u16> = Builder::data(&bytes)
+    ///   .context(Endian::Big)
+    ///   .count(3)
+    ///   .transducer(|(data, endian), index| {
+    ///       let (hi, lo) = (data[index * 2] as u16, data[index * 2 + 1] as u16);
+    ///       if endian == Endian::Big { hi << 8 | lo } else { lo << 8 | hi }
+    ///   })
+    ///   .finish();
+    /// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn context<Ctx: Copy>(self, ctx: Ctx) -> Builder<'a, (&'a [u8], Ctx), Output, NeedsTransducer> {
+        let data = self.input;
+        self.into_state((data, ctx), ())
+    }
+}
+
+#[cfg(feature = "scroll")]
+impl<'a, S, Output> Builder<'a, &'a S, Output, NeedsTransducer>
+    where S: 'a + Source + ?Sized
+{
+    /// Create a scroll-based transducer over the input source with the given parsing `ctx`, whose
+    /// table starts at the builder's offset, and whose records are the builder's stride apart.
+    pub fn parse_with<Ctx, E>(self, ctx: Ctx) -> Result<ScrollTransducer<'a, Output, Ctx, S>, TransducerError>
+    where
+        Ctx: Copy,
+        E: From<scroll::Error> + Debug,
+        Output: 'a + for<'b> ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>
+    {
+        let stride = self.stride_or(Output::size_with(&ctx));
+        ScrollTransducer::parse_table(self.input, self.offset, self.count, stride, ctx, self.policy)
     }
 }
 
-impl<'a, Output> Builder<'a, &'a [u8], Output>
+#[cfg(feature = "scroll")]
+impl<'a, Output, Ctx> Builder<'a, (&'a [u8], Ctx), Output, NeedsTransducer>
+    where Ctx: Copy
 {
-    /// Create a scroll-based transducer with the given parsing `ctx`.
-    pub fn parse_with<Ctx, E>(self, ctx: Ctx) -> Result<ScrollTransducer<'a, Output, Ctx>, Error>
+    /// Create a scroll-based transducer using the context of the `(data, context)` input; see
+    /// [parse_with](#method.parse_with).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{ScrollTransducer, Builder, Endian};
+    ///
+    /// let bytes = [0u8, 0, 0, 1, 0, 0, 0, 2];
+    /// let lt: ScrollTransducer<u32> = Builder::data(&bytes).context(Endian::Big).count(2).parse().unwrap();
+    /// assert_eq!(lt.get(1), Some(2));
+    /// ```
+    pub fn parse<E>(self) -> Result<ScrollTransducer<'a, Output, Ctx>, TransducerError>
     where
-        Ctx: Default + Copy,
         E: From<scroll::Error> + Debug,
-        Output: 'a + ctx::TryFromCtx<'a, Ctx, Error = E, Size = usize> + SizeWith<Ctx, Units = usize>
+        Output: 'a + for<'b> ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>
     {
-        let input = self.input.ok_or(TransducerError::BuilderError("No input given".to_string()))?;
-        ScrollTransducer::parse_with(input, self.count, ctx)
+        let (data, ctx) = self.input;
+        self.into_state::<_, NeedsTransducer>(data, ()).parse_with(ctx)
     }
 }
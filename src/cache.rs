@@ -0,0 +1,60 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// A least recently used cache of blocks, e.g., pages or decompressed frames, bounded by both a
+/// number of blocks and a resident byte budget, shared by the cached input sources
+pub(crate) struct BlockCache {
+    /// The cached blocks, least recently used first
+    blocks: VecDeque<(u64, Arc<Vec<u8>>)>,
+    capacity: usize,
+    budget: usize,
+    resident: usize,
+}
+
+impl BlockCache {
+    /// A cache of up to `capacity` blocks, with no byte budget
+    pub(crate) fn new(capacity: usize) -> Self {
+        BlockCache { blocks: VecDeque::with_capacity(capacity), capacity, budget: usize::MAX, resident: 0 }
+    }
+    /// The maximum number of bytes of cached blocks
+    pub(crate) fn budget(&self) -> usize {
+        self.budget
+    }
+    /// The number of bytes of cached blocks
+    pub(crate) fn resident(&self) -> usize {
+        self.resident
+    }
+    /// Set the byte budget, evicting the least recently used blocks until the cache fits it
+    pub(crate) fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.evict(0, 0);
+    }
+    /// Returns the block `key`, marking it as the most recently used
+    pub(crate) fn get(&mut self, key: u64) -> Option<Arc<Vec<u8>>> {
+        let pos = self.blocks.iter().position(|&(cached, _)| cached == key)?;
+        let entry = self.blocks.remove(pos).expect("position is in the cache");
+        let block = entry.1.clone();
+        self.blocks.push_back(entry);
+        Some(block)
+    }
+    /// Cache `block` as the most recently used, evicting the least recently used blocks to make
+    /// room for it; a block larger than the whole budget is not cached.
+    ///
+    /// Blocks are read without holding the cache lock, so another thread may have cached the same
+    /// block in the meantime, in which case that one is kept.
+    pub(crate) fn insert(&mut self, key: u64, block: Arc<Vec<u8>>) {
+        if block.len() > self.budget || self.get(key).is_some() {
+            return
+        }
+        self.evict(1, block.len());
+        self.resident += block.len();
+        self.blocks.push_back((key, block));
+    }
+    /// Evict the least recently used blocks until `count` more blocks, of `size` bytes in total, fit
+    fn evict(&mut self, count: usize, size: usize) {
+        while !self.blocks.is_empty() && (self.blocks.len() + count > self.capacity || self.resident + size > self.budget) {
+            let (_, block) = self.blocks.pop_front().expect("the cache is not empty");
+            self.resident -= block.len();
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+use LazyTransducer;
+
+/// A lazy transducer which memoizes its elements, so that every element is transduced at most
+/// once, e.g., a symbol which is looked up thousands of times, no matter how many times, or from
+/// how many threads, it is accessed.
+///
+/// Elements are still only transduced when they are first accessed, and iterating, in parallel or
+/// not, yields references to the memoized elements. Every index has a slot for its element,
+/// allocated up front, so this costs about `size_of::<Output>()` bytes per element even if few
+/// elements are ever accessed.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::LazyTransducer;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let names = ["main", "_start", "memcpy"];
+/// let lt: LazyTransducer<&[&str], String> = LazyTransducer::new(&names, names.len(), |input, idx| input[idx].to_uppercase());
+/// let cached = lt.cached();
+/// assert!(!cached.is_cached(2));
+/// assert_eq!(cached.get(2).map(String::as_str), Some("MEMCPY"));
+/// assert!(cached.is_cached(2));
+/// // the same element is returned, rather than transduced again
+/// assert!(::std::ptr::eq(cached.get(2).unwrap(), cached.get(2).unwrap()));
+///
+/// let total: usize = cached.into_par_iter().map(String::len).sum();
+/// assert_eq!(total, 16);
+/// # }
+/// ```
+pub struct CachedTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    lt: LazyTransducer<'a, Input, Output>,
+    slots: Vec<OnceLock<Output>>,
+}
+
+impl<'a, Input, Output> fmt::Debug for CachedTransducer<'a, Input, Output>
+    where Input: 'a + Copy + fmt::Debug,
+          Output: 'a + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CachedTransducer")
+            .field("lt", &self.lt)
+            .field("slots", &self.slots)
+            .finish()
+    }
+}
+
+impl<'a, Input, Output> CachedTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    /// Create a new lazy transducer memoizing the elements of `lt`; see also
+    /// [LazyTransducer::cached](struct.LazyTransducer.html#method.cached).
+    pub fn new(lt: LazyTransducer<'a, Input, Output>) -> Self {
+        let slots = (0..lt.len()).map(|_| OnceLock::new()).collect();
+        CachedTransducer { lt, slots }
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+    /// Whether the `idx`th element has been transduced; `false` if the index is greater than the
+    /// number of elements.
+    pub fn is_cached(&self, idx: usize) -> bool {
+        self.slots.get(idx).is_some_and(|slot| slot.get().is_some())
+    }
+    /// Get an element out of the lazy transducer, transducing it only if it hasn't been already,
+    /// returning `None` if the index is greater than the number of elements in this lazy
+    /// transducer.
+    ///
+    /// If several threads access an element which hasn't been transduced yet, only one of them
+    /// transduces it, and the others block until it has.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&Output> {
+        let slot = self.slots.get(idx)?;
+        Some(slot.get_or_init(|| (self.lt.transducer)(self.lt.contents, idx)))
+    }
+    /// Forget every memoized element, so that they are transduced again when next accessed.
+    pub fn clear(&mut self) {
+        for slot in &mut self.slots {
+            slot.take();
+        }
+    }
+    /// Returns the underlying lazy transducer, dropping the memoized elements.
+    pub fn into_inner(self) -> LazyTransducer<'a, Input, Output> {
+        self.lt
+    }
+}
+
+impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    /// Memoize the elements of this lazy transducer, so that every element is transduced at most
+    /// once; see [CachedTransducer](struct.CachedTransducer.html).
+    pub fn cached(self) -> CachedTransducer<'a, Input, Output> {
+        CachedTransducer::new(self)
+    }
+    /// Memoize up to `capacity` of the most recently used elements of this lazy transducer; see
+    /// [LruTransducer](struct.LruTransducer.html).
+    pub fn cached_lru(self, capacity: usize) -> LruTransducer<'a, Input, Output> {
+        LruTransducer::new(self, capacity)
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over references to the memoized elements, borrowing this one.
+    impl['a, Input, Output] CachedTransducer<'a, Input, Output> => <'b> &'b Output
+    where [Input: Copy]
+    parallel where [Input: Sync + Send + Copy, Output: Send + Sync]
+}
+
+/// A least recently used cache of elements, keyed by their index
+struct Lru<T> {
+    /// Every cached element, and when it was last used
+    entries: HashMap<usize, (Arc<T>, u64)>,
+    /// The index of every cached element by when it was last used, least recently used first
+    order: BTreeMap<u64, usize>,
+    tick: u64,
+    capacity: usize,
+}
+
+impl<T> Lru<T> {
+    fn new(capacity: usize) -> Self {
+        Lru { entries: HashMap::new(), order: BTreeMap::new(), tick: 0, capacity }
+    }
+    /// Returns the element `idx`, marking it as the most recently used
+    fn get(&mut self, idx: usize) -> Option<Arc<T>> {
+        let entry = self.entries.get_mut(&idx)?;
+        self.order.remove(&entry.1);
+        self.tick += 1;
+        entry.1 = self.tick;
+        self.order.insert(self.tick, idx);
+        Some(entry.0.clone())
+    }
+    /// Cache `element` as the most recently used, evicting the least recently used element to make
+    /// room for it, unless another thread cached the element `idx` first, in which case that is
+    /// returned instead
+    fn insert(&mut self, idx: usize, element: Arc<T>) -> Arc<T> {
+        if let Some(cached) = self.get(idx) {
+            return cached
+        }
+        if self.capacity == 0 {
+            return element
+        }
+        if self.entries.len() == self.capacity {
+            let (_, lru) = self.order.pop_first().expect("the cache is not empty");
+            self.entries.remove(&lru);
+        }
+        self.tick += 1;
+        self.entries.insert(idx, (element.clone(), self.tick));
+        self.order.insert(self.tick, idx);
+        element
+    }
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+/// A lazy transducer which memoizes up to a fixed number of its most recently used elements, for
+/// tables which are too large to memoize entirely with a
+/// [CachedTransducer](struct.CachedTransducer.html), yet whose accesses are skewed towards a
+/// working set, e.g., the hot symbols of a symbol table.
+///
+/// Accessing an element which is cached returns it, and marks it as the most recently used;
+/// otherwise, the element is transduced, and replaces the least recently used element if the cache
+/// is full. Elements are shared as `Arc`s, so an evicted element stays alive as long as a caller
+/// holds it. The cache is behind a lock, so this is `Sync`, and can be accessed by parallel
+/// readers; an element is transduced outside of the lock, so readers only contend on the cache
+/// bookkeeping.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::LazyTransducer;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let data: Vec<u32> = (0..1000).collect();
+/// let lt: LazyTransducer<&[u32], String> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx].to_string());
+/// let lru = lt.cached_lru(2);
+/// assert_eq!(lru.get(7).as_ref().map(|n| n.as_str()), Some("7"));
+/// lru.get(8);
+/// lru.get(7);
+/// // 8 is the least recently used, so it is evicted
+/// lru.get(9);
+/// assert_eq!((lru.is_cached(7), lru.is_cached(8), lru.is_cached(9)), (true, false, true));
+/// assert_eq!((lru.hits(), lru.misses()), (1, 3));
+///
+/// let digits: usize = lru.into_par_iter().map(|n| n.len()).sum();
+/// assert_eq!(digits, 2890);
+/// # }
+/// ```
+pub struct LruTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    lt: LazyTransducer<'a, Input, Output>,
+    cache: Mutex<Lru<Output>>,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl<'a, Input, Output> fmt::Debug for LruTransducer<'a, Input, Output>
+    where Input: 'a + Copy + fmt::Debug,
+          Output: 'a + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LruTransducer")
+            .field("lt", &self.lt)
+            .field("capacity", &self.capacity())
+            .field("hits", &self.hits())
+            .field("misses", &self.misses())
+            .finish()
+    }
+}
+
+impl<'a, Input, Output> LruTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    /// Create a new lazy transducer memoizing up to `capacity` of the most recently used elements
+    /// of `lt`; a `capacity` of 0 memoizes nothing. See also
+    /// [LazyTransducer::cached_lru](struct.LazyTransducer.html#method.cached_lru).
+    pub fn new(lt: LazyTransducer<'a, Input, Output>, capacity: usize) -> Self {
+        LruTransducer { lt, cache: Mutex::new(Lru::new(capacity)), hits: AtomicUsize::new(0), misses: AtomicUsize::new(0) }
+    }
+    fn cache(&self) -> MutexGuard<'_, Lru<Output>> {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.lt.len()
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.lt.len() == 0
+    }
+    /// The maximum number of memoized elements
+    pub fn capacity(&self) -> usize {
+        self.cache().capacity
+    }
+    /// Whether the `idx`th element is currently memoized, without marking it as used
+    pub fn is_cached(&self, idx: usize) -> bool {
+        self.cache().entries.contains_key(&idx)
+    }
+    /// How many accesses were served from the cache so far
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+    /// How many accesses transduced their element so far
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+    /// Get an element out of the lazy transducer, from the cache if it is memoized, and transducing
+    /// and memoizing it otherwise, returning `None` if the index is greater than the number of
+    /// elements in this lazy transducer.
+    pub fn get(&self, idx: usize) -> Option<Arc<Output>> {
+        if idx >= self.len() {
+            return None
+        }
+        if let Some(element) = self.cache().get(idx) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Some(element)
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        // transduce without holding the lock, so that other readers aren't blocked
+        let element = Arc::new((self.lt.transducer)(self.lt.contents, idx));
+        Some(self.cache().insert(idx, element))
+    }
+    /// Forget every memoized element.
+    pub fn clear(&self) {
+        self.cache().clear();
+    }
+    /// Returns the underlying lazy transducer, dropping the memoized elements.
+    pub fn into_inner(self) -> LazyTransducer<'a, Input, Output> {
+        self.lt
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the elements, borrowing this one.
+    impl['a, Input, Output] LruTransducer<'a, Input, Output> => <'b> Arc<Output>
+    where [Input: Copy]
+    parallel where [Input: Sync + Send + Copy, Output: Send + Sync]
+}
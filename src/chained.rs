@@ -0,0 +1,106 @@
+use std::borrow::Cow;
+
+use {Source, TransducerError};
+use source::overflow;
+use lazy_transducer::range_containing;
+
+/// An input source which presents several byte slices, e.g., the segments of a dataset which is
+/// split across multiple files, as one contiguous address space.
+///
+/// Elements must not straddle the boundary between two segments; reading bytes which would is a
+/// `StraddlesBoundary` error, rather than silently stitching them together.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{ChainedSource, ScrollTransducer, Endian};
+///
+/// # fn main() {
+/// let first = [1u8, 0, 2, 0];
+/// let second = [3u8, 0];
+/// let source = ChainedSource::new(vec![&first[..], &second[..]]);
+/// assert_eq!(source.len(), 6);
+///
+/// let lt: ScrollTransducer<u16, _, _> = ScrollTransducer::parse_with(&source, 3, Endian::Little).unwrap();
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+///
+/// // 4 bytes at offset 2 span both segments
+/// assert!(source.read(2, 4).is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChainedSource<'a> {
+    segments: Vec<&'a [u8]>,
+    /// The offset of each segment, plus the total size as a final entry
+    starts: Vec<usize>,
+}
+
+impl<'a> ChainedSource<'a> {
+    /// Create a new chained source over `segments`, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total size of the segments overflows a `usize`.
+    pub fn new(segments: Vec<&'a [u8]>) -> Self {
+        let mut starts = Vec::with_capacity(segments.len() + 1);
+        let mut total: usize = 0;
+        for segment in &segments {
+            starts.push(total);
+            total = total.checked_add(segment.len()).expect("the total size of the segments overflows");
+        }
+        starts.push(total);
+        ChainedSource { segments, starts }
+    }
+    /// The total size in bytes of all the segments
+    pub fn len(&self) -> usize {
+        self.starts[self.starts.len() - 1]
+    }
+    /// Whether the segments are all empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// The segments, in order
+    pub fn segments(&self) -> &[&'a [u8]] {
+        &self.segments
+    }
+    /// Returns the `(segment, offset)` pair of the byte at `offset`, or `None` if it is past the
+    /// end of the last segment.
+    pub fn locate(&self, offset: usize) -> Option<(usize, usize)> {
+        if offset >= self.len() {
+            return None
+        }
+        let segment = range_containing(&self.starts, offset, |&start| start)?;
+        Some((segment, offset - self.starts[segment]))
+    }
+    /// Returns the `len` bytes starting at `offset`, returning a `StraddlesBoundary` error if they
+    /// span more than one segment, or an `ElementOverflow` error if they are past the end of the
+    /// last segment.
+    pub fn read(&self, offset: usize, len: usize) -> Result<&'a [u8], TransducerError> {
+        let overflow = overflow(offset, len, self.len());
+        let (segment, start) = match self.locate(offset) {
+            Some(location) => location,
+            None if len == 0 && offset == self.len() => return Ok(&[]),
+            None => return Err(overflow),
+        };
+        let bytes = self.segments[segment];
+        if bytes.len() - start >= len {
+            Ok(&bytes[start..start + len])
+        } else if offset.checked_add(len).filter(|&end| end <= self.len()).is_none() {
+            // this includes reads which only run into trailing empty segments
+            Err(overflow)
+        } else {
+            Err(TransducerError::StraddlesBoundary{ offset, len, boundary: self.starts[segment + 1] })
+        }
+    }
+}
+
+impl<'a> Source for ChainedSource<'a> {
+    fn len(&self) -> usize {
+        ChainedSource::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        ChainedSource::read(self, offset, len).map(Cow::Borrowed)
+    }
+}
@@ -0,0 +1,162 @@
+use std::ops::Range;
+
+#[cfg(feature = "graphemes")]
+use unicode_segmentation::UnicodeSegmentation;
+
+/// How many chars share a base offset in the index; since a char is at most 4 bytes, the offset of
+/// every char relative to its base fits in a `u16`
+const BLOCK: usize = 1024;
+
+/// A lazy transducer over the chars of a string, so that, unlike `str::chars`, the `i`th char can
+/// be fetched in O(1), and the chars can be processed in parallel.
+///
+/// A single pass at construction records where every char starts in a compact index of about 2
+/// bytes per char. See also [GraphemeTransducer](struct.GraphemeTransducer.html), with the
+/// `graphemes` feature, for user-perceived characters.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::CharTransducer;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let lt = CharTransducer::new("naïve café ☕");
+/// assert_eq!(lt.len(), 12);
+/// assert_eq!(lt.get(2), Some('ï'));
+/// assert_eq!(lt.offset_of(3), Some(4));
+/// assert_eq!(lt.slice(6..10), Some("café"));
+/// let ascii = lt.into_par_iter().filter(char::is_ascii).count();
+/// assert_eq!(ascii, 9);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CharTransducer<'a> {
+    text: &'a str,
+    /// The offset of every `BLOCK`th char
+    bases: Vec<usize>,
+    /// The offset of every char relative to the base of its block, plus the end of the last char
+    /// as a final entry
+    offsets: Vec<u16>,
+}
+
+impl<'a> CharTransducer<'a> {
+    /// Create a new lazy transducer over the chars of `text`.
+    pub fn new(text: &'a str) -> Self {
+        let mut bases = Vec::new();
+        let mut offsets = Vec::new();
+        let ends = text.char_indices().map(|(offset, _)| offset).chain(Some(text.len()));
+        for (idx, offset) in ends.enumerate() {
+            if idx % BLOCK == 0 {
+                bases.push(offset);
+            }
+            offsets.push((offset - bases[idx / BLOCK]) as u16);
+        }
+        CharTransducer { text, bases, offsets }
+    }
+    /// How many chars are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no chars
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th char, or `None` if the index is greater than the
+    /// number of chars; the index of one past the last char is the end of the string.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.offsets.get(idx).map(|offset| self.bases[idx / BLOCK] + *offset as usize)
+    }
+    /// Get a char out of the lazy transducer, returning `None` if the index is greater than the
+    /// number of chars in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<char> {
+        if idx >= self.len() {
+            return None
+        }
+        self.text[self.offset_of(idx).unwrap()..].chars().next()
+    }
+    /// Returns the substring of the chars in `range`, or `None` if the range is out of bounds.
+    pub fn slice(&self, range: Range<usize>) -> Option<&'a str> {
+        let (start, end) = (self.offset_of(range.start)?, self.offset_of(range.end)?);
+        self.text.get(start..end)
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the chars, borrowing this one.
+    impl['a] CharTransducer<'a> => <'b> char
+    where []
+    parallel where []
+}
+
+/// A lazy transducer over the extended grapheme clusters of a string, i.e., its user-perceived
+/// characters, such as a letter with combining accents, or an emoji with modifiers, so that the
+/// `i`th grapheme can be fetched in O(1).
+///
+/// A single pass at construction, using
+/// [unicode-segmentation](https://docs.rs/unicode-segmentation), records where every grapheme
+/// starts in an index; since a grapheme has no maximum size, the index has a `usize` per grapheme.
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::GraphemeTransducer;
+///
+/// // "e" with a combining acute accent, and a family emoji joined with zero width joiners
+/// let lt = GraphemeTransducer::new("ce\u{301}\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}!");
+/// assert_eq!(lt.len(), 4);
+/// assert_eq!(lt.get(1), Some("e\u{301}"));
+/// assert_eq!(lt.get(3), Some("!"));
+/// assert_eq!(lt.into_iter().map(str::len).collect::<Vec<_>>(), vec![1, 3, 18, 1]);
+/// ```
+#[cfg(feature = "graphemes")]
+#[derive(Debug, Clone)]
+pub struct GraphemeTransducer<'a> {
+    text: &'a str,
+    /// The offset of every grapheme, plus the end of the last grapheme as a final entry
+    offsets: Vec<usize>,
+}
+
+#[cfg(feature = "graphemes")]
+impl<'a> GraphemeTransducer<'a> {
+    /// Create a new lazy transducer over the extended grapheme clusters of `text`.
+    pub fn new(text: &'a str) -> Self {
+        let offsets = text.grapheme_indices(true).map(|(offset, _)| offset).chain(Some(text.len())).collect();
+        GraphemeTransducer { text, offsets }
+    }
+    /// How many graphemes are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no graphemes
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th grapheme, or `None` if the index is greater than the
+    /// number of graphemes; the index of one past the last grapheme is the end of the string.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.offsets.get(idx).cloned()
+    }
+    /// Get a grapheme out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of graphemes in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&'a str> {
+        if idx >= self.len() { None } else { Some(&self.text[self.offsets[idx]..self.offsets[idx + 1]]) }
+    }
+    /// Returns the substring of the graphemes in `range`, or `None` if the range is out of bounds.
+    pub fn slice(&self, range: Range<usize>) -> Option<&'a str> {
+        let (start, end) = (self.offset_of(range.start)?, self.offset_of(range.end)?);
+        self.text.get(start..end)
+    }
+}
+
+#[cfg(feature = "graphemes")]
+borrowed_transducer! {
+    /// Returns a lazy transducer over the graphemes, borrowing this one.
+    impl['a] GraphemeTransducer<'a> => <'b> &'a str
+    where []
+    parallel where []
+}
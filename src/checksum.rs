@@ -0,0 +1,115 @@
+use std::fmt;
+#[cfg(feature = "scroll")]
+use std::fmt::Debug;
+
+use crc32fast;
+use rayon::prelude::*;
+#[cfg(feature = "scroll")]
+use scroll::{self, ctx};
+#[cfg(feature = "scroll")]
+use scroll::ctx::SizeWith;
+use sha2::{self, Digest as Sha2Digest};
+
+#[cfg(feature = "scroll")]
+use {ScrollTransducer, TransducerError};
+#[cfg(feature = "scroll")]
+use lazy_transducer::total_size;
+
+/// The size of the chunks which are checksummed in parallel
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// A checksum of some bytes, e.g., the expected checksum of a table, as recorded by the format
+/// it's in, or computed when it was written.
+///
+/// CRC32 checksums of large inputs are computed in parallel over chunks, which are then combined;
+/// SHA-256 digests are computed sequentially.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Digest {
+    /// A CRC32 (IEEE) checksum
+    Crc32(u32),
+    /// A SHA-256 digest
+    Sha256([u8; 32]),
+}
+
+impl Digest {
+    /// Computes the CRC32 checksum of `bytes`.
+    pub fn crc32(bytes: &[u8]) -> Self {
+        let hasher = bytes.par_chunks(CHUNK_SIZE)
+            .map(|chunk| {
+                let mut hasher = crc32fast::Hasher::new();
+                hasher.update(chunk);
+                hasher
+            })
+            .reduce(crc32fast::Hasher::new, |mut hasher, next| {
+                hasher.combine(&next);
+                hasher
+            });
+        Digest::Crc32(hasher.finalize())
+    }
+    /// Computes the SHA-256 digest of `bytes`.
+    pub fn sha256(bytes: &[u8]) -> Self {
+        let mut digest = [0; 32];
+        digest.copy_from_slice(&sha2::Sha256::digest(bytes));
+        Digest::Sha256(digest)
+    }
+    /// Computes the same kind of checksum as this one of `bytes`.
+    pub fn of(&self, bytes: &[u8]) -> Self {
+        match *self {
+            Digest::Crc32(_) => Digest::crc32(bytes),
+            Digest::Sha256(_) => Digest::sha256(bytes),
+        }
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Digest::Crc32(crc) => write!(f, "crc32:{:08x}", crc),
+            Digest::Sha256(ref digest) => {
+                write!(f, "sha256:")?;
+                for byte in digest.iter() {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+#[cfg(feature = "scroll")]
+impl<'a, Output, Ctx, E> ScrollTransducer<'a, Output, Ctx>
+    where Ctx: Copy,
+          Output: 'a + for<'b> ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>,
+          E: From<scroll::Error> + Debug,
+{
+    /// Create a new scroll-based lazy transducer like [parse_with](#method.parse_with), but first
+    /// verify that the bytes of the `count` elements have the `expected` checksum, returning a
+    /// `ChecksumMismatch` error otherwise, so that the elements can be trusted when they are
+    /// lazily read later.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{Digest, ScrollTransducer, Endian};
+    ///
+    /// let bytes = [1u8, 0, 2, 0, 0xff];
+    /// let expected = Digest::crc32(&bytes[..4]);
+    /// let lt: ScrollTransducer<u16> = ScrollTransducer::parse_with_checksum(&bytes, 2, Endian::Little, expected).unwrap();
+    /// assert_eq!(lt.get(1), Some(2));
+    ///
+    /// let corrupted = [1u8, 0, 3, 0];
+    /// assert!(ScrollTransducer::<u16>::parse_with_checksum(&corrupted, 2, Endian::Little, expected).is_err());
+    /// ```
+    pub fn parse_with_checksum(contents: &'a [u8], count: usize, ctx: Ctx, expected: Digest) -> Result<Self, TransducerError> {
+        let sizeof_element = Output::size_with(&ctx);
+        let size = total_size(count, sizeof_element)?;
+        if size > contents.len() {
+            return Err(TransducerError::ElementOverflow{ nelements: count, sizeof_element, stride: sizeof_element, src_size: contents.len() })
+        }
+        let actual = expected.of(&contents[..size]);
+        if actual != expected {
+            return Err(TransducerError::ChecksumMismatch{ expected, actual })
+        }
+        Self::parse_with(contents, count, ctx)
+    }
+}
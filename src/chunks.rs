@@ -0,0 +1,168 @@
+use std::io;
+
+use TransducerError;
+
+/// The signature which starts every PNG file
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// A four character code, which identifies the type of a chunk
+pub type FourCC = [u8; 4];
+
+/// The layout of the chunks of a [ChunkTransducer](struct.ChunkTransducer.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkFormat {
+    /// A FourCC followed by a little endian `u32` length, with odd-sized payloads padded to an
+    /// even size, as in WAV, AVI, and WebP
+    Riff,
+    /// A FourCC followed by a big endian `u32` length, with odd-sized payloads padded to an even
+    /// size, as in AIFF and other IFF files
+    Iff,
+    /// A big endian `u32` length followed by a FourCC, with a CRC after every payload, as in PNG
+    Png,
+}
+
+impl ChunkFormat {
+    /// Reads the FourCC and payload length of the header at the start of `header`
+    fn header(self, header: &[u8]) -> (FourCC, usize) {
+        let (fourcc, len) = match self {
+            ChunkFormat::Riff | ChunkFormat::Iff => (&header[..4], &header[4..8]),
+            ChunkFormat::Png => (&header[4..8], &header[..4]),
+        };
+        let len = [len[0], len[1], len[2], len[3]];
+        let len = if self == ChunkFormat::Riff { u32::from_le_bytes(len) } else { u32::from_be_bytes(len) };
+        ([fourcc[0], fourcc[1], fourcc[2], fourcc[3]], len as usize)
+    }
+    /// The number of bytes following a payload of `len` bytes, before the next chunk
+    fn trailer(self, len: usize) -> usize {
+        match self {
+            ChunkFormat::Riff | ChunkFormat::Iff => len & 1,
+            ChunkFormat::Png => 4,
+        }
+    }
+}
+
+/// Walks the chunks from `start` to the end of `bytes`, returning the offset of every header
+fn walk(bytes: &[u8], start: usize, format: ChunkFormat) -> Result<Vec<usize>, TransducerError> {
+    let mut offsets = Vec::new();
+    let mut offset = start;
+    while offset < bytes.len() {
+        let idx = offsets.len();
+        let malformed = |msg| TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) };
+        let header = bytes.get(offset..offset + 8).ok_or_else(|| malformed("truncated chunk header"))?;
+        let (_, len) = format.header(header);
+        if len > bytes.len() - offset - 8 {
+            return Err(malformed("truncated chunk"))
+        }
+        offsets.push(offset);
+        offset = (offset + 8 + len).saturating_add(format.trailer(len));
+    }
+    Ok(offsets)
+}
+
+/// A lazy transducer over the chunks of a RIFF, IFF, or PNG style container, yielding the FourCC
+/// and payload of every chunk.
+///
+/// A single pass at construction walks the chunk headers, and records where every chunk lies in an
+/// index, so that afterwards, accessing a chunk is O(1); the index can be restricted to the chunks
+/// of one type with [of_type](#method.of_type).
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::ChunkTransducer;
+///
+/// let mut wav = b"RIFF\x1a\0\0\0WAVE".to_vec();
+/// wav.extend_from_slice(b"fmt \x03\0\0\0abc\0");
+/// wav.extend_from_slice(b"data\x02\0\0\0\x01\x02");
+/// let lt = ChunkTransducer::from_file(&wav).unwrap();
+/// assert_eq!(lt.form_type(), Some(*b"WAVE"));
+/// assert_eq!(lt.len(), 2);
+/// // the odd-sized payload is padded
+/// assert_eq!(lt.get(0), Some((*b"fmt ", &b"abc"[..])));
+/// assert_eq!(lt.offset_of(1), Some(24));
+/// let data = lt.of_type(*b"data");
+/// assert_eq!(data.into_iter().map(|(_, payload)| payload).collect::<Vec<_>>(), vec![&[1, 2][..]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChunkTransducer<'a> {
+    bytes: &'a [u8],
+    format: ChunkFormat,
+    form: Option<FourCC>,
+    /// The offset of the header of every chunk
+    offsets: Vec<usize>,
+}
+
+impl<'a> ChunkTransducer<'a> {
+    /// Create a new lazy transducer over the chunks laid out back to back in `bytes`, in `format`,
+    /// returning a `Parse` error with the index and offset of the first chunk which is truncated.
+    /// The padding or CRC of the last chunk may be missing.
+    pub fn new(bytes: &'a [u8], format: ChunkFormat) -> Result<Self, TransducerError> {
+        let offsets = walk(bytes, 0, format)?;
+        Ok(ChunkTransducer { bytes, format, form: None, offsets })
+    }
+    /// Create a new lazy transducer over the chunks of a whole RIFF, IFF, or PNG file, detected
+    /// from its header, returning an `InvalidData` I/O error if it is none of them, or its `RIFF`
+    /// or `FORM` chunk is truncated, and a `Parse` error like [new](#method.new) otherwise.
+    pub fn from_file(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        let form = |format: ChunkFormat| {
+            let truncated = || io::Error::new(io::ErrorKind::InvalidData, "truncated form");
+            let (_, len) = format.header(bytes.get(..8).ok_or_else(truncated)?);
+            // the form type is the start of the payload
+            let end = len.checked_add(8).filter(|end| *end >= 12 && *end <= bytes.len()).ok_or_else(truncated)?;
+            let offsets = walk(&bytes[..end], 12, format)?;
+            Ok(ChunkTransducer { bytes: &bytes[..end], format, form: Some([bytes[8], bytes[9], bytes[10], bytes[11]]), offsets })
+        };
+        if bytes.starts_with(b"RIFF") {
+            form(ChunkFormat::Riff)
+        } else if bytes.starts_with(b"FORM") {
+            form(ChunkFormat::Iff)
+        } else if bytes.starts_with(PNG_SIGNATURE) {
+            let offsets = walk(bytes, PNG_SIGNATURE.len(), ChunkFormat::Png)?;
+            Ok(ChunkTransducer { bytes, format: ChunkFormat::Png, form: None, offsets })
+        } else {
+            Err(io::Error::new(io::ErrorKind::InvalidData, "not a RIFF, IFF, or PNG file").into())
+        }
+    }
+    /// The layout of the chunks
+    pub fn format(&self) -> ChunkFormat {
+        self.format
+    }
+    /// The form type of a RIFF or IFF file, e.g., `WAVE` or `AIFF`, if this lazy transducer was
+    /// created with [from_file](#method.from_file)
+    pub fn form_type(&self) -> Option<FourCC> {
+        self.form
+    }
+    /// Returns a lazy transducer over only the chunks of this one whose type is `fourcc`.
+    pub fn of_type(&self, fourcc: FourCC) -> Self {
+        let offsets = self.offsets.iter().cloned().filter(|offset| self.format.header(&self.bytes[*offset..]).0 == fourcc).collect();
+        ChunkTransducer { offsets, ..self.clone() }
+    }
+    /// How many chunks are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+    /// Whether this lazy transducer has no chunks
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+    /// Returns the byte offset of the header of the `idx`th chunk, or `None` if the index is
+    /// greater than the number of chunks.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.offsets.get(idx).cloned()
+    }
+    /// Get the FourCC and payload of a chunk out of the lazy transducer, returning `None` if the
+    /// index is greater than the number of chunks in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<(FourCC, &'a [u8])> {
+        let offset = self.offset_of(idx)?;
+        let (fourcc, len) = self.format.header(&self.bytes[offset..]);
+        Some((fourcc, &self.bytes[offset + 8..offset + 8 + len]))
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the chunks, borrowing this one.
+    impl['a] ChunkTransducer<'a> => <'b> (FourCC, &'a [u8])
+    where []
+    parallel where []
+}
@@ -0,0 +1,129 @@
+use csv::{ByteRecord, ReaderBuilder, StringRecord};
+use serde::de::DeserializeOwned;
+
+use {LazyTransducer, TransducerError};
+
+/// A lazy transducer over the rows of a CSV file, which parses a row only when it is accessed, so
+/// that a huge CSV file can be randomly accessed, and processed in parallel.
+///
+/// A single pass at construction, using the [csv](https://docs.rs/csv) crate, records where every
+/// row starts in an index, correctly skipping over quoted newlines; afterwards, accessing a row
+/// is O(1). Rows can be accessed as a `StringRecord`, or deserialized with serde.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// #[macro_use]
+/// extern crate serde_derive;
+/// use lazy_transducer::CsvTransducer;
+/// use rayon::prelude::*;
+///
+/// #[derive(Debug, PartialEq, Deserialize)]
+/// struct City {
+///     name: String,
+///     population: u64,
+/// }
+///
+/// # fn main() {
+/// let csv = b"name,population\nTokyo,37400068\n\"Washington,\nD.C.\",5207000\n";
+/// let lt = CsvTransducer::new(csv).unwrap();
+/// assert_eq!(lt.len(), 2);
+/// assert_eq!(&lt.get(1).unwrap().unwrap()[0], "Washington,\nD.C.");
+///
+/// let city: City = lt.deserialize(0).unwrap().unwrap();
+/// assert_eq!(city, City { name: "Tokyo".to_string(), population: 37400068 });
+/// let total: u64 = lt.deserialized::<City>().into_par_iter().map(|city| city.unwrap().population).sum();
+/// assert_eq!(total, 42607068);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CsvTransducer<'a> {
+    bytes: &'a [u8],
+    delimiter: u8,
+    headers: Option<StringRecord>,
+    /// The offset of every row, plus the end of the last row as a final entry
+    offsets: Vec<usize>,
+}
+
+impl<'a> CsvTransducer<'a> {
+    /// Create a new lazy transducer over the comma-separated rows of `bytes`, whose first row is a
+    /// header; see [with_config](#method.with_config).
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        Self::with_config(bytes, b',', true)
+    }
+    /// Create a new lazy transducer over the rows of `bytes` whose fields are separated by
+    /// `delimiter`, and whose first row is a header if `has_headers`, returning a `Parse` error
+    /// with the index and offset of the first malformed row, e.g., one with a different number of
+    /// fields than the first.
+    pub fn with_config(bytes: &'a [u8], delimiter: u8, has_headers: bool) -> Result<Self, TransducerError> {
+        let mut reader = ReaderBuilder::new().has_headers(false).delimiter(delimiter).from_reader(bytes);
+        let mut record = ByteRecord::new();
+        let mut offsets = Vec::new();
+        let mut headers = None;
+        loop {
+            let offset = reader.position().byte() as usize;
+            let idx = offsets.len();
+            if !reader.read_byte_record(&mut record).map_err(|err| TransducerError::Parse{ idx, offset, source: Box::new(err) })? {
+                break
+            }
+            // we unwrap because the reader records the position of every record
+            let start = record.position().unwrap().byte() as usize;
+            if has_headers && headers.is_none() {
+                headers = Some(StringRecord::from_byte_record(record.clone()).map_err(|err| TransducerError::Parse{ idx, offset, source: Box::new(err) })?);
+            } else {
+                offsets.push(start);
+            }
+        }
+        offsets.push(bytes.len());
+        Ok(CsvTransducer { bytes, delimiter, headers, offsets })
+    }
+    /// The header row, if there is one
+    pub fn headers(&self) -> Option<&StringRecord> {
+        self.headers.as_ref()
+    }
+    /// How many rows, excluding any header, are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no rows
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th row, or `None` if the index is greater than the
+    /// number of rows.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len() { None } else { Some(self.offsets[idx]) }
+    }
+    /// Parse a row out of the lazy transducer, returning `None` if the index is greater than the
+    /// number of rows in this lazy transducer, and a `Parse` error if it is not valid UTF-8.
+    pub fn get(&self, idx: usize) -> Option<Result<StringRecord, TransducerError>> {
+        let offset = self.offset_of(idx)?;
+        let mut reader = ReaderBuilder::new().has_headers(false).delimiter(self.delimiter).from_reader(&self.bytes[offset..self.offsets[idx + 1]]);
+        let mut record = StringRecord::new();
+        Some(reader.read_record(&mut record)
+            .map(|_| record)
+            .map_err(|err| TransducerError::Parse{ idx, offset, source: Box::new(err) }))
+    }
+    /// Parse a row out of the lazy transducer and deserialize it, using the header row (if there is
+    /// one) to match fields by name, returning `None` if the index is greater than the number of
+    /// rows in this lazy transducer, and a `Parse` error if it fails to deserialize.
+    pub fn deserialize<T: DeserializeOwned>(&self, idx: usize) -> Option<Result<T, TransducerError>> {
+        self.get(idx).map(|record| {
+            record?.deserialize(self.headers.as_ref())
+                .map_err(|err| TransducerError::Parse{ idx, offset: self.offsets[idx], source: Box::new(err) })
+        })
+    }
+    /// Returns a lazy transducer over the deserialized rows, borrowing this one.
+    pub fn deserialized<'b, T: DeserializeOwned>(&'b self) -> LazyTransducer<'b, &'b Self, Result<T, TransducerError>> {
+        LazyTransducer::new(self, self.len(), |lt, idx| lt.deserialize(idx).unwrap())
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the parsed rows, borrowing this one.
+    impl['a] CsvTransducer<'a> => <'b> Result<StringRecord, TransducerError>
+    where []
+    parallel where []
+}
@@ -0,0 +1,101 @@
+use std::str;
+
+use memchr::memchr_iter;
+
+use TransducerError;
+
+/// A lazy transducer over the records of a delimiter-separated blob, e.g., the lines of a log file
+/// or the NUL-separated paths from `find -print0`; an indexable, parallel alternative to
+/// `BufRead::lines`.
+///
+/// A single scan at construction, using [memchr](https://docs.rs/memchr), records where every
+/// record starts in an index, so that afterwards, accessing a record is O(1). Records don't include
+/// their delimiter, and a trailing delimiter does not start an empty final record.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::Delimited;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let log = b"GET /index.html 200\r\nGET /missing 404\r\nPOST /login 200\r\n";
+/// let lines = Delimited::lines(log);
+/// assert_eq!(lines.len(), 3);
+/// assert_eq!(lines.get_str(1).unwrap().unwrap(), "GET /missing 404");
+/// let ok = lines.into_par_iter().filter(|line| line.ends_with(b" 200")).count();
+/// assert_eq!(ok, 2);
+///
+/// let paths = Delimited::new(b"./a\0./b/c\0", b'\0');
+/// assert_eq!(paths.into_iter().collect::<Vec<_>>(), vec![&b"./a"[..], &b"./b/c"[..]]);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Delimited<'a> {
+    bytes: &'a [u8],
+    /// The offset of every record, plus one past the delimiter of the last record as a final entry,
+    /// whether or not it has one
+    offsets: Vec<usize>,
+    /// Whether to strip a `\r` preceding the delimiter
+    strip_cr: bool,
+}
+
+impl<'a> Delimited<'a> {
+    /// Create a new lazy transducer over the records of `bytes` separated by `delimiter`.
+    pub fn new(bytes: &'a [u8], delimiter: u8) -> Self {
+        let mut offsets = vec![0];
+        offsets.extend(memchr_iter(delimiter, bytes).map(|end| end + 1));
+        if offsets[offsets.len() - 1] != bytes.len() {
+            offsets.push(bytes.len() + 1);
+        }
+        Delimited { bytes, offsets, strip_cr: false }
+    }
+    /// Create a new lazy transducer over the lines of `bytes`, which end with either `\n` or
+    /// `\r\n`, like `BufRead::lines`.
+    pub fn lines(bytes: &'a [u8]) -> Self {
+        Delimited { strip_cr: true, ..Self::new(bytes, b'\n') }
+    }
+    /// How many records are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th record, or `None` if the index is greater than the
+    /// number of records.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len() { None } else { Some(self.offsets[idx]) }
+    }
+    /// Get a record out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of records in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&'a [u8]> {
+        if idx >= self.len() {
+            return None
+        }
+        let record = &self.bytes[self.offsets[idx]..self.offsets[idx + 1] - 1];
+        match record.split_last() {
+            Some((b'\r', rest)) if self.strip_cr => Some(rest),
+            _ => Some(record),
+        }
+    }
+    /// Get a record out of the lazy transducer as a string, returning `None` if the index is
+    /// greater than the number of records in this lazy transducer, and a `Parse` error if it is not
+    /// valid UTF-8.
+    pub fn get_str(&self, idx: usize) -> Option<Result<&'a str, TransducerError>> {
+        self.get(idx).map(|record| {
+            str::from_utf8(record).map_err(|err| TransducerError::Parse{ idx, offset: self.offsets[idx], source: Box::new(err) })
+        })
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the records, borrowing this one.
+    impl['a] Delimited<'a> => <'b> &'a [u8]
+    where []
+    parallel where []
+}
@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use {Source, TransducerError};
+use cache::BlockCache;
+use source::overflow;
+
+/// The number of plaintext blocks cached by [new](struct.EncryptedSource.html#method.new)
+const DEFAULT_CAPACITY: usize = 64;
+
+/// An input source over encrypted-at-rest bytes, which are decrypted in fixed-size blocks by a
+/// caller supplied `decrypt(block_index, block)` function the first time they are touched, so
+/// that an encrypted archive can be parsed lazily while the keys stay with the caller.
+///
+/// The decrypt function decrypts the ciphertext of the `block_index`th block in place; the last
+/// block may be shorter than the block size. The most recently used plaintext blocks are cached,
+/// and elements may straddle blocks.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{EncryptedSource, ScrollTransducer, Endian};
+///
+/// # fn main() {
+/// // a toy cipher, which xors every byte with the key and the index of its block
+/// let key = 0x5a;
+/// let plaintext: Vec<u8> = (0..64u16).flat_map(|n| n.to_le_bytes().to_vec()).collect();
+/// let ciphertext: Vec<u8> = plaintext.iter().enumerate().map(|(i, byte)| byte ^ key ^ (i / 16) as u8).collect();
+///
+/// let source = EncryptedSource::new(&ciphertext, 16, move |block, bytes: &mut [u8]| {
+///     for byte in bytes {
+///         *byte ^= key ^ block as u8;
+///     }
+/// });
+/// let lt: ScrollTransducer<u16, _, _> = ScrollTransducer::parse_with(&source, 64, Endian::Little).unwrap();
+/// assert_eq!(lt.get(42), Some(42));
+/// assert_eq!(source.decryptions(), 1);
+/// assert_eq!(lt.into_iter().sum::<u16>(), 2016);
+/// assert_eq!(source.decryptions(), 8);
+/// # }
+/// ```
+pub struct EncryptedSource<'a, F> {
+    ciphertext: &'a [u8],
+    block_size: usize,
+    decrypt: F,
+    /// The most recently used plaintext blocks
+    cache: Mutex<BlockCache>,
+    decryptions: AtomicUsize,
+}
+
+impl<'a, F> ::std::fmt::Debug for EncryptedSource<'a, F> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let cache = self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        f.debug_struct("EncryptedSource")
+            .field("len", &self.ciphertext.len())
+            .field("block_size", &self.block_size)
+            .field("memory_budget", &cache.budget())
+            .field("resident_bytes", &cache.resident())
+            .field("decryptions", &self.decryptions.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl<'a, F> EncryptedSource<'a, F>
+    where F: Fn(usize, &mut [u8])
+{
+    /// Create a new encrypted source over `ciphertext`, whose blocks of `block_size` bytes are
+    /// decrypted with `decrypt`, caching up to 64 plaintext blocks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is zero.
+    pub fn new(ciphertext: &'a [u8], block_size: usize, decrypt: F) -> Self {
+        Self::with_capacity(ciphertext, block_size, decrypt, DEFAULT_CAPACITY)
+    }
+    /// Create a new encrypted source like [new](#method.new), caching up to `capacity` plaintext
+    /// blocks; when the cache is full, the least recently used block is evicted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` or `capacity` is zero.
+    pub fn with_capacity(ciphertext: &'a [u8], block_size: usize, decrypt: F, capacity: usize) -> Self {
+        assert!(block_size != 0, "block size must be non-zero");
+        assert!(capacity != 0, "capacity must be non-zero");
+        EncryptedSource { ciphertext, block_size, decrypt, cache: Mutex::new(BlockCache::new(capacity)), decryptions: AtomicUsize::new(0) }
+    }
+    /// Limit the cached plaintext blocks to `budget` bytes in total, evicting the least recently
+    /// used blocks first, so that plaintext never piles up in memory; a block larger than the
+    /// budget is decrypted every time it is accessed.
+    pub fn with_memory_budget(mut self, budget: usize) -> Self {
+        self.cache.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner()).set_budget(budget);
+        self
+    }
+    /// The size in bytes of the input source
+    pub fn len(&self) -> usize {
+        self.ciphertext.len()
+    }
+    /// Whether the input source is empty
+    pub fn is_empty(&self) -> bool {
+        self.ciphertext.is_empty()
+    }
+    /// The size in bytes of a block
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+    /// How many blocks the input source contains
+    pub fn blocks(&self) -> usize {
+        self.len().div_ceil(self.block_size)
+    }
+    /// How many blocks have been decrypted so far
+    pub fn decryptions(&self) -> usize {
+        self.decryptions.load(Ordering::Relaxed)
+    }
+    /// The maximum number of bytes of cached plaintext blocks, which is unlimited unless set with
+    /// [with_memory_budget](#method.with_memory_budget)
+    pub fn memory_budget(&self) -> usize {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).budget()
+    }
+    /// The number of bytes of plaintext blocks currently cached
+    pub fn resident_bytes(&self) -> usize {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).resident()
+    }
+    /// Returns the `n`th block, decrypted, decrypting it if it isn't cached.
+    pub fn block(&self, n: usize) -> Result<Arc<Vec<u8>>, TransducerError> {
+        if n >= self.blocks() {
+            return Err(TransducerError::OutOfBounds{ idx: n, len: self.blocks() })
+        }
+        if let Some(block) = self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(n as u64) {
+            return Ok(block)
+        }
+        // the cache isn't locked while decrypting, so other blocks can be accessed meanwhile
+        let start = n * self.block_size;
+        let end = ::std::cmp::min(start + self.block_size, self.len());
+        let mut block = self.ciphertext[start..end].to_vec();
+        (self.decrypt)(n, &mut block);
+        self.decryptions.fetch_add(1, Ordering::Relaxed);
+        let block = Arc::new(block);
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(n as u64, block.clone());
+        Ok(block)
+    }
+    /// Call `f` with the `len` plaintext bytes starting at `offset`, returning an error if they
+    /// are not all in the input source.
+    ///
+    /// The bytes are borrowed straight out of the cache if they are in a single block, and are
+    /// otherwise copied out of every block they span.
+    pub fn with_bytes<T, G>(&self, offset: usize, len: usize, f: G) -> Result<T, TransducerError>
+        where G: FnOnce(&[u8]) -> T
+    {
+        let end = offset.checked_add(len)
+            .filter(|end| *end <= self.len())
+            .ok_or_else(|| overflow(offset, len, self.len()))?;
+        if len == 0 {
+            return Ok(f(&[]))
+        }
+        let (first, last) = (offset / self.block_size, (end - 1) / self.block_size);
+        if first == last {
+            let start = offset - first * self.block_size;
+            let block = self.block(first)?;
+            return Ok(f(&block[start..start + len]))
+        }
+        let mut bytes = Vec::with_capacity(len);
+        for n in first..=last {
+            let block = self.block(n)?;
+            let from = offset.saturating_sub(n * self.block_size);
+            let to = ::std::cmp::min(block.len(), end - n * self.block_size);
+            bytes.extend_from_slice(&block[from..to]);
+        }
+        Ok(f(&bytes))
+    }
+}
+
+impl<'a, F> Source for EncryptedSource<'a, F>
+    where F: Fn(usize, &mut [u8])
+{
+    fn len(&self) -> usize {
+        EncryptedSource::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self.with_bytes(offset, len, |bytes| Cow::Owned(bytes.to_vec()))
+    }
+}
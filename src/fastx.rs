@@ -0,0 +1,190 @@
+use std::borrow::Cow;
+use std::io;
+
+use memchr::{memchr, memchr_iter};
+
+use TransducerError;
+
+/// The format of the records of a [SeqTransducer](struct.SeqTransducer.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeqFormat {
+    /// A `>` header line, followed by a sequence which may be wrapped over several lines
+    Fasta,
+    /// An `@` header line, a sequence line, a `+` separator line, and a quality line
+    Fastq,
+}
+
+/// Returns the line starting at `offset`, without its `\n` or `\r\n`, and the offset of the next
+fn line(bytes: &[u8], offset: usize) -> (&[u8], usize) {
+    let (end, next) = match memchr(b'\n', &bytes[offset..]) {
+        Some(len) => (offset + len, offset + len + 1),
+        None => (bytes.len(), bytes.len()),
+    };
+    match bytes[offset..end].split_last() {
+        Some((b'\r', rest)) => (rest, next),
+        _ => (&bytes[offset..end], next),
+    }
+}
+
+/// A sequence record of a [SeqTransducer](struct.SeqTransducer.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeqRecord<'a> {
+    /// The identifier, i.e., the header up to its first whitespace
+    pub id: &'a [u8],
+    /// The rest of the header, without leading whitespace
+    pub description: &'a [u8],
+    /// The sequence, which is only owned if it is joined from several lines
+    pub sequence: Cow<'a, [u8]>,
+    /// The quality scores of a FASTQ record, one byte per base
+    pub quality: Option<&'a [u8]>,
+}
+
+/// A lazy transducer over the records of a FASTA or FASTQ file, which splits out the header,
+/// sequence, and quality of a record only when it is accessed, so that the reads of a sequencing
+/// run can be sampled at random, or processed in parallel.
+///
+/// A single pass at construction records where every record starts in an index, so that
+/// afterwards, accessing a record is O(1); for FASTQ, the pass also validates the framing of every
+/// record, since a quality line may itself start with `@`. Line endings may be `\n` or `\r\n`.
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::{SeqFormat, SeqTransducer};
+///
+/// let fasta = b">chr1 first\nACGT\nAC\n>chr2\nTTGA\n";
+/// let lt = SeqTransducer::new(fasta).unwrap();
+/// assert_eq!(lt.format(), SeqFormat::Fasta);
+/// assert_eq!(lt.len(), 2);
+/// let record = lt.get(0).unwrap();
+/// assert_eq!((record.id, record.description), (&b"chr1"[..], &b"first"[..]));
+/// // the wrapped sequence is joined
+/// assert_eq!(&*record.sequence, b"ACGTAC");
+/// assert_eq!(lt.offset_of(1), Some(20));
+///
+/// let fastq = b"@read1\nGATT\n+\n@@?I\n@read2\nCA\n+read2\nII\n";
+/// let lt = SeqTransducer::new(fastq).unwrap();
+/// let quality = lt.into_iter().map(|record| record.quality.unwrap()).collect::<Vec<_>>();
+/// assert_eq!(quality, vec![&b"@@?I"[..], b"II"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SeqTransducer<'a> {
+    bytes: &'a [u8],
+    format: SeqFormat,
+    /// The offset of every record, plus the end of the last record as a final entry
+    offsets: Vec<usize>,
+}
+
+impl<'a> SeqTransducer<'a> {
+    /// Create a new lazy transducer over the records of the FASTA or FASTQ file in `bytes`,
+    /// detected from its first byte, returning an `InvalidData` I/O error if it is neither, and a
+    /// `Parse` error like [with_format](#method.with_format) otherwise. An empty file is FASTA,
+    /// with no records.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        match bytes.first() {
+            None | Some(b'>') => Self::with_format(bytes, SeqFormat::Fasta),
+            Some(b'@') => Self::with_format(bytes, SeqFormat::Fastq),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "not a FASTA or FASTQ file").into()),
+        }
+    }
+    /// Create a new lazy transducer over the records of `bytes`, in `format`, returning a `Parse`
+    /// error with the index and offset of the first record which does not start with a header, or,
+    /// for FASTQ, which is truncated, lacks its `+` separator, or whose quality differs in length
+    /// from its sequence.
+    pub fn with_format(bytes: &'a [u8], format: SeqFormat) -> Result<Self, TransducerError> {
+        let malformed = |idx, offset, msg| TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) };
+        let mut offsets = Vec::new();
+        match format {
+            SeqFormat::Fasta => if !bytes.is_empty() {
+                if bytes[0] != b'>' {
+                    return Err(malformed(0, 0, "record does not start with '>'"))
+                }
+                offsets.push(0);
+                offsets.extend(memchr_iter(b'\n', bytes).map(|end| end + 1).filter(|offset| bytes.get(*offset) == Some(&b'>')));
+            },
+            SeqFormat::Fastq => {
+                let mut offset = 0;
+                // trailing blank lines don't start a record
+                while bytes[offset..].iter().any(|byte| !byte.is_ascii_whitespace()) {
+                    let idx = offsets.len();
+                    let (header, next) = line(bytes, offset);
+                    if header.first() != Some(&b'@') {
+                        return Err(malformed(idx, offset, "record does not start with '@'"))
+                    }
+                    let (sequence, next) = line(bytes, next);
+                    let (separator, next) = line(bytes, next);
+                    if separator.first() != Some(&b'+') {
+                        return Err(malformed(idx, offset, "truncated record, or missing '+' separator"))
+                    }
+                    let (quality, next) = line(bytes, next);
+                    if quality.len() != sequence.len() {
+                        return Err(malformed(idx, offset, "quality and sequence differ in length"))
+                    }
+                    offsets.push(offset);
+                    offset = next;
+                }
+            },
+        }
+        offsets.push(bytes.len());
+        Ok(SeqTransducer { bytes, format, offsets })
+    }
+    /// The format of the records
+    pub fn format(&self) -> SeqFormat {
+        self.format
+    }
+    /// How many records are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the header of the `idx`th record, or `None` if the index is
+    /// greater than the number of records.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len() { None } else { Some(self.offsets[idx]) }
+    }
+    /// Get a record out of the lazy transducer, returning `None` if the index is greater than the
+    /// number of records in this lazy transducer.
+    pub fn get(&self, idx: usize) -> Option<SeqRecord<'a>> {
+        if idx >= self.len() {
+            return None
+        }
+        let bytes = &self.bytes[..self.offsets[idx + 1]];
+        let (header, mut offset) = line(bytes, self.offsets[idx]);
+        // skip the '>' or '@'
+        let header = &header[1..];
+        let id_len = header.iter().position(u8::is_ascii_whitespace).unwrap_or(header.len());
+        let description = &header[id_len..];
+        let description = &description[description.iter().position(|byte| !byte.is_ascii_whitespace()).unwrap_or(description.len())..];
+        let (sequence, quality) = match self.format {
+            SeqFormat::Fasta => {
+                let mut sequence = Cow::Borrowed(&[][..]);
+                while offset < bytes.len() {
+                    let (part, next) = line(bytes, offset);
+                    match sequence {
+                        Cow::Borrowed([]) => sequence = Cow::Borrowed(part),
+                        _ if part.is_empty() => (),
+                        _ => sequence.to_mut().extend_from_slice(part),
+                    }
+                    offset = next;
+                }
+                (sequence, None)
+            },
+            SeqFormat::Fastq => {
+                let (sequence, next) = line(bytes, offset);
+                let (_, next) = line(bytes, next);
+                (Cow::Borrowed(sequence), Some(line(bytes, next).0))
+            },
+        };
+        Some(SeqRecord { id: &header[..id_len], description, sequence, quality })
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the records, borrowing this one.
+    impl['a] SeqTransducer<'a> => <'b> SeqRecord<'a>
+    where []
+    parallel where []
+}
@@ -0,0 +1,145 @@
+use rayon::prelude::*;
+
+use LazyTransducer;
+use lazy_transducer::range_containing;
+
+const BITS: usize = 64;
+
+/// A lazily filtered view over a lazy transducer.
+///
+/// The predicate is evaluated exactly once per element when the view is constructed, and the
+/// matching indices are recorded in a compact bitmap (one bit per element). Afterwards, accessing
+/// the `i`th match is a rank/select query into the bitmap followed by a regular (lazy) `get` on the
+/// underlying transducer; i.e., the element payloads are only ever transduced on demand.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::LazyTransducer;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let data = [0u32, 7, 0, 0, 9, 11];
+/// let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+/// let defined = lt.filter_indexed(|n| *n != 0);
+///
+/// assert_eq!(defined.len(), 3);
+/// assert_eq!(defined.get(1), Some(9));
+/// assert_eq!(defined.index_of(1), Some(4));
+///
+/// let sum: u32 = defined.into_par_iter().sum();
+/// assert_eq!(sum, 27);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Filter<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    lt: LazyTransducer<'a, Input, Output>,
+    bitmap: Vec<u64>,
+    /// The number of set bits preceding each word of the bitmap, plus the total as a final entry
+    ranks: Vec<usize>,
+}
+
+impl<'a, Input, Output> Filter<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    fn from_bitmap(lt: LazyTransducer<'a, Input, Output>, bitmap: Vec<u64>) -> Self {
+        let mut ranks = Vec::with_capacity(bitmap.len() + 1);
+        let mut total = 0;
+        for word in &bitmap {
+            ranks.push(total);
+            total += word.count_ones() as usize;
+        }
+        ranks.push(total);
+        Filter { lt, bitmap, ranks }
+    }
+    /// How many elements matched the predicate
+    pub fn len(&self) -> usize {
+        self.ranks[self.ranks.len() - 1]
+    }
+    /// Whether no elements matched the predicate
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the index in the underlying lazy transducer of the `idx`th match, or `None` if
+    /// `idx` is greater than the number of matches.
+    pub fn index_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len() {
+            return None
+        }
+        let word = range_containing(&self.ranks, idx, |&rank| rank)?;
+        let mut bits = self.bitmap[word];
+        for _ in 0..(idx - self.ranks[word]) {
+            bits &= bits - 1;
+        }
+        Some(word * BITS + bits.trailing_zeros() as usize)
+    }
+    /// Get the `idx`th matching element, returning `None` if the index is greater than the number
+    /// of matches.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Output> {
+        self.index_of(idx).and_then(|idx| self.lt.get(idx))
+    }
+}
+
+/// The number of bitmap words needed for `count` elements, without overflowing
+fn nwords(count: usize) -> usize {
+    count.div_ceil(BITS)
+}
+
+fn bitmap_word<'a, Input: Copy, Output>(lt: &LazyTransducer<'a, Input, Output>, word: usize, predicate: fn(&Output) -> bool) -> u64 {
+    let start = word * BITS;
+    // the last word may be partial, and `start + BITS` may overflow for a huge count
+    let end = start + ::std::cmp::min(BITS, lt.count - start);
+    let mut bits = 0u64;
+    for idx in start..end {
+        if predicate(&(lt.transducer)(lt.contents, idx)) {
+            bits |= 1 << (idx - start);
+        }
+    }
+    bits
+}
+
+impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    /// Create a filtered view of this lazy transducer containing only the elements for which
+    /// `predicate` returns true; see [Filter](struct.Filter.html).
+    ///
+    /// Every element is transduced once here to evaluate the predicate; use
+    /// [par_filter_indexed](#method.par_filter_indexed) to do so in parallel.
+    pub fn filter_indexed(self, predicate: fn(&Output) -> bool) -> Filter<'a, Input, Output> {
+        let nwords = nwords(self.count);
+        let bitmap = (0..nwords).map(|word| bitmap_word(&self, word, predicate)).collect();
+        Filter::from_bitmap(self, bitmap)
+    }
+}
+
+impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
+    where Input: 'a + Sync + Send + Copy,
+          Output: 'a + Send + Sync,
+{
+    /// Create a filtered view of this lazy transducer, evaluating `predicate` over the elements in
+    /// parallel; see [filter_indexed](#method.filter_indexed).
+    pub fn par_filter_indexed(self, predicate: fn(&Output) -> bool) -> Filter<'a, Input, Output> {
+        let nwords = nwords(self.count);
+        let bitmap = {
+            let lt = &self;
+            (0..nwords).into_par_iter().map(|word| bitmap_word(lt, word, predicate)).collect()
+        };
+        Filter::from_bitmap(self, bitmap)
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the matching elements, borrowing this filter.
+    impl['a, Input, Output] Filter<'a, Input, Output> => <'b> Output
+    where [Input: Copy]
+    parallel where [Input: Sync + Send + Copy, Output: Send + Sync]
+}
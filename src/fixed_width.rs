@@ -0,0 +1,165 @@
+use std::io;
+use std::str;
+
+use TransducerError;
+
+/// How the text of a field of a [FixedWidthTransducer](struct.FixedWidthTransducer.html) is
+/// decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Text, with leading and trailing whitespace trimmed
+    Text,
+    /// A signed integer, possibly padded with whitespace
+    Int,
+    /// A floating point number, possibly padded with whitespace
+    Float,
+}
+
+/// Where a field lies in every record of a [FixedWidthTransducer](struct.FixedWidthTransducer.html),
+/// and how it is decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedField {
+    /// The byte offset of the field from the start of the record
+    pub offset: usize,
+    /// The size in bytes of the field
+    pub width: usize,
+    /// How the field is decoded
+    pub kind: FieldKind,
+}
+
+impl FixedField {
+    /// A text field of `width` bytes at `offset`
+    pub fn text(offset: usize, width: usize) -> Self {
+        FixedField { offset, width, kind: FieldKind::Text }
+    }
+    /// An integer field of `width` bytes at `offset`
+    pub fn int(offset: usize, width: usize) -> Self {
+        FixedField { offset, width, kind: FieldKind::Int }
+    }
+    /// A floating point field of `width` bytes at `offset`
+    pub fn float(offset: usize, width: usize) -> Self {
+        FixedField { offset, width, kind: FieldKind::Float }
+    }
+    /// The byte offset of the end of the field from the start of the record, or `None` if it
+    /// overflows
+    fn end(&self) -> Option<usize> {
+        self.offset.checked_add(self.width)
+    }
+}
+
+/// A decoded field of a [FixedWidthTransducer](struct.FixedWidthTransducer.html)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FixedValue<'a> {
+    /// The trimmed text of a `Text` field
+    Text(&'a str),
+    /// The value of an `Int` field
+    Int(i64),
+    /// The value of a `Float` field
+    Float(f64),
+    /// A numeric field which is blank
+    Null,
+}
+
+/// A lazy transducer over fixed-width text records, as in mainframe extracts and many flat files,
+/// which decodes the fields of a record only when it is accessed.
+///
+/// Every record is `record_len` bytes, including any line terminator, and its fields are described
+/// by a layout of [FixedField](struct.FixedField.html)s; text fields are trimmed, and numeric
+/// fields are parsed, yielding [FixedValue](enum.FixedValue.html)s. Since every record has the same
+/// size, accessing a record, or a single field of it, is O(1).
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::{FixedWidthTransducer, FixedField, FixedValue};
+///
+/// let bytes = b"ALICE     0042  3.50\nBOB       0007      \nCAROL     0100 12.25";
+/// let layout = vec![FixedField::text(0, 10), FixedField::int(10, 4), FixedField::float(14, 6)];
+/// let lt = FixedWidthTransducer::new(bytes, 21, layout).unwrap();
+/// // the last record has no line terminator
+/// assert_eq!(lt.len(), 3);
+/// assert_eq!(lt.get(0).unwrap().unwrap(), vec![FixedValue::Text("ALICE"), FixedValue::Int(42), FixedValue::Float(3.5)]);
+/// assert_eq!(lt.field(1, 2).unwrap().unwrap(), FixedValue::Null);
+/// let total: i64 = (0..lt.len()).map(|idx| match lt.field(idx, 1).unwrap().unwrap() {
+///     FixedValue::Int(n) => n,
+///     _ => 0,
+/// }).sum();
+/// assert_eq!(total, 149);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedWidthTransducer<'a> {
+    bytes: &'a [u8],
+    record_len: usize,
+    fields: Vec<FixedField>,
+    len: usize,
+}
+
+impl<'a> FixedWidthTransducer<'a> {
+    /// Create a new lazy transducer over the records of `record_len` bytes in `bytes`, whose fields
+    /// are laid out according to `fields`, returning an `InvalidInput` I/O error if a field does
+    /// not fit in a record.
+    ///
+    /// A final record which is shorter than `record_len`, e.g., because it lacks a line
+    /// terminator, is included if every field fits in it; otherwise, it is ignored.
+    pub fn new(bytes: &'a [u8], record_len: usize, fields: Vec<FixedField>) -> Result<Self, TransducerError> {
+        let fields_len = fields.iter()
+            .try_fold(0, |len, field| field.end().map(|end| ::std::cmp::max(len, end)))
+            .filter(|&fields_len| record_len != 0 && fields_len <= record_len)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "fields do not fit in a record"))?;
+        let remainder = bytes.len() % record_len;
+        let len = bytes.len() / record_len + if remainder > 0 && remainder >= fields_len { 1 } else { 0 };
+        Ok(FixedWidthTransducer { bytes, record_len, fields, len })
+    }
+    /// The layout of the fields of every record
+    pub fn fields(&self) -> &[FixedField] {
+        &self.fields
+    }
+    /// How many records are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Whether this lazy transducer has no records
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns the byte offset of the `idx`th record, or `None` if the index is greater than the
+    /// number of records.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len { None } else { Some(idx * self.record_len) }
+    }
+    /// Decode the `field`th field of a record out of the lazy transducer, returning `None` if
+    /// either index is out of bounds, and a `Parse` error, with the offset of the field, if it is
+    /// not valid UTF-8, or is not a number.
+    pub fn field(&self, idx: usize, field: usize) -> Option<Result<FixedValue<'a>, TransducerError>> {
+        let field = *self.fields.get(field)?;
+        let offset = self.offset_of(idx)? + field.offset;
+        let bytes = &self.bytes[offset..offset + field.width];
+        let parse = |err| TransducerError::Parse{ idx, offset, source: err };
+        Some(str::from_utf8(bytes).map_err(|err| parse(Box::new(err))).and_then(|text| {
+            let text = text.trim();
+            match field.kind {
+                FieldKind::Text => Ok(FixedValue::Text(text)),
+                _ if text.is_empty() => Ok(FixedValue::Null),
+                FieldKind::Int => text.parse().map(FixedValue::Int).map_err(|err| parse(Box::new(err))),
+                FieldKind::Float => text.parse().map(FixedValue::Float).map_err(|err| parse(Box::new(err))),
+            }
+        }))
+    }
+    /// Decode every field of a record out of the lazy transducer, returning `None` if the index is
+    /// greater than the number of records in this lazy transducer, and the `Parse` error of the
+    /// first field which fails to decode.
+    pub fn get(&self, idx: usize) -> Option<Result<Vec<FixedValue<'a>>, TransducerError>> {
+        if idx >= self.len {
+            return None
+        }
+        // we unwrap because both indices are in bounds
+        Some((0..self.fields.len()).map(|field| self.field(idx, field).unwrap()).collect())
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the decoded records, borrowing this one.
+    impl['a] FixedWidthTransducer<'a> => <'b> Result<Vec<FixedValue<'a>>, TransducerError>
+    where []
+    parallel where []
+}
@@ -0,0 +1,110 @@
+use LazyTransducer;
+use lazy_transducer::range_containing;
+
+/// A lazily flattened view over a lazy transducer of lazy transducers, e.g., sections which each
+/// contain a table of entries.
+///
+/// Every outer element is transduced once when the view is constructed, to obtain its inner lazy
+/// transducer, and the cumulative number of inner elements preceding each one is recorded in an
+/// index. Afterwards, accessing the `i`th inner element is a binary search into the index followed
+/// by a regular (lazy) `get` on the inner transducer containing it.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::LazyTransducer;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// // three sections, as (start, count) pairs into a table of entries
+/// static ENTRIES: [u32; 6] = [10, 11, 12, 20, 30, 31];
+/// let sections = [(0, 3), (3, 1), (4, 2)];
+/// let lt: LazyTransducer<&[(usize, usize)], (usize, usize)> = LazyTransducer::new(&sections, 3, |input, idx| input[idx]);
+/// let entries = lt.flat_map_lazy(|(start, count)| {
+///   LazyTransducer::new(&ENTRIES[start..start + count], count, |input, idx| input[idx])
+/// });
+///
+/// assert_eq!(entries.len(), 6);
+/// assert_eq!(entries.locate(4), Some((2, 0)));
+/// assert_eq!(entries.get(4), Some(30));
+///
+/// let sum: u32 = entries.into_par_iter().sum();
+/// assert_eq!(sum, 114);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FlatMap<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    sections: Vec<LazyTransducer<'a, Input, Output>>,
+    /// The number of inner elements preceding each section, plus the total as a final entry
+    starts: Vec<usize>,
+}
+
+impl<'a, Input, Output> FlatMap<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    /// How many inner elements there are, across all sections
+    pub fn len(&self) -> usize {
+        self.starts[self.starts.len() - 1]
+    }
+    /// Whether there are no inner elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// How many sections, i.e., outer elements, there are
+    pub fn sections(&self) -> usize {
+        self.sections.len()
+    }
+    /// Returns the `(section, entry)` pair of the `idx`th inner element, or `None` if `idx` is
+    /// greater than the number of inner elements.
+    pub fn locate(&self, idx: usize) -> Option<(usize, usize)> {
+        if idx >= self.len() {
+            return None
+        }
+        let section = range_containing(&self.starts, idx, |&start| start)?;
+        Some((section, idx - self.starts[section]))
+    }
+    /// Get the `idx`th inner element, returning `None` if the index is greater than the number of
+    /// inner elements.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Output> {
+        self.locate(idx).and_then(|(section, entry)| self.sections[section].get(entry))
+    }
+}
+
+impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    /// Create a flattened view over the inner lazy transducers which `f` returns for each element
+    /// of this one; see [FlatMap](struct.FlatMap.html).
+    ///
+    /// Every element is transduced once here to build the index, but the inner elements are only
+    /// transduced on demand.
+    pub fn flat_map_lazy<InnerInput, Inner>(self, f: fn(Output) -> LazyTransducer<'a, InnerInput, Inner>) -> FlatMap<'a, InnerInput, Inner>
+        where InnerInput: 'a + Copy,
+              Inner: 'a,
+    {
+        let sections: Vec<_> = self.into_iter().map(f).collect();
+        let mut starts = Vec::with_capacity(sections.len() + 1);
+        let mut total: usize = 0;
+        for section in &sections {
+            starts.push(total);
+            total = total.checked_add(section.len()).expect("the total number of inner elements overflows");
+        }
+        starts.push(total);
+        FlatMap { sections, starts }
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the inner elements, borrowing this view.
+    impl['a, Input, Output] FlatMap<'a, Input, Output> => <'b> Output
+    where [Input: Copy]
+    parallel where [Input: Sync + Send + Copy, Output: Send + Sync]
+}
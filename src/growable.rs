@@ -0,0 +1,196 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "scroll")]
+use scroll::{self, ctx};
+#[cfg(feature = "scroll")]
+use scroll::ctx::SizeWith;
+
+use {LazyTransducer, Source, TransducerError};
+use source::overflow;
+
+/// An input source over a file which is still being appended to, e.g., a log, whose length is
+/// re-checked by [refresh](#method.refresh).
+///
+/// Bytes are read on demand with a seek and a read, and only the bytes which were present at the
+/// last refresh are visible; a [GrowableTransducer](struct.GrowableTransducer.html) over it picks
+/// up the newly appended elements after every refresh.
+///
+/// If the file is truncated, e.g., when a log is rotated, reading bytes past its new end is an
+/// `Io` error until the next refresh.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{GrowableSource, GrowableTransducer, Endian};
+/// use std::fs::OpenOptions;
+/// use std::io::Write;
+///
+/// # fn main() {
+/// let path = std::env::temp_dir().join("lazy_transducer_growable_example");
+/// std::fs::write(&path, [1u8, 0, 2, 0]).unwrap();
+///
+/// let source = GrowableSource::open(&path).unwrap();
+/// let lt: GrowableTransducer<_, u16, _> = GrowableTransducer::parse_with(&source, Endian::Little);
+/// assert_eq!(lt.len(), 2);
+///
+/// OpenOptions::new().append(true).open(&path).unwrap().write_all(&[3, 0, 4]).unwrap();
+/// assert_eq!(lt.get(2), None);
+/// source.refresh().unwrap();
+/// // the trailing byte is not a whole element yet
+/// assert_eq!(lt.len(), 3);
+/// assert_eq!(lt.get(2), Some(3));
+/// # }
+/// ```
+pub struct GrowableSource<R> {
+    reader: Mutex<R>,
+    len: AtomicUsize,
+}
+
+impl<R> ::std::fmt::Debug for GrowableSource<R> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("GrowableSource")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl GrowableSource<File> {
+    /// Open the file at `path` as a growable source.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        GrowableSource::new(File::open(path)?)
+    }
+}
+
+impl<R> GrowableSource<R> {
+    /// The size in bytes of the input source, as of the last refresh
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+    /// Whether the input source was empty as of the last refresh
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<R: Read + Seek> GrowableSource<R> {
+    /// Create a new growable source over `reader`, whose current length is visible.
+    pub fn new(reader: R) -> io::Result<Self> {
+        let source = GrowableSource { reader: Mutex::new(reader), len: AtomicUsize::new(0) };
+        source.refresh()?;
+        Ok(source)
+    }
+    /// Re-check the length of the input source, making any bytes appended since the last refresh
+    /// visible, and return the new length.
+    pub fn refresh(&self) -> io::Result<usize> {
+        let mut reader = self.reader.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let len = reader.seek(SeekFrom::End(0))?;
+        if len > usize::MAX as u64 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "input source is larger than a usize"))
+        }
+        self.len.store(len as usize, Ordering::Release);
+        Ok(len as usize)
+    }
+    /// Returns the `len` bytes starting at `offset`, returning an error if they are not all in
+    /// the input source as of the last refresh, or can't be read.
+    pub fn read(&self, offset: usize, len: usize) -> Result<Vec<u8>, TransducerError> {
+        offset.checked_add(len)
+            .filter(|end| *end <= self.len())
+            .ok_or_else(|| overflow(offset, len, self.len()))?;
+        let mut bytes = vec![0; len];
+        let mut reader = self.reader.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        reader.seek(SeekFrom::Start(offset as u64))?;
+        reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl<R: Read + Seek> Source for GrowableSource<R> {
+    fn len(&self) -> usize {
+        GrowableSource::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self.read(offset, len).map(Cow::Owned)
+    }
+}
+
+/// A lazy transducer over the elements of a [GrowableSource](struct.GrowableSource.html), which
+/// are `stride` bytes apart, whose number grows whenever the source is refreshed.
+///
+/// The transducer receives the `(source, ctx)` pair; use `()` if you don't need a context.
+pub struct GrowableTransducer<'a, R: 'a, Output, Ctx = ()> {
+    source: &'a GrowableSource<R>,
+    ctx: Ctx,
+    stride: usize,
+    transducer: fn((&'a GrowableSource<R>, Ctx), usize) -> Output,
+}
+
+impl<'a, R, Output, Ctx: Copy> GrowableTransducer<'a, R, Output, Ctx> {
+    /// Create a new lazy transducer over the elements of `source`, which are `stride` bytes apart.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn new(source: &'a GrowableSource<R>, stride: usize, ctx: Ctx, transducer: fn((&'a GrowableSource<R>, Ctx), usize) -> Output) -> Self {
+        assert!(stride != 0, "stride must be non-zero");
+        GrowableTransducer { source, ctx, stride, transducer }
+    }
+    /// How many whole elements the source contained as of its last refresh
+    pub fn len(&self) -> usize {
+        self.source.len() / self.stride
+    }
+    /// Whether the source contained no whole elements as of its last refresh
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Get the `idx`th element, returning `None` if the index is greater than the number of
+    /// elements as of the last refresh.
+    pub fn get(&self, idx: usize) -> Option<Output> {
+        if idx >= self.len() { None } else { Some((self.transducer)((self.source, self.ctx), idx)) }
+    }
+    /// Returns a lazy transducer over the elements as of the last refresh; it does not grow with
+    /// later refreshes.
+    pub fn transducer(&self) -> LazyTransducer<'a, (&'a GrowableSource<R>, Ctx), Output> {
+        LazyTransducer::new((self.source, self.ctx), self.len(), self.transducer)
+    }
+}
+
+impl<'a, R, Output, Ctx: Copy> Clone for GrowableTransducer<'a, R, Output, Ctx> {
+    fn clone(&self) -> Self {
+        GrowableTransducer { source: self.source, ctx: self.ctx, stride: self.stride, transducer: self.transducer }
+    }
+}
+
+impl<'a, R, Output, Ctx: Debug> Debug for GrowableTransducer<'a, R, Output, Ctx> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("GrowableTransducer")
+            .field("source", self.source)
+            .field("ctx", &self.ctx)
+            .field("stride", &self.stride)
+            .finish()
+    }
+}
+
+#[cfg(feature = "scroll")]
+impl<'a, R, Output, Ctx, E> GrowableTransducer<'a, R, Output, Ctx>
+    where R: Read + Seek,
+          Ctx: Copy,
+          Output: for<'b> ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>,
+          E: From<scroll::Error> + ::std::error::Error + Send + Sync + 'static,
+{
+    /// Create a new scroll-based lazy transducer over the elements of `source`.
+    pub fn parse_with(source: &'a GrowableSource<R>, ctx: Ctx) -> Self {
+        // we unwrap because only elements which were in the source at its last refresh are
+        // transduced; the file can still fail to read, e.g., if it was truncated, in which case we panic
+        GrowableTransducer::new(source, Output::size_with(&ctx), ctx, |(source, ctx), idx| {
+            source.pread_with(idx, idx * Output::size_with(&ctx), ctx).unwrap()
+        })
+    }
+}
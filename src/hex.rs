@@ -0,0 +1,88 @@
+use std::borrow::Cow;
+use std::io;
+
+use {Source, TransducerError};
+use source::overflow;
+
+/// The value of a hex digit, in either case
+fn nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// An input source over hex encoded bytes, e.g., a table pasted as hex into an issue report or a
+/// test fixture, which decodes bytes on demand.
+///
+/// The `n`th byte is the `n`th pair of hex digits, so reading some bytes only decodes their pairs,
+/// and the text is never decoded up front. Digits may be in either case; separators, such as
+/// whitespace, are not accepted.
+///
+/// An invalid digit is only detected when a byte containing it is read, which is an `Io` error
+/// with kind `InvalidData`.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{HexSource, ScrollTransducer, Endian};
+///
+/// # fn main() {
+/// let source = HexSource::new(b"deadbeefCAFED00D").unwrap();
+/// assert_eq!(source.len(), 8);
+/// let lt: ScrollTransducer<u32, _, _> = ScrollTransducer::parse_with(&source, 2, Endian::Big).unwrap();
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![0xdeadbeef, 0xcafed00d]);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HexSource<'a> {
+    encoded: &'a [u8],
+}
+
+impl<'a> HexSource<'a> {
+    /// Create a new hex source over `encoded`, returning an error if it has an odd number of
+    /// digits.
+    pub fn new(encoded: &'a [u8]) -> Result<Self, TransducerError> {
+        if encoded.len() & 1 == 1 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "odd number of hex digits").into())
+        }
+        Ok(HexSource { encoded })
+    }
+    /// The decoded size in bytes of the input source
+    pub fn len(&self) -> usize {
+        self.encoded.len() / 2
+    }
+    /// Whether the input source is empty
+    pub fn is_empty(&self) -> bool {
+        self.encoded.is_empty()
+    }
+    /// Returns the `len` decoded bytes starting at `offset`, returning an error if they are not all
+    /// in the input source, or are not valid hex.
+    pub fn read(&self, offset: usize, len: usize) -> Result<Vec<u8>, TransducerError> {
+        let end = offset.checked_add(len)
+            .filter(|end| *end <= self.len())
+            .ok_or_else(|| overflow(offset, len, self.len()))?;
+        self.encoded[offset * 2..end * 2].chunks(2).enumerate().map(|(i, pair)| {
+            match (nibble(pair[0]), nibble(pair[1])) {
+                (Some(high), Some(low)) => Ok(high << 4 | low),
+                _ => {
+                    let msg = format!("invalid hex digits {:?} at offset {}", String::from_utf8_lossy(pair), (offset + i) * 2);
+                    Err(io::Error::new(io::ErrorKind::InvalidData, msg).into())
+                },
+            }
+        }).collect()
+    }
+}
+
+impl<'a> Source for HexSource<'a> {
+    fn len(&self) -> usize {
+        HexSource::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self.read(offset, len).map(Cow::Owned)
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::{self, Read, Seek, SeekFrom};
+
+use ureq;
+
+use PagedSource;
+
+/// A paged source over a remote file, whose pages are fetched with HTTP range requests; see
+/// [from_url](struct.PagedSource.html#method.from_url).
+pub type HttpSource = PagedSource<HttpReader>;
+
+fn other<E: ::std::fmt::Display>(err: E) -> io::Error {
+    io::Error::other(err.to_string())
+}
+
+/// A `Read + Seek` view of a remote file, where every read is a single HTTP range request, and
+/// seeking is free.
+///
+/// This is meant to be wrapped in a [PagedSource](struct.PagedSource.html), which reads whole
+/// pages, so that every page costs exactly one request.
+#[derive(Debug)]
+pub struct HttpReader {
+    agent: ureq::Agent,
+    url: String,
+    len: u64,
+    pos: u64,
+}
+
+impl HttpReader {
+    /// Create a new reader for the file at `url`, returning an error if the server does not
+    /// support range requests.
+    pub fn new(url: &str) -> io::Result<Self> {
+        let agent = ureq::Agent::new();
+        // probe with a single byte, which tells us the size of the file and whether ranges work
+        let response = agent.get(url).set("Range", "bytes=0-0").call().map_err(other)?;
+        if response.status() != 206 {
+            return Err(other(format!("{} does not support range requests", url)))
+        }
+        let len = response.header("Content-Range")
+            .and_then(|range| range.rsplit('/').next())
+            .and_then(|len| len.trim().parse().ok())
+            .ok_or_else(|| other(format!("{} did not report its size", url)))?;
+        Ok(HttpReader { agent, url: url.to_owned(), len, pos: 0 })
+    }
+    /// The size in bytes of the remote file
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+    /// Whether the remote file is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Read for HttpReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.len || buf.is_empty() {
+            return Ok(0)
+        }
+        let n = ::std::cmp::min(buf.len() as u64, self.len - self.pos) as usize;
+        let range = format!("bytes={}-{}", self.pos, self.pos + n as u64 - 1);
+        let response = self.agent.get(&self.url).set("Range", &range).call().map_err(other)?;
+        if response.status() != 206 {
+            return Err(other(format!("{} ignored the range request", self.url)))
+        }
+        response.into_reader().read_exact(&mut buf[..n])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for HttpReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::End(delta) => (self.len as i64).checked_add(delta).filter(|pos| *pos >= 0).map(|pos| pos as u64),
+            SeekFrom::Current(delta) => (self.pos as i64).checked_add(delta).filter(|pos| *pos >= 0).map(|pos| pos as u64),
+        };
+        self.pos = pos.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek to a negative position"))?;
+        Ok(self.pos)
+    }
+}
+
+impl PagedSource<HttpReader> {
+    /// Create a new paged source over the remote file at `url`, which fetches pages of
+    /// `page_size` bytes with HTTP range requests, so that a table inside a huge remote file can
+    /// be parsed lazily without downloading all of it.
+    ///
+    /// # Example
+    ///
+    #[cfg_attr(feature = "scroll", doc = "```rust,no_run")]
+    #[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+    /// extern crate lazy_transducer;
+    /// use lazy_transducer::{PagedSource, ScrollTransducer, Endian};
+    ///
+    /// # fn main() {
+    /// let source = PagedSource::from_url("https://example.com/huge.debug", 64 * 1024).unwrap();
+    /// let lt: ScrollTransducer<u64, _, _> = ScrollTransducer::parse_with_at(&source, 0x1000, 16, Endian::Little).unwrap();
+    /// // only the page containing the table is fetched
+    /// println!("{:?}", lt.get(3));
+    /// # }
+    /// ```
+    pub fn from_url(url: &str, page_size: usize) -> io::Result<Self> {
+        PagedSource::new(HttpReader::new(url)?, page_size)
+    }
+}
@@ -0,0 +1,90 @@
+
+use LazyTransducer;
+
+/// A lazy transducer which pairs each record of another lazy transducer with a separate string
+/// table (or any other blob), e.g., symbols and their names in ELF or PE binaries.
+///
+/// The join function is only applied when an element is requested, so looking up names costs
+/// nothing for the records which are never accessed.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// #[macro_use]
+/// extern crate scroll;
+/// extern crate rayon;
+/// use lazy_transducer::{JoinedTransducer, ScrollTransducer};
+/// use scroll::Pread;
+/// use rayon::prelude::*;
+///
+/// #[derive(Debug, Pread, SizeWith)]
+/// #[repr(C)]
+/// pub struct Sym {
+///     pub st_name: u32,
+///     pub st_value: u32,
+/// }
+///
+/// # fn main() {
+/// let symtab = [1, 0, 0, 0, 0xef, 0xbe, 0xad, 0xde, 6, 0, 0, 0, 0x0d, 0xd0, 0xfe, 0xca];
+/// let strtab = b"\0main\0_start\0";
+/// let syms: ScrollTransducer<Sym> = ScrollTransducer::parse(&symtab[..], scroll::LE).unwrap();
+/// let named = JoinedTransducer::new(syms, strtab, |sym, strtab| {
+///   (strtab.pread::<&str>(sym.st_name as usize).unwrap_or(""), sym.st_value)
+/// });
+///
+/// assert_eq!(named.get(1), Some(("_start", 0xcafed00d)));
+/// named.into_par_iter().for_each(|(name, value)| {
+///   println!("{:x}: {}", value, name);
+/// });
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct JoinedTransducer<'a, Input, Output, Joined>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    records: LazyTransducer<'a, Input, Output>,
+    strtab: &'a [u8],
+    join: fn(Output, &'a [u8]) -> Joined,
+}
+
+impl<'a, Input, Output, Joined> JoinedTransducer<'a, Input, Output, Joined>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    /// Create a new joined transducer, which yields `join(record, strtab)` for every record.
+    pub fn new(records: LazyTransducer<'a, Input, Output>, strtab: &'a [u8], join: fn(Output, &'a [u8]) -> Joined) -> Self {
+        JoinedTransducer { records, strtab, join }
+    }
+    /// How many elements are contained in this lazy transducer, i.e., the number of records
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Get the `idx`th record joined with the string table, returning `None` if the index is
+    /// greater than the number of records.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Joined> {
+        self.records.get(idx).map(|record| (self.join)(record, self.strtab))
+    }
+}
+
+impl<'a, Input: Copy, Output, Joined> Clone for JoinedTransducer<'a, Input, Output, Joined> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, Input: Copy, Output, Joined> Copy for JoinedTransducer<'a, Input, Output, Joined> {}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the joined elements, borrowing this one.
+    impl['a, Input, Output, Joined] JoinedTransducer<'a, Input, Output, Joined> => <'b> Joined
+    where [Input: Copy]
+    parallel where [Input: Sync + Send + Copy, Output: Send + Sync, Joined: Send + Sync]
+}
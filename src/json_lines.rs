@@ -0,0 +1,100 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde_json;
+
+use {Delimited, TransducerError};
+
+/// A lazy transducer over a JSON Lines (or NDJSON) file, e.g., a multi-gigabyte structured log,
+/// which deserializes a line only when it is accessed.
+///
+/// The lines are indexed like [Delimited::lines](struct.Delimited.html#method.lines), so that
+/// accessing line `i` is O(1), and the lines can be deserialized in parallel, via
+/// [serde_json](https://docs.rs/serde_json).
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// #[macro_use]
+/// extern crate serde_derive;
+/// use lazy_transducer::JsonLines;
+/// use rayon::prelude::*;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Event {
+///     level: String,
+///     ms: u64,
+/// }
+///
+/// # fn main() {
+/// let log = br#"{"level":"info","ms":12}
+/// {"level":"error","ms":340}
+/// {"level":"info","ms":7}
+/// "#;
+/// let lt: JsonLines<Event> = JsonLines::new(log);
+/// assert_eq!(lt.len(), 3);
+/// assert_eq!(lt.get(1).unwrap().unwrap().level, "error");
+/// let slow = lt.into_par_iter().filter(|event| event.as_ref().unwrap().ms > 100).count();
+/// assert_eq!(slow, 1);
+/// # }
+/// ```
+pub struct JsonLines<'a, T> {
+    lines: Delimited<'a>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> fmt::Debug for JsonLines<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JsonLines")
+            .field("lines", &self.lines)
+            .finish()
+    }
+}
+
+impl<'a, T> Clone for JsonLines<'a, T> {
+    fn clone(&self) -> Self {
+        JsonLines { lines: self.lines.clone(), _marker: PhantomData }
+    }
+}
+
+impl<'a, T: DeserializeOwned> JsonLines<'a, T> {
+    /// Create a new lazy transducer over the lines of `bytes`, each of which is a JSON value.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        JsonLines { lines: Delimited::lines(bytes), _marker: PhantomData }
+    }
+    /// How many lines are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+    /// Whether this lazy transducer has no lines
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+    /// Returns the byte offset of the `idx`th line, or `None` if the index is greater than the
+    /// number of lines.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.lines.offset_of(idx)
+    }
+    /// Returns the raw bytes of the `idx`th line, or `None` if the index is greater than the
+    /// number of lines.
+    pub fn line(&self, idx: usize) -> Option<&'a [u8]> {
+        self.lines.get(idx)
+    }
+    /// Deserialize a line out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of lines in this lazy transducer, and a `Parse` error if it fails to deserialize.
+    pub fn get(&self, idx: usize) -> Option<Result<T, TransducerError>> {
+        self.line(idx).map(|line| {
+            serde_json::from_slice(line).map_err(|err| TransducerError::Parse{ idx, offset: self.lines.offset_of(idx).unwrap(), source: Box::new(err) })
+        })
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the deserialized lines, borrowing this one.
+    impl['a, T] JsonLines<'a, T> => <'b> Result<T, TransducerError>
+    where [T: DeserializeOwned]
+    parallel where [T: DeserializeOwned + Send + Sync]
+}
@@ -1,14 +1,23 @@
+use std::cmp;
+#[cfg(feature = "scroll")]
+use std::error;
+#[cfg(feature = "scroll")]
 use std::fmt::Debug;
+use std::iter::FusedIterator;
 use std::marker::PhantomData;
+use std::mem;
 
 use rayon::iter::*;
-use rayon::iter::plumbing::*;
-use failure::Error;
+use rayon::iter::plumbing::{self, *};
 
+#[cfg(feature = "scroll")]
 use scroll::{self, ctx, Pread};
+#[cfg(feature = "scroll")]
 use scroll::ctx::SizeWith;
 
-use TransducerError;
+use {CancelToken, Source, TransducerError};
+#[cfg(feature = "scroll")]
+use {TruncationPolicy, TryLazyTransducer, ValidationReport};
 
 /// A lazy transducer transforms `n` elements from a source type into an output type.
 ///
@@ -49,7 +58,8 @@ use TransducerError;
 /// # Advanced Example
 ///
 /// This example uses the [bincode](https://github.com/TyOverby/bincode) binary serializer as
-/// its transducer.
+/// its transducer; with the `bincode` feature, [BincodeTransducer](struct.BincodeTransducer.html)
+/// does the same offset math for you.
 ///
 /// ```rust
 /// extern crate lazy_transducer;
@@ -60,7 +70,7 @@ use TransducerError;
 /// extern crate rayon;
 ///
 /// use lazy_transducer::LazyTransducer;
-/// use bincode::{serialize, deserialize, Infinite, Error};
+/// use bincode::{serialize, deserialize, Error};
 /// use rayon::prelude::*;
 ///
 /// #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -76,9 +86,9 @@ use TransducerError;
 ///
 ///   // we need to serialize the data, which we do by extending a byte vector with the individually
 ///   // serialized components
-///   let mut data = serialize(&foo1, Infinite)?;
+///   let mut data = serialize(&foo1)?;
 ///   let sizeof_serialized_element = data.len();
-///   data.extend_from_slice(&serialize(&foo2, Infinite)?);
+///   data.extend_from_slice(&serialize(&foo2)?);
 ///
 ///   // we construct our transducer by providing the serialized bytes _and_ the size of a serialized
 ///   // element as input; our transducer just reads at the appropriate byte offset, and deserializes!
@@ -119,6 +129,26 @@ impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
     pub fn len(&self) -> usize {
         self.count
     }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// Iterate over the elements of this lazy transducer without copying it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    /// let data = [0xdeadbeefu32, 0xcafed00d];
+    /// let lt: LazyTransducer<&[u32], u64> = LazyTransducer::new(&data, 2, |input, idx| input[idx] as u64);
+    /// for n in lt.iter() {
+    ///   println!("{:x}", n);
+    /// }
+    /// assert_eq!(lt.iter().rev().next(), Some(0xcafed00d));
+    /// ```
+    pub fn iter<'b>(&'b self) -> Iter<'b, 'a, Input, Output> {
+        Iter::new(self, 0, self.count)
+    }
     /// Create a new LazyTransducer with `count` elements in `contents`, using `transducer` to extract
     /// them.
     ///
@@ -138,7 +168,39 @@ impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
             count,
             contents,
             transducer,
-            _marker: PhantomData::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a new LazyTransducer like [new](#method.new), but for byte [sources](trait.Source.html),
+    /// additionally checking that `count` elements of `sizeof_element` bytes each fit in `contents`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    /// use std::mem::size_of;
+    ///
+    /// let bytes = [1u8, 0, 2, 0, 3, 0];
+    /// let read = |input: &[u8], idx| input[idx * 2] as u16 | (input[idx * 2 + 1] as u16) << 8;
+    /// let lt: LazyTransducer<&[u8], u16> = LazyTransducer::new_checked(&bytes[..], 3, size_of::<u16>(), read).unwrap();
+    /// assert_eq!(lt.get(2), Some(3));
+    ///
+    /// let lying = LazyTransducer::new_checked(&bytes[..], 4, size_of::<u16>(), read);
+    /// assert!(lying.is_err());
+    /// ```
+    pub fn new_checked(contents: Input,
+                       count: usize,
+                       sizeof_element: usize,
+                       transducer: fn(Input, usize) -> Output)
+                       -> Result<Self, TransducerError>
+        where Input: Source
+    {
+        let src_size = contents.len();
+        if total_size(count, sizeof_element)? > src_size {
+            Err(TransducerError::ElementOverflow{ nelements: count, sizeof_element, stride: sizeof_element, src_size })
+        } else {
+            Ok(Self::new(contents, count, transducer))
         }
     }
 
@@ -169,6 +231,268 @@ impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
             Some((self.transducer)(self.contents, idx))
         }
     }
+
+    /// Call `f` on every element in order, stopping at, and returning, the first error it returns.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let data = [1u32, 2, 3, 4];
+    /// let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, 4, |input, idx| input[idx]);
+    /// let mut seen = Vec::new();
+    /// let res = lt.try_for_each(|n| {
+    ///   if n == 3 { return Err(n) }
+    ///   seen.push(n);
+    ///   Ok(())
+    /// });
+    /// assert_eq!(res, Err(3));
+    /// assert_eq!(seen, vec![1, 2]);
+    /// ```
+    pub fn try_for_each<E, F>(&self, f: F) -> Result<(), E>
+        where F: FnMut(Output) -> Result<(), E>
+    {
+        self.iter().try_for_each(f)
+    }
+
+    /// Zip this lazy transducer with `other`, yielding pairs of elements at the same index.
+    ///
+    /// The result is itself a lazy transducer, so it is still indexable and parallel; its length is
+    /// the minimum of the two lengths.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate lazy_transducer;
+    /// extern crate rayon;
+    /// use lazy_transducer::LazyTransducer;
+    /// use rayon::prelude::*;
+    ///
+    /// # fn main() {
+    /// let symbols = [0xdeadbeefu32, 0xcafed00d, 0x1337];
+    /// let versions = [1u16, 2];
+    /// let syms: LazyTransducer<&[u32], u32> = LazyTransducer::new(&symbols, 3, |input, idx| input[idx]);
+    /// let vers: LazyTransducer<&[u16], u16> = LazyTransducer::new(&versions, 2, |input, idx| input[idx]);
+    ///
+    /// let zipped = syms.zip(vers);
+    /// assert_eq!(zipped.len(), 2);
+    /// assert_eq!(zipped.get(1), Some((0xcafed00d, 2)));
+    ///
+    /// zipped.into_par_iter().for_each(|(sym, ver)| {
+    ///   println!("{:x}: {}", sym, ver);
+    /// });
+    /// # }
+    /// ```
+    pub fn zip<OtherInput, OtherOutput>(self, other: LazyTransducer<'a, OtherInput, OtherOutput>)
+                                        -> Zip<'a, Input, Output, OtherInput, OtherOutput>
+        where OtherInput: 'a + Copy,
+              OtherOutput: 'a
+    {
+        LazyTransducer {
+            count: cmp::min(self.count, other.count),
+            contents: (self, other),
+            transducer: |(left, right), idx| {
+                ((left.transducer)(left.contents, idx), (right.transducer)(right.contents, idx))
+            },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Map every element of this lazy transducer through `f`, returning a new lazy transducer.
+    ///
+    /// Unlike `into_iter().map(..)`, the result is still indexable and parallel; `f` is only applied
+    /// when an element is requested.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let data = [1u32, 2, 3];
+    /// let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, 3, |input, idx| input[idx]);
+    /// let doubled = lt.map(|n| n as u64 * 2);
+    ///
+    /// assert_eq!(doubled.get(2), Some(6));
+    /// assert_eq!(doubled.into_iter().collect::<Vec<_>>(), vec![2, 4, 6]);
+    /// ```
+    pub fn map<T: 'a>(self, f: fn(Output) -> T) -> Map<'a, Input, Output, T> {
+        LazyTransducer {
+            count: self.count,
+            contents: (self, f),
+            transducer: |(lt, f), idx| f((lt.transducer)(lt.contents, idx)),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a lazy view over all overlapping windows of `size` consecutive elements.
+    ///
+    /// Element `i` of the view is itself a lazy transducer over elements `i..i + size` of this one;
+    /// if `size` is larger than this transducer, the view is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate lazy_transducer;
+    /// extern crate rayon;
+    /// use lazy_transducer::LazyTransducer;
+    /// use rayon::prelude::*;
+    ///
+    /// # fn main() {
+    /// let samples = [1.0f32, 2.0, 3.0, 4.0, 5.0];
+    /// let lt: LazyTransducer<&[f32], f32> = LazyTransducer::new(&samples, 5, |input, idx| input[idx]);
+    /// let windows = lt.windows(3);
+    /// assert_eq!(windows.len(), 3);
+    ///
+    /// let last = windows.get(2).expect("has 3 windows");
+    /// assert_eq!(last.into_iter().collect::<Vec<_>>(), vec![3.0, 4.0, 5.0]);
+    ///
+    /// let averages: Vec<f32> = windows.into_par_iter().map(|window| {
+    ///   window.into_iter().sum::<f32>() / 3.0
+    /// }).collect();
+    /// assert_eq!(averages, vec![2.0, 3.0, 4.0]);
+    /// # }
+    /// ```
+    pub fn windows(self, size: usize) -> Windows<'a, Input, Output> {
+        assert!(size != 0, "window size must be non-zero");
+        let count = if size > self.count { 0 } else { self.count - size + 1 };
+        LazyTransducer {
+            count,
+            contents: (self, size),
+            transducer: |(lt, size), start| {
+                LazyTransducer {
+                    count: size,
+                    contents: (lt, start),
+                    transducer: |(lt, start), idx| (lt.transducer)(lt.contents, start + idx),
+                    _marker: PhantomData,
+                }
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> LazyTransducer<'a, &'a [u8], &'a T> {
+    /// Create a new LazyTransducer yielding `&T` views of the `count` elements of the table at
+    /// `offset` in `contents`, like [new_checked](#method.new_checked), but additionally checking
+    /// that the table is properly aligned for `T`, returning a `Misaligned` error otherwise; the
+    /// transducer receives the bytes of the table.
+    ///
+    /// Since the size of a type is a multiple of its alignment, every element of an aligned table
+    /// is aligned too, so a transducer casting the bytes of an element to a `&T` only needs every
+    /// bit pattern to be a valid `T`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    /// use std::mem::size_of;
+    ///
+    /// #[repr(align(4))]
+    /// struct Aligned([u8; 12]);
+    /// let bytes = Aligned([0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0]);
+    ///
+    /// let lt: LazyTransducer<_, &u32> = LazyTransducer::aligned(&bytes.0, 4, 2, |table, idx| {
+    ///     unsafe { &*(table[idx * size_of::<u32>()..].as_ptr() as *const u32) }
+    /// }).unwrap();
+    /// assert_eq!(lt.get(1).map(|n| u32::from_le(*n)), Some(2));
+    ///
+    /// assert!(LazyTransducer::aligned(&bytes.0, 2, 2, |table, idx| {
+    ///     unsafe { &*(table[idx * size_of::<u32>()..].as_ptr() as *const u32) }
+    /// }).is_err());
+    /// ```
+    pub fn aligned(contents: &'a [u8],
+                   offset: usize,
+                   count: usize,
+                   transducer: fn(&'a [u8], usize) -> &'a T)
+                   -> Result<Self, TransducerError>
+    {
+        let align = mem::align_of::<T>();
+        let table = table_at(contents, offset);
+        if table.as_ptr() as usize & (align - 1) != 0 {
+            return Err(TransducerError::Misaligned{ offset, align })
+        }
+        Self::new_checked(table, count, mem::size_of::<T>(), transducer)
+    }
+}
+
+/// A single window of consecutive elements of a lazy transducer; see
+/// [windows](struct.LazyTransducer.html#method.windows).
+pub type Window<'a, Input, Output> = LazyTransducer<'a, (LazyTransducer<'a, Input, Output>, usize), Output>;
+
+/// A lazy transducer over the overlapping windows of another lazy transducer; see
+/// [windows](struct.LazyTransducer.html#method.windows).
+pub type Windows<'a, Input, Output> = LazyTransducer<'a, (LazyTransducer<'a, Input, Output>, usize), Window<'a, Input, Output>>;
+
+/// A lazy transducer which maps the elements of another lazy transducer; see
+/// [map](struct.LazyTransducer.html#method.map).
+pub type Map<'a, Input, Output, T> = LazyTransducer<'a, (LazyTransducer<'a, Input, Output>, fn(Output) -> T), T>;
+
+/// A lazy transducer which pairs up the elements of two other lazy transducers; see
+/// [zip](struct.LazyTransducer.html#method.zip).
+pub type Zip<'a, Input, Output, OtherInput, OtherOutput> =
+    LazyTransducer<'a, (LazyTransducer<'a, Input, Output>, LazyTransducer<'a, OtherInput, OtherOutput>), (Output, OtherOutput)>;
+
+/// Computes the size in bytes of `count` elements of `sizeof_element` bytes each, returning an error
+/// instead of overflowing.
+pub(crate) fn total_size(count: usize, sizeof_element: usize) -> Result<usize, TransducerError> {
+    count.checked_mul(sizeof_element).ok_or(TransducerError::ArithmeticOverflow{ nelements: count, sizeof_element, stride: sizeof_element })
+}
+
+/// The number of bytes spanned by `count` elements of `sizeof_element` bytes which are `stride`
+/// bytes apart, i.e., up to the end of the last element.
+pub(crate) fn strided_size(count: usize, stride: usize, sizeof_element: usize) -> Result<usize, TransducerError> {
+    if count == 0 {
+        return Ok(0)
+    }
+    (count - 1).checked_mul(stride)
+        .and_then(|start| start.checked_add(sizeof_element))
+        .ok_or(TransducerError::ArithmeticOverflow{ nelements: count, sizeof_element, stride })
+}
+
+/// The byte offset of the `idx`th element of a table at `table_offset` whose elements of
+/// `sizeof_element` bytes are `stride` bytes apart, returning an error instead of overflowing.
+#[cfg(feature = "scroll")]
+pub(crate) fn element_offset(table_offset: usize, idx: usize, stride: usize, sizeof_element: usize) -> Result<usize, TransducerError> {
+    stride.checked_mul(idx)
+        .and_then(|offset| offset.checked_add(table_offset))
+        .ok_or(TransducerError::ArithmeticOverflow{ nelements: idx, sizeof_element, stride })
+}
+
+/// How many elements of `sizeof_element` bytes which are `stride` bytes apart completely fit in
+/// `len` bytes; `stride` must be non-zero.
+pub(crate) fn strided_count(len: usize, stride: usize, sizeof_element: usize) -> usize {
+    if len < sizeof_element {
+        0
+    } else {
+        (len - sizeof_element) / stride + 1
+    }
+}
+
+/// The capacity to reserve for `count` records of at least `min_size` bytes each in `len` bytes;
+/// the count may come from an untrusted header, so it is capped by how many records could fit,
+/// instead of driving the allocation.
+pub(crate) fn capacity_for(count: usize, len: usize, min_size: usize) -> usize {
+    cmp::min(count, len / cmp::max(min_size, 1))
+}
+
+/// The index of the range containing `pos` in ranges which are laid out back to back, and sorted
+/// by their `start`, i.e., of the last range starting at or before `pos`; this skips over any
+/// empty ranges, which start where their successor does.
+pub(crate) fn range_containing<T, F>(ranges: &[T], pos: usize, start: F) -> Option<usize>
+    where F: Fn(&T) -> usize
+{
+    ranges.partition_point(|range| start(range) <= pos).checked_sub(1)
+}
+
+/// The bytes of a table starting at `offset`, which are empty if `offset` is past the end of
+/// `contents`, so that the usual bounds check reports the overflow.
+pub(crate) fn table_at(contents: &[u8], offset: usize) -> &[u8] {
+    &contents[cmp::min(offset, contents.len())..]
 }
 
 /// A [scroll](https://docs.rs/scroll)-based transducer only requires a parsing context for construction.
@@ -176,7 +500,11 @@ impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
 /// according to the size of the input and the number of elements requested from the byte source.
 ///
 /// In order to use this, you must implement TryFromCtx and SizeWith, which you can usually derive
-/// with `#[derive(Pread, SizeWith)]`
+/// with `#[derive(Pread, SizeWith)]`.
+///
+/// The byte source `S` is a slice by default, but it can be any [Source](trait.Source.html), e.g., a
+/// [PagedSource](struct.PagedSource.html), in which case the elements are read out of it on demand;
+/// since `S` is inferred from the input, annotate a non-slice source as `ScrollTransducer<Output, _, _>`.
 ///
 /// # Example
 ///
@@ -195,30 +523,117 @@ impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
 ///
 /// # fn main () {
 /// let bytes = vec![4, 0, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0];
-/// let lt: ScrollTransducer<Rel, scroll::Endian> = ScrollTransducer::parse_with(&bytes, 2, scroll::LE).unwrap();
+/// let lt: ScrollTransducer<Rel, scroll::Endian> = ScrollTransducer::parse_with(&bytes[..], 2, scroll::LE).unwrap();
 /// for reloc in lt.into_iter() {
 ///   assert_eq!(reloc.r_info, 5);
 ///   println!("{:?}", reloc);
 /// }
 /// # }
 /// ```
-pub type ScrollTransducer<'a, Output, Ctx = scroll::Endian> = LazyTransducer<'a, (&'a[u8], Ctx), Output>;
+#[cfg(feature = "scroll")]
+pub type ScrollTransducer<'a, Output, Ctx = scroll::Endian, S = [u8]> = LazyTransducer<'a, (&'a S, Ctx, usize, usize), Output>;
 
-impl<'a, Output, Ctx, E> ScrollTransducer<'a, Output, Ctx>
+#[cfg(feature = "scroll")]
+impl<'a, Output, Ctx, E, S> ScrollTransducer<'a, Output, Ctx, S>
     where
-        Ctx: Copy + Default,
-        Output: 'a + ctx::TryFromCtx<'a, Ctx, Error = E, Size = usize> + SizeWith<Ctx, Units = usize>,
+        S: 'a + Source + ?Sized,
+        Ctx: Copy,
+        Output: 'a + for<'b> ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>,
         E: From<scroll::Error> + Debug,
 {
-    /// The transducer is just `pread`, whose impl is defined by the user, or via derive macro.
-    /// We unwrap because we bounds checked on creation
-    fn pread((input, ctx): (&'a [u8], Ctx), idx: usize) -> Output {
-        let offset = Output::size_with(&ctx) * idx;
-        input.pread_with(offset, ctx).unwrap()
+    /// The fallible transducer is just `pread`, whose impl is defined by the user, or via derive macro.
+    /// The parse error is wrapped with the index of the element and the offset it was parsed at.
+    fn try_pread((source, ctx, table_offset, stride): (&'a S, Ctx, usize, usize), idx: usize) -> Result<Output, TransducerError>
+        where E: error::Error + Send + Sync + 'static
+    {
+        let offset = element_offset(table_offset, idx, stride, Output::size_with(&ctx))?;
+        Source::pread_with(source, idx, offset, ctx)
+    }
+    /// We unwrap because we bounds checked on creation; a source other than a byte slice can
+    /// still fail to read, e.g., with an I/O error, in which case we panic
+    fn pread((source, ctx, table_offset, stride): (&'a S, Ctx, usize, usize), idx: usize) -> Output {
+        let sizeof_element = Output::size_with(&ctx);
+        let offset = element_offset(table_offset, idx, stride, sizeof_element).unwrap();
+        let bytes = source.read_bytes(offset, sizeof_element).unwrap();
+        Pread::pread_with(&*bytes, 0, ctx).unwrap()
+    }
+    /// Parse an element out of the scroll transducer, returning a `Parse` error with the element
+    /// index and byte offset instead of panicking if the element is malformed, or an `OutOfBounds`
+    /// error if the index is greater than the number of elements.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate lazy_transducer;
+    /// extern crate scroll;
+    /// use lazy_transducer::ScrollTransducer;
+    ///
+    /// # fn main() {
+    /// let bytes = vec![1, 0, 0, 0, 2, 0, 0, 0];
+    /// let lt: ScrollTransducer<u32> = ScrollTransducer::parse_with(&bytes[..], 2, scroll::LE).unwrap();
+    /// assert_eq!(lt.try_get(1).unwrap(), 2);
+    /// assert!(lt.try_get(2).is_err());
+    /// # }
+    /// ```
+    pub fn try_get(&self, idx: usize) -> Result<Output, TransducerError>
+        where E: error::Error + Send + Sync + 'static
+    {
+        if idx >= self.count {
+            Err(TransducerError::OutOfBounds{ idx, len: self.count })
+        } else {
+            Self::try_pread(self.contents, idx)
+        }
+    }
+    /// Eagerly parse every element in parallel, returning a report of every malformed element, if
+    /// any; see [validate](struct.TryLazyTransducer.html#method.validate).
+    pub fn validate(&self) -> Result<(), ValidationReport<TransducerError>>
+        where S: Sync,
+              Ctx: Send + Sync,
+              Output: Send + Sync,
+              E: error::Error + Send + Sync + 'static,
+    {
+        self.into_fallible().validate()
+    }
+    /// Eagerly parse the elements in parallel, stopping as soon as one element is malformed and
+    /// returning its error; see [validate_fail_fast](struct.TryLazyTransducer.html#method.validate_fail_fast).
+    pub fn validate_fail_fast(&self) -> Result<(), TransducerError>
+        where S: Sync,
+              Ctx: Send + Sync,
+              Output: Send + Sync,
+              E: error::Error + Send + Sync + 'static,
+    {
+        self.into_fallible().validate_fail_fast().map_err(|(_, err)| err)
+    }
+    /// Convert this scroll transducer into a [fallible](struct.TryLazyTransducer.html) one, whose
+    /// elements and iterators surface parse errors, along with the element index and byte offset,
+    /// instead of panicking.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate lazy_transducer;
+    /// extern crate scroll;
+    /// use lazy_transducer::ScrollTransducer;
+    ///
+    /// # fn main() {
+    /// let bytes = vec![1, 0, 0, 0, 2, 0, 0, 0];
+    /// let lt: ScrollTransducer<u32> = ScrollTransducer::parse_with(&bytes[..], 2, scroll::LE).unwrap();
+    /// for n in lt.into_fallible() {
+    ///   println!("{}", n.unwrap());
+    /// }
+    /// # }
+    /// ```
+    pub fn into_fallible(self) -> TryLazyTransducer<'a, (&'a S, Ctx, usize, usize), Output, TransducerError>
+        where E: error::Error + Send + Sync + 'static
+    {
+        TryLazyTransducer::new(self.contents, self.count, Self::try_pread)
     }
     /// Create a new scroll-based lazy transducer,
     /// using the given context to parse `count` elements out of `contents`
     ///
+    /// The transducer is empty if the elements are zero-sized, as they have no sensible layout;
+    /// this is also the case for the other constructors whose stride is the element size.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -229,7 +644,7 @@ impl<'a, Output, Ctx, E> ScrollTransducer<'a, Output, Ctx>
     ///
     /// # fn main() {
     /// let bytes = vec![1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0xef, 0xbe, 0xad, 0xde];
-    /// let lt: ScrollTransducer<u32> = ScrollTransducer::parse_with(&bytes, 4, Endian::Little).unwrap();
+    /// let lt: ScrollTransducer<u32> = ScrollTransducer::parse_with(&bytes[..], 4, Endian::Little).unwrap();
     ///
     /// let deadbeef = lt.get(3).expect("has 4 elements");
     /// assert_eq!(deadbeef, 0xdeadbeef);
@@ -239,47 +654,187 @@ impl<'a, Output, Ctx, E> ScrollTransducer<'a, Output, Ctx>
     /// });
     /// # }
     /// ```
-    pub fn parse_with(contents: &'a [u8],
+    pub fn parse_with(contents: &'a S,
                       count: usize,
                       ctx: Ctx,
-    ) -> Result<Self, Error>
+    ) -> Result<Self, TransducerError>
     {
+        Self::parse_with_policy(contents, count, ctx, TruncationPolicy::Strict)
+    }
+    /// Create a new scroll-based lazy transducer over every element in `contents`, i.e., the count
+    /// is the length of `contents` divided by the size of an element.
+    ///
+    /// Returns an `ElementOverflow` error if the length is not a multiple of the element size; see
+    /// [parse_policy](#method.parse_policy) to ignore the trailing bytes instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{ScrollTransducer, Endian};
+    ///
+    /// let bytes = vec![1, 0, 2, 0, 3, 0];
+    /// let lt: ScrollTransducer<u16> = ScrollTransducer::parse(&bytes[..], Endian::Little).unwrap();
+    /// assert_eq!(lt.len(), 3);
+    ///
+    /// assert!(ScrollTransducer::<u16>::parse(&bytes[..5], Endian::Little).is_err());
+    /// ```
+    pub fn parse(contents: &'a S, ctx: Ctx) -> Result<Self, TransducerError> {
+        Self::parse_policy(contents, ctx, TruncationPolicy::Strict)
+    }
+    /// Create a new scroll-based lazy transducer over every element in `contents` like
+    /// [parse](#method.parse), using `policy` to decide what to do when the length is not a
+    /// multiple of the element size; `ClampToSource` ignores the trailing bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{ScrollTransducer, Endian, TruncationPolicy};
+    ///
+    /// let bytes = vec![1, 0, 2, 0, 3];
+    /// let lt: ScrollTransducer<u16> = ScrollTransducer::parse_policy(&bytes[..], Endian::Little, TruncationPolicy::ClampToSource).unwrap();
+    /// assert_eq!(lt.len(), 2);
+    /// ```
+    pub fn parse_policy(contents: &'a S, ctx: Ctx, policy: TruncationPolicy) -> Result<Self, TransducerError> {
         let sizeof_element = Output::size_with(&ctx);
-        let total_size = sizeof_element * count;
-        if total_size > contents.len() {
-            Err(TransducerError::ElementOverflow{ nelements: count, sizeof_element, src_size: contents.len() }.into())
-        } else {
-            Ok(LazyTransducer {
-                contents: (contents, ctx),
-                count,
-                transducer: Self::pread,
-                _marker: PhantomData::default(),
-            })
+        // zero-sized elements have no sensible count, so there are none, and the transducer is empty
+        let src_size = contents.len();
+        let count = src_size.checked_div(sizeof_element).unwrap_or(0);
+        if sizeof_element != 0 && policy == TruncationPolicy::Strict && count * sizeof_element != src_size {
+            return Err(TransducerError::ElementOverflow{ nelements: count + 1, sizeof_element, stride: sizeof_element, src_size })
         }
+        Self::parse_with_policy(contents, count, ctx, policy)
+    }
+    /// Create a new scroll-based lazy transducer like [parse_with](#method.parse_with), for a table
+    /// of `count` elements starting at byte `table_offset` of `contents`, i.e., index 0 is parsed
+    /// at `table_offset`, and `table_offset + count * size` must not exceed the length of
+    /// `contents`.
+    ///
+    /// The offset is stored in the transducer, so `contents` need not be re-sliced, and the byte
+    /// offsets of parse errors are relative to the start of `contents`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{ScrollTransducer, Endian};
+    ///
+    /// // a 2 byte header, followed by the table
+    /// let bytes = vec![0xff, 0xff, 1, 0, 0, 0, 2, 0, 0, 0];
+    /// let lt: ScrollTransducer<u32> = ScrollTransducer::parse_with_at(&bytes[..], 2, 2, Endian::Little).unwrap();
+    /// assert_eq!(lt.get(0), Some(1));
+    /// assert_eq!(lt.get(1), Some(2));
+    ///
+    /// assert!(ScrollTransducer::<u32>::parse_with_at(&bytes[..], 4, 2, Endian::Little).is_err());
+    /// ```
+    pub fn parse_with_at(contents: &'a S,
+                         table_offset: usize,
+                         count: usize,
+                         ctx: Ctx,
+    ) -> Result<Self, TransducerError>
+    {
+        Self::parse_table(contents, table_offset, count, Output::size_with(&ctx), ctx, TruncationPolicy::Strict)
+    }
+    /// Create a new scroll-based lazy transducer like [parse_with](#method.parse_with), using
+    /// `policy` to decide what to do when `count` elements do not fit in `contents`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{ScrollTransducer, Endian, TruncationPolicy};
+    ///
+    /// let bytes = vec![1, 0, 0, 0, 2, 0, 0, 0, 3, 0];
+    /// // the header claims there are 10 entries, but only 2 fit
+    /// let lt: ScrollTransducer<u32> = ScrollTransducer::parse_with_policy(&bytes[..], 10, Endian::Little, TruncationPolicy::ClampToSource).unwrap();
+    /// assert_eq!(lt.len(), 2);
+    ///
+    /// assert!(ScrollTransducer::<u32>::parse_with_policy(&bytes[..], 10, Endian::Little, TruncationPolicy::Strict).is_err());
+    /// ```
+    pub fn parse_with_policy(contents: &'a S,
+                             count: usize,
+                             ctx: Ctx,
+                             policy: TruncationPolicy,
+    ) -> Result<Self, TransducerError>
+    {
+        Self::parse_table(contents, 0, count, Output::size_with(&ctx), ctx, policy)
+    }
+    /// Create a new scroll-based lazy transducer like [parse_with](#method.parse_with), for
+    /// records which are `stride` bytes apart, i.e., the `i`th element is parsed at `i * stride`.
+    ///
+    /// This is useful for records with trailing padding, or for tables interleaved with other
+    /// data; only the last element needs to fit in `contents` completely.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{ScrollTransducer, Endian};
+    ///
+    /// // `u16`s padded to 4 bytes, without padding after the last one
+    /// let bytes = vec![1, 0, 0xff, 0xff, 2, 0, 0xff, 0xff, 3, 0];
+    /// let lt: ScrollTransducer<u16> = ScrollTransducer::parse_strided(&bytes[..], 3, 4, Endian::Little).unwrap();
+    /// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn parse_strided(contents: &'a S,
+                         count: usize,
+                         stride: usize,
+                         ctx: Ctx,
+    ) -> Result<Self, TransducerError>
+    {
+        assert!(stride != 0, "stride must be non-zero");
+        Self::parse_table(contents, 0, count, stride, ctx, TruncationPolicy::Strict)
+    }
+    /// Create a new scroll-based lazy transducer for a table of `count` elements, which are
+    /// `stride` bytes apart, starting at byte `table_offset` of `contents`; the transducer is empty
+    /// if `stride` is zero.
+    pub(crate) fn parse_table(contents: &'a S,
+                              table_offset: usize,
+                              count: usize,
+                              stride: usize,
+                              ctx: Ctx,
+                              policy: TruncationPolicy,
+    ) -> Result<Self, TransducerError>
+    {
+        let sizeof_element = Output::size_with(&ctx);
+        let src_size = contents.len().saturating_sub(table_offset);
+        let count = match strided_size(count, stride, sizeof_element) {
+            // the stride is only zero when it is the size of a zero-sized element, and those have
+            // no sensible layout, so there are none
+            _ if stride == 0 => 0,
+            Ok(total_size) if total_size <= src_size => count,
+            _ if policy == TruncationPolicy::ClampToSource => strided_count(src_size, stride, sizeof_element),
+            Ok(_) => return Err(TransducerError::ElementOverflow{ nelements: count, sizeof_element, stride, src_size }),
+            Err(err) => return Err(err),
+        };
+        Ok(LazyTransducer {
+            contents: (contents, ctx, table_offset, stride),
+            count,
+            transducer: Self::pread,
+            _marker: PhantomData,
+        })
     }
 }
 
 impl<'a, Input: Copy, Output> Clone for LazyTransducer<'a, Input, Output> {
     fn clone(&self) -> Self {
-        LazyTransducer {
-            count: self.count,
-            contents: self.contents,
-            transducer: self.transducer.clone(),
-            _marker: PhantomData::default(),
-        }
+        *self
     }
 }
 
+impl<'a, Input: Copy, Output> Copy for LazyTransducer<'a, Input, Output> {}
+
 /// A generic iterator over the elements produced by the lazy transducer
 pub struct IntoIter<'a, Input: 'a + Copy, Output: 'a> {
     current: usize,
+    end: usize,
     lt: LazyTransducer<'a, Input, Output>,
 }
 
 impl<'a, Input: Copy, Output> Iterator for IntoIter<'a, Input, Output> {
     type Item = Output;
     fn next (&mut self) -> Option<Self::Item> {
-        if self.current >= self.lt.count {
+        if self.current >= self.end {
             None
         } else {
             let output = self.lt.get(self.current);
@@ -287,8 +842,42 @@ impl<'a, Input: Copy, Output> Iterator for IntoIter<'a, Input, Output> {
             output
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.end.saturating_sub(self.current);
+        (remaining, Some(remaining))
+    }
+    /// Jumps directly to the `n`th remaining element, since lazy transducers are random access
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.end.saturating_sub(self.current) {
+            self.current = self.end;
+            None
+        } else {
+            self.current += n;
+            self.next()
+        }
+    }
+    fn last(self) -> Option<Self::Item> {
+        if self.current >= self.end {
+            None
+        } else {
+            self.lt.get(self.end - 1)
+        }
+    }
+}
+
+impl<'a, Input: Copy, Output> DoubleEndedIterator for IntoIter<'a, Input, Output> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.current >= self.end {
+            None
+        } else {
+            self.end -= 1;
+            self.lt.get(self.end)
+        }
+    }
 }
 
+impl<'a, Input: Copy, Output> FusedIterator for IntoIter<'a, Input, Output> {}
+
 impl<'a, Input: Copy, Output> IntoIterator for LazyTransducer<'a, Input, Output> {
     type Item = Output;
     type IntoIter = IntoIter<'a, Input, Output>;
@@ -296,6 +885,7 @@ impl<'a, Input: Copy, Output> IntoIterator for LazyTransducer<'a, Input, Output>
     fn into_iter(self) -> Self::IntoIter {
         IntoIter {
             current: 0,
+            end: self.count,
             lt: self,
         }
     }
@@ -303,22 +893,64 @@ impl<'a, Input: Copy, Output> IntoIterator for LazyTransducer<'a, Input, Output>
 
 impl<'a, 'b, Input: Copy, Output> IntoIterator for &'b LazyTransducer<'a, Input, Output> {
     type Item = Output;
-    type IntoIter = IntoIter<'a, Input, Output>;
+    type IntoIter = Iter<'b, 'a, Input, Output>;
 
     fn into_iter(self) -> Self::IntoIter {
-        IntoIter {
-            current: 0,
-            lt: self.clone(),
-        }
+        self.iter()
     }
 }
 
 impl<'a, Input: Copy, Output> ExactSizeIterator for IntoIter<'a, Input, Output> {
     fn len(&self) -> usize {
-        self.lt.count
+        self.end.saturating_sub(self.current)
     }
 }
 
+/// A generic iterator over the elements produced by a borrowed lazy transducer.
+///
+/// Unlike [IntoIter](struct.IntoIter.html), this does not consume the lazy transducer; since a lazy
+/// transducer is `Copy`, it iterates over a copy of it, and never copies the input it refers to.
+pub struct Iter<'b, 'a: 'b, Input: 'a + Copy, Output: 'a> {
+    inner: IntoIter<'a, Input, Output>,
+    _marker: PhantomData<&'b LazyTransducer<'a, Input, Output>>,
+}
+
+impl<'b, 'a, Input: Copy, Output> Iter<'b, 'a, Input, Output> {
+    fn new(lt: &'b LazyTransducer<'a, Input, Output>, current: usize, end: usize) -> Self {
+        Iter { inner: IntoIter { current, end, lt: *lt }, _marker: PhantomData }
+    }
+}
+
+impl<'b, 'a, Input: Copy, Output> Iterator for Iter<'b, 'a, Input, Output> {
+    type Item = Output;
+    fn next (&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.inner.nth(n)
+    }
+    fn last(self) -> Option<Self::Item> {
+        self.inner.last()
+    }
+}
+
+impl<'b, 'a, Input: Copy, Output> DoubleEndedIterator for Iter<'b, 'a, Input, Output> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'b, 'a, Input: Copy, Output> ExactSizeIterator for Iter<'b, 'a, Input, Output> {
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+}
+
+impl<'b, 'a, Input: Copy, Output> FusedIterator for Iter<'b, 'a, Input, Output> {}
+
 /// A generic, parallel iterator over the elements produced by the lazy transducer.
 ///
 /// This implements rayon's ParallelIterator trait, so you need only use `rayon::prelude::*` and
@@ -344,21 +976,112 @@ impl<'a, Input: Sync + Send + Copy, Output: Send + Sync> ParallelIterator for In
     type Item = Output;
     fn drive_unindexed<C>(self, consumer: C) -> C::Result
         where C: UnindexedConsumer<Self::Item> {
-        bridge_unindexed(Producer { top: self.lt.count, lt: &self.lt, current: self.current }, consumer)
+        bridge(self, consumer)
+    }
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.lt.count - self.current)
+    }
+}
+
+impl<'a, Input: Sync + Send + Copy, Output: Send + Sync> IndexedParallelIterator for IntoParIter<'a, Input, Output> {
+    fn drive<C>(self, consumer: C) -> C::Result
+        where C: Consumer<Self::Item> {
+        bridge(self, consumer)
+    }
+    fn len(&self) -> usize {
+        self.lt.count - self.current
+    }
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+        where CB: ProducerCallback<Self::Item> {
+        callback.callback(Producer { top: self.lt.count, lt: &self.lt, current: self.current, cancel: None })
+    }
+}
+
+/// A generic, parallel iterator over the elements produced by a borrowed lazy transducer.
+///
+/// This is what rayon's `par_iter()` returns for a `&LazyTransducer`, which lets a transducer
+/// stored elsewhere be iterated in parallel repeatedly without moving it.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::LazyTransducer;
+/// use rayon::prelude::*;
+///
+/// struct Table<'a> {
+///   entries: LazyTransducer<'a, &'a [u32], u32>,
+/// }
+///
+/// # fn main() {
+/// let data = [1u32, 2, 3, 4];
+/// let table = Table { entries: LazyTransducer::new(&data, 4, |input, idx| input[idx]) };
+/// let sum: u32 = table.entries.par_iter().sum();
+/// let max = table.entries.par_iter().max();
+/// assert_eq!(sum, 10);
+/// assert_eq!(max, Some(4));
+/// # }
+/// ```
+pub struct ParIter<'b, 'a: 'b, Input: 'a + Copy, Output: 'a> {
+    pub(crate) lt: &'b LazyTransducer<'a, Input, Output>,
+}
+
+impl<'b, 'a, Input: Sync + Copy + Send, Output: Send + Sync> IntoParallelIterator for &'b LazyTransducer<'a, Input, Output> {
+    type Iter = ParIter<'b, 'a, Input, Output>;
+    type Item = Output;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter {
+            lt: self,
+        }
+    }
+}
+
+impl<'b, 'a, Input: Sync + Send + Copy, Output: Send + Sync> ParallelIterator for ParIter<'b, 'a, Input, Output> {
+    type Item = Output;
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<Self::Item> {
+        bridge(self, consumer)
+    }
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.lt.count)
+    }
+}
+
+impl<'b, 'a, Input: Sync + Send + Copy, Output: Send + Sync> IndexedParallelIterator for ParIter<'b, 'a, Input, Output> {
+    fn drive<C>(self, consumer: C) -> C::Result
+        where C: Consumer<Self::Item> {
+        bridge(self, consumer)
+    }
+    fn len(&self) -> usize {
+        self.lt.count
+    }
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+        where CB: ProducerCallback<Self::Item> {
+        callback.callback(Producer { top: self.lt.count, lt: self.lt, current: 0, cancel: None })
     }
 }
 
 /// The parallel iterator producer for a lazy transducer, required by rayon.
 pub struct Producer<'b, 'a: 'b, Input: 'a + Sync + Copy + Send, Output: 'a + Send + Sync> {
-    lt: &'b LazyTransducer<'a, Input, Output>,
-    current: usize,
-    top: usize,
+    pub(crate) lt: &'b LazyTransducer<'a, Input, Output>,
+    pub(crate) current: usize,
+    pub(crate) top: usize,
+    /// Checked between elements; once cancelled, this producer yields nothing more
+    pub(crate) cancel: Option<CancelToken>,
+}
+
+impl<'b, 'a, Input: Sync + Copy + Send, Output: Send + Sync> Producer<'b, 'a, Input, Output> {
+    fn is_cancelled(&self) -> bool {
+        self.cancel.as_ref().map(CancelToken::is_cancelled).unwrap_or(false)
+    }
 }
 
 impl<'b, 'a, Input: Sync + Copy + Send, Output: Send + Sync> Iterator for Producer<'b, 'a, Input, Output> {
     type Item = Output;
     fn next (&mut self) -> Option<Self::Item> {
-        if self.current >= self.top || self.current >= self.lt.count {
+        if self.current >= self.top || self.current >= self.lt.count || self.is_cancelled() {
             None
         } else {
             let output = self.lt.get(self.current);
@@ -373,11 +1096,11 @@ impl<'b, 'a, Input: Send + Sync + Copy, Output: Sync + Send> UnindexedProducer f
 
     fn split(mut self) -> (Self, Option<Self>) {
         let len = self.top - self.current;
-        if len > 1 {
+        if len > 1 && !self.is_cancelled() {
             let old_top = self.top;
             let split_len = len / 2;
             self.top = self.current + split_len;
-            let right = Producer { lt: self.lt, current: self.top, top: old_top };
+            let right = Producer { lt: self.lt, current: self.top, top: old_top, cancel: self.cancel.clone() };
             (self, Some(right))
         } else {
             (self, None)
@@ -387,6 +1110,21 @@ impl<'b, 'a, Input: Send + Sync + Copy, Output: Sync + Send> UnindexedProducer f
     fn fold_with<F>(self, folder: F) -> F
         where F: Folder<Self::Item>
     {
-        folder.consume_iter(self.into_iter())
+        folder.consume_iter(self)
+    }
+}
+
+impl<'b, 'a, Input: Send + Sync + Copy, Output: Sync + Send> plumbing::Producer for Producer<'b, 'a, Input, Output> {
+    type Item = Output;
+    type IntoIter = Iter<'b, 'a, Input, Output>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter::new(self.lt, self.current, self.top)
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let mid = self.current + index;
+        let right = Producer { lt: self.lt, current: mid, top: self.top, cancel: self.cancel.clone() };
+        (Producer { lt: self.lt, current: self.current, top: mid, cancel: self.cancel }, right)
     }
 }
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use rayon::prelude::*;
+
+use TryLazyTransducer;
+
+/// A lenient view over a [fallible lazy transducer](struct.TryLazyTransducer.html), for corrupted
+/// or truncated inputs.
+///
+/// Elements which fail to be extracted are skipped (or replaced by a sentinel) while iteration
+/// continues, and their indices and errors are recorded in an error log on this view, which can be
+/// inspected afterwards. Every index is logged at most once, no matter how often it is accessed.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::TryLazyTransducer;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let data = [1u32, 0, 3, 0, 5];
+/// let lt: TryLazyTransducer<&[u32], u32, &'static str> = TryLazyTransducer::new(&data, 5, |input, idx| {
+///     if input[idx] == 0 { Err("corrupt") } else { Ok(input[idx]) }
+/// });
+/// let lenient = lt.lenient();
+///
+/// let good: Vec<u32> = lenient.par_iter().collect();
+/// assert_eq!(good, vec![1, 3, 5]);
+/// assert_eq!(lenient.skipped(), vec![1, 3]);
+///
+/// let patched: Vec<u32> = lenient.iter_or(0xffff).collect();
+/// assert_eq!(patched, vec![1, 0xffff, 3, 0xffff, 5]);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Lenient<'a, Input, Output, Error>
+    where Input: 'a + Copy,
+          Output: 'a,
+          Error: 'a,
+{
+    lt: TryLazyTransducer<'a, Input, Output, Error>,
+    log: Mutex<BTreeMap<usize, Error>>,
+}
+
+impl<'a, Input, Output, Error> Lenient<'a, Input, Output, Error>
+    where Input: 'a + Copy,
+          Output: 'a,
+          Error: 'a,
+{
+    fn record(&self, idx: usize, result: Result<Output, Error>) -> Option<Output> {
+        match result {
+            Ok(output) => Some(output),
+            Err(err) => {
+                self.log.lock().unwrap().entry(idx).or_insert(err);
+                None
+            }
+        }
+    }
+    /// How many elements are contained in the underlying lazy transducer, including the ones which
+    /// fail to be extracted
+    pub fn len(&self) -> usize {
+        self.lt.len()
+    }
+    /// Whether the underlying lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.lt.is_empty()
+    }
+    /// Get an element out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of elements, or if the element could not be extracted, in which case the error is
+    /// logged.
+    pub fn get(&self, idx: usize) -> Option<Output> {
+        self.lt.get(idx).and_then(|result| self.record(idx, result))
+    }
+    /// Iterate over the elements which could be extracted, logging and skipping the rest.
+    pub fn iter<'b>(&'b self) -> impl Iterator<Item = Output> + 'b {
+        self.lt.iter().enumerate().filter_map(move |(idx, result)| self.record(idx, result))
+    }
+    /// Iterate over every element, logging the ones which could not be extracted and replacing them
+    /// with `sentinel`.
+    pub fn iter_or<'b>(&'b self, sentinel: Output) -> impl Iterator<Item = Output> + 'b
+        where Output: Clone
+    {
+        self.lt.iter().enumerate().map(move |(idx, result)| self.record(idx, result).unwrap_or_else(|| sentinel.clone()))
+    }
+    /// The indices of every element which failed to be extracted so far, in ascending order
+    pub fn skipped(&self) -> Vec<usize> {
+        self.log.lock().unwrap().keys().cloned().collect()
+    }
+    /// Removes and returns the error log, i.e., the index and error of every element which failed to
+    /// be extracted so far, in ascending index order
+    pub fn take_errors(&self) -> Vec<(usize, Error)> {
+        let log = ::std::mem::take(&mut *self.log.lock().unwrap());
+        log.into_iter().collect()
+    }
+}
+
+impl<'a, Input, Output, Error> Lenient<'a, Input, Output, Error>
+    where Input: 'a + Sync + Send + Copy,
+          Output: 'a + Send + Sync,
+          Error: 'a + Send + Sync,
+{
+    /// Iterate in parallel over the elements which could be extracted, logging and skipping the
+    /// rest.
+    pub fn par_iter<'b>(&'b self) -> impl ParallelIterator<Item = Output> + 'b {
+        self.lt.into_par_iter().enumerate().filter_map(move |(idx, result)| self.record(idx, result))
+    }
+}
+
+impl<'a, Input, Output, Error> TryLazyTransducer<'a, Input, Output, Error>
+    where Input: 'a + Copy,
+          Output: 'a,
+          Error: 'a,
+{
+    /// Create a lenient view over this fallible lazy transducer, which skips elements that fail to
+    /// be extracted; see [Lenient](struct.Lenient.html).
+    pub fn lenient(self) -> Lenient<'a, Input, Output, Error> {
+        Lenient { lt: self, log: Mutex::new(BTreeMap::new()) }
+    }
+}
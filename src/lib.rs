@@ -33,6 +33,68 @@
 //!
 //! The parallel implementation uses [rayon](https://docs.rs/rayon).
 //!
+//! # Features
+//!
+//! The `scroll` feature, which is enabled by default, provides the scroll-based transducers and
+//! the `Endian` re-export; disable it with `default-features = false` if you only use the plain
+//! transducer function API.
+//!
+//! The `mmap` feature provides [MmapTransducer](type.MmapTransducer.html), which owns a memory
+//! map of its input source, via [memmap2](https://docs.rs/memmap2).
+//!
+//! The `http` feature provides [HttpSource](type.HttpSource.html), a
+//! [PagedSource](struct.PagedSource.html) over a remote file, whose pages are fetched with HTTP
+//! range requests, via [ureq](https://docs.rs/ureq).
+//!
+//! The `bgzf` feature provides [BgzfSource](struct.BgzfSource.html), a compressed input source
+//! over concatenated gzip members, such as BGZF files, via [flate2](https://docs.rs/flate2).
+//!
+//! The `zstd` feature provides [ZstdSource](struct.ZstdSource.html), a compressed input source over
+//! zstd frames, such as the seekable format, via [zstd](https://docs.rs/zstd).
+//!
+//! The `checksum` feature provides [Digest](enum.Digest.html) and
+//! [parse_with_checksum](type.ScrollTransducer.html#method.parse_with_checksum), which verifies a
+//! CRC32 or SHA-256 checksum of a table before transducing it, via
+//! [crc32fast](https://docs.rs/crc32fast) and [sha2](https://docs.rs/sha2).
+//!
+//! The `pod` feature provides [PodTransducer](struct.PodTransducer.html), which safely yields `&T`
+//! views of a validated table of plain old data, rather than `transmute`ing the bytes of each
+//! element as in the example below, via [bytemuck](https://docs.rs/bytemuck).
+//!
+//! The `csv` feature provides [CsvTransducer](struct.CsvTransducer.html), which lazily parses the
+//! rows of a CSV file into records, or deserializes them with serde, via [csv](https://docs.rs/csv).
+//!
+//! The `json` feature provides [JsonLines](struct.JsonLines.html), which lazily deserializes the
+//! lines of a JSON Lines file, via [serde_json](https://docs.rs/serde_json).
+//!
+//! The `bincode` feature provides [BincodeTransducer](struct.BincodeTransducer.html), which lazily
+//! deserializes a table of serialized values, doing the offset math of the
+//! [bincode example](struct.LazyTransducer.html#advanced-example) for you, via
+//! [bincode](https://docs.rs/bincode).
+//!
+//! The `msgpack` feature provides [MsgPackTransducer](struct.MsgPackTransducer.html), which lazily
+//! deserializes a stream of concatenated MessagePack values, via
+//! [rmp-serde](https://docs.rs/rmp-serde).
+//!
+//! The `prost` feature provides [decode](struct.LengthDelimited.html#method.decode), which decodes
+//! the length-delimited protobuf messages of a [LengthDelimited](struct.LengthDelimited.html), via
+//! [prost](https://docs.rs/prost).
+//!
+//! The `arrow` feature provides [ArrowColumn](struct.ArrowColumn.html), which lazily yields the
+//! possibly null values of a column of an Arrow IPC file or stream, via
+//! [arrow-ipc](https://docs.rs/arrow-ipc).
+//!
+//! The `graphemes` feature provides [GraphemeTransducer](struct.GraphemeTransducer.html), which
+//! indexes the extended grapheme clusters of a string, via
+//! [unicode-segmentation](https://docs.rs/unicode-segmentation).
+//!
+//! The `nom` feature provides [NomTransducer](struct.NomTransducer.html), which indexes the
+//! records of any grammar given a parser for one record, via [nom](https://docs.rs/nom).
+//!
+//! The `uuid` feature provides [uuids](struct.UuidTransducer.html#method.uuids), which yields the
+//! elements of a [UuidTransducer](struct.UuidTransducer.html) as `Uuid`s, via
+//! [uuid](https://docs.rs/uuid).
+//!
 //! # Example
 //!
 //! ```rust
@@ -61,9 +123,133 @@
 //! ```
 
 extern crate rayon;
+extern crate memchr;
+#[cfg(feature = "scroll")]
 extern crate scroll;
-#[macro_use]
-extern crate failure;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+#[cfg(feature = "http")]
+extern crate ureq;
+#[cfg(feature = "bgzf")]
+extern crate flate2;
+#[cfg(feature = "zstd")]
+extern crate zstd;
+#[cfg(feature = "checksum")]
+extern crate crc32fast;
+#[cfg(feature = "checksum")]
+extern crate sha2;
+#[cfg(feature = "pod")]
+extern crate bytemuck;
+#[cfg(feature = "csv")]
+extern crate csv;
+#[cfg(any(feature = "csv", feature = "json", feature = "bincode", feature = "msgpack"))]
+extern crate serde;
+#[cfg(feature = "json")]
+extern crate serde_json;
+#[cfg(feature = "bincode")]
+extern crate bincode;
+#[cfg(feature = "msgpack")]
+extern crate rmp_serde;
+#[cfg(feature = "prost")]
+extern crate prost;
+#[cfg(feature = "arrow")]
+extern crate arrow_array;
+#[cfg(feature = "arrow")]
+extern crate arrow_buffer;
+#[cfg(feature = "arrow")]
+extern crate arrow_ipc;
+#[cfg(feature = "arrow")]
+extern crate arrow_schema;
+#[cfg(feature = "graphemes")]
+extern crate unicode_segmentation;
+#[cfg(feature = "nom")]
+extern crate nom;
+#[cfg(feature = "uuid")]
+extern crate uuid;
+
+/// Construct a lazy transducer from an input source, a count, and a transducer, via the
+/// [Builder](struct.Builder.html).
+///
+/// The input, count, and transducer are all required, so forgetting one is a compile time error.
+///
+/// # Example
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::LazyTransducer;
+///
+/// # fn main() {
+/// let data = [1u32, 2, 3];
+/// let lt: LazyTransducer<_, u64> = lazy_transducer!(&data; count = 3; |input, idx| input[idx] as u64 * 2);
+/// assert_eq!(lt.get(2), Some(6));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// #[macro_use]
+/// extern crate lazy_transducer;
+///
+/// # fn main() {
+/// let data = [1u32, 2, 3];
+/// let lt = lazy_transducer!(&data; |input, idx| input[idx]);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! lazy_transducer {
+    ($input:expr; count = $count:expr; $transducer:expr) => {
+        $crate::Builder::new($input).count($count).transducer($transducer).finish()
+    };
+    ($input:expr; count = $count:expr; $transducer:expr;) => {
+        $crate::lazy_transducer!($input; count = $count; $transducer)
+    };
+}
+
+/// Implements `transducer`, which returns a lazy transducer over the elements of a random access
+/// type with `len` and `get` methods, and the `IntoIterator` and `IntoParallelIterator` impls for a
+/// reference to the type, which iterate over that lazy transducer.
+///
+/// The `iterators` form only implements the iterator traits, for a type whose `transducer` returns
+/// a lazy transducer over the given input instead.
+macro_rules! borrowed_transducer {
+    ($(#[$attr:meta])*
+     impl[$($gen:tt)*] $ty:ty => <$b:lifetime> $item:ty
+     where [$($bounds:tt)*]
+     parallel where [$($par_bounds:tt)*]) => {
+        impl<$($gen)*> $ty where $($bounds)* {
+            $(#[$attr])*
+            pub fn transducer<$b>(&$b self) -> $crate::LazyTransducer<$b, &$b Self, $item> {
+                $crate::LazyTransducer::new(self, self.len(), |lt, idx| lt.get(idx).unwrap())
+            }
+        }
+        borrowed_transducer! {
+            iterators impl[$($gen)*] $ty => <$b> $item, &$b $ty
+            where [$($bounds)*]
+            parallel where [$($par_bounds)*]
+        }
+    };
+    (iterators impl[$($gen:tt)*] $ty:ty => <$b:lifetime> $item:ty, $input:ty
+     where [$($bounds:tt)*]
+     parallel where [$($par_bounds:tt)*]) => {
+        impl<$b, $($gen)*> IntoIterator for &$b $ty where $($bounds)* {
+            type Item = $item;
+            type IntoIter = $crate::IntoIter<$b, $input, $item>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.transducer().into_iter()
+            }
+        }
+
+        impl<$b, $($gen)*> ::rayon::iter::IntoParallelIterator for &$b $ty where $($par_bounds)* {
+            type Iter = $crate::IntoParIter<$b, $input, $item>;
+            type Item = $item;
+
+            fn into_par_iter(self) -> Self::Iter {
+                ::rayon::iter::IntoParallelIterator::into_par_iter(self.transducer())
+            }
+        }
+    };
+}
 
 mod builder;
 pub use builder::*;
@@ -71,13 +257,325 @@ pub use builder::*;
 mod lazy_transducer;
 pub use lazy_transducer::*;
 
+mod filter;
+pub use filter::*;
+
+mod flat_map;
+pub use flat_map::*;
+
+mod joined;
+pub use joined::*;
+
+mod boxed;
+pub use boxed::*;
+
+mod cached;
+pub use cached::*;
+
+#[cfg(feature = "scroll")]
+mod variable;
+#[cfg(feature = "scroll")]
+pub use variable::*;
+
+#[cfg(feature = "scroll")]
+mod strings;
+#[cfg(feature = "scroll")]
+pub use strings::*;
+
+#[cfg(feature = "scroll")]
+mod utf16;
+#[cfg(feature = "scroll")]
+pub use utf16::*;
+
+mod strtab;
+pub use strtab::*;
+
+mod varint;
+pub use varint::*;
+
+mod protobuf;
+pub use protobuf::*;
+
+mod bits;
+pub use bits::*;
+
+mod uuids;
+pub use uuids::*;
+
+mod delimited;
+pub use delimited::*;
+
+mod fixed_width;
+pub use fixed_width::*;
+
+mod chars;
+pub use chars::*;
+
+mod chunks;
+pub use chunks::*;
+
+mod tar;
+pub use tar::*;
+
+mod zip;
+pub use zip::*;
+
+mod fastx;
+pub use fastx::*;
+
+#[cfg(feature = "scroll")]
+mod variant;
+#[cfg(feature = "scroll")]
+pub use variant::*;
+
+#[cfg(feature = "scroll")]
+mod scroll_mut;
+#[cfg(feature = "scroll")]
+pub use scroll_mut::*;
+
+#[cfg(feature = "scroll")]
+mod num;
+#[cfg(feature = "scroll")]
+pub use num::*;
+
+#[cfg(feature = "scroll")]
+mod tlv;
+#[cfg(feature = "scroll")]
+pub use tlv::*;
+
+#[cfg(feature = "scroll")]
+mod notes;
+#[cfg(feature = "scroll")]
+pub use notes::*;
+
+mod parallel;
+pub use parallel::*;
+
+mod mutable;
+pub use mutable::*;
+
+#[cfg(feature = "scroll")]
+mod write;
+
+mod owned;
+pub use owned::*;
+
+#[cfg(feature = "mmap")]
+mod mmap;
+#[cfg(feature = "mmap")]
+pub use mmap::*;
+
+mod cache;
+
+mod source;
+pub use source::*;
+
+mod streaming;
+pub use streaming::*;
+
+mod growable;
+pub use growable::*;
+
+mod paged;
+pub use paged::*;
+
+mod chained;
+pub use chained::*;
+
+mod sparse;
+pub use sparse::*;
+
+mod base64;
+pub use base64::*;
+
+mod hex;
+pub use hex::*;
+
+mod encrypted;
+pub use encrypted::*;
+
+#[cfg(feature = "bgzf")]
+mod bgzf;
+#[cfg(feature = "bgzf")]
+pub use bgzf::*;
+
+#[cfg(feature = "zstd")]
+mod seekable;
+#[cfg(feature = "zstd")]
+pub use seekable::*;
+
+#[cfg(feature = "checksum")]
+mod checksum;
+#[cfg(feature = "checksum")]
+pub use checksum::*;
+
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+pub use http::*;
+
+#[cfg(feature = "pod")]
+mod pod;
+#[cfg(feature = "pod")]
+pub use pod::*;
+
+#[cfg(feature = "csv")]
+mod csv_rows;
+#[cfg(feature = "csv")]
+pub use csv_rows::*;
+
+#[cfg(feature = "json")]
+mod json_lines;
+#[cfg(feature = "json")]
+pub use json_lines::*;
+
+#[cfg(feature = "bincode")]
+mod bincoded;
+#[cfg(feature = "bincode")]
+pub use bincoded::*;
+
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "msgpack")]
+pub use msgpack::*;
+
+#[cfg(feature = "arrow")]
+mod arrow_column;
+#[cfg(feature = "arrow")]
+pub use arrow_column::*;
+
+#[cfg(feature = "nom")]
+mod nom_records;
+#[cfg(feature = "nom")]
+pub use nom_records::*;
+
+mod try_lazy_transducer;
+pub use try_lazy_transducer::*;
+
+mod lenient;
+pub use lenient::*;
+
+#[cfg(feature = "scroll")]
 pub use scroll::Endian;
 
+/// Not public API; used by the code generated by `lazy_transducer_derive`
+#[cfg(feature = "scroll")]
+#[doc(hidden)]
+pub mod __private {
+    pub use scroll::Pread;
+}
+
+/// What to do when more elements are requested than fit in the input source during construction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TruncationPolicy {
+    /// Return an `ElementOverflow` error
+    #[default]
+    Strict,
+    /// Clamp the number of elements to however many completely fit in the input source, e.g., when
+    /// a header lies about the number of entries in a table
+    ClampToSource,
+}
+
+use std::error;
+use std::fmt;
+use std::io;
+
 /// The kind of errors for constructing lazy transducers
-#[derive(Fail, Debug)]
+///
+/// Some variants only exist with their feature enabled, e.g., `ChecksumMismatch` with `checksum`,
+/// so matches on it must have a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum TransducerError {
-    #[fail(display = "Error during building: {}", _0)]
+    /// An input source or transducer was missing when building
     BuilderError(String),
-    #[fail(display = "Too many elements (size = {} * {}) requested from src of size: {}", nelements, sizeof_element, src_size)]
-    ElementOverflow{ nelements: usize, sizeof_element: usize, src_size: usize },
+    /// More elements, which are `stride` bytes apart, were requested than fit in the input source
+    ElementOverflow{ nelements: usize, sizeof_element: usize, stride: usize, src_size: usize },
+    /// Computing the total size of the requested elements, which are `stride` bytes apart,
+    /// overflows a `usize`
+    ArithmeticOverflow{ nelements: usize, sizeof_element: usize, stride: usize },
+    /// The requested element index is greater than the number of elements
+    OutOfBounds{ idx: usize, len: usize },
+    /// The `idx`th element, starting at byte `offset`, failed to parse
+    Parse{ idx: usize, offset: usize, source: Box<dyn error::Error + Send + Sync> },
+    /// The `idx`th element failed to be written at byte `offset`
+    Write{ idx: usize, offset: usize, source: Box<dyn error::Error + Send + Sync> },
+    /// The `len` bytes at `offset` of a [ChainedSource](struct.ChainedSource.html) straddle the
+    /// boundary between two of its segments, at byte `boundary`
+    StraddlesBoundary{ offset: usize, len: usize, boundary: usize },
+    /// The `idx`th element of a [StreamingTransducer](struct.StreamingTransducer.html) has not
+    /// fully arrived yet; only `available` elements have
+    NotYetAvailable{ idx: usize, available: usize },
+    /// The `len` bytes at `offset` of a [SparseSource](struct.SparseSource.html) are a hole, which
+    /// no segment maps, and the source has no fill byte
+    Hole{ offset: usize, len: usize },
+    /// The table at byte `offset` is not aligned to the `align` bytes required by the references
+    /// to its elements
+    Misaligned{ offset: usize, align: usize },
+    /// The bytes of a table do not have the `expected` checksum
+    #[cfg(feature = "checksum")]
+    ChecksumMismatch{ expected: Digest, actual: Digest },
+    /// An I/O error occurred while reading the input source
+    Io(io::Error),
+}
+
+impl fmt::Display for TransducerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TransducerError::BuilderError(ref msg) => write!(f, "Error during building: {}", msg),
+            TransducerError::ElementOverflow{ nelements, sizeof_element, stride, src_size } if stride != sizeof_element => {
+                write!(f, "Too many elements (size = {} * {}, {} bytes apart) requested from src of size: {}", nelements, sizeof_element, stride, src_size)
+            },
+            TransducerError::ElementOverflow{ nelements, sizeof_element, src_size, .. } => {
+                write!(f, "Too many elements (size = {} * {}) requested from src of size: {}", nelements, sizeof_element, src_size)
+            },
+            TransducerError::ArithmeticOverflow{ nelements, sizeof_element, stride } if stride != sizeof_element => {
+                write!(f, "Size of {} elements of size {}, {} bytes apart, overflows", nelements, sizeof_element, stride)
+            },
+            TransducerError::ArithmeticOverflow{ nelements, sizeof_element, .. } => {
+                write!(f, "Size of {} elements of size {} overflows", nelements, sizeof_element)
+            },
+            TransducerError::OutOfBounds{ idx, len } => write!(f, "Index {} is out of bounds for {} elements", idx, len),
+            TransducerError::Parse{ idx, offset, ref source } => {
+                write!(f, "Failed to parse element {} at offset {:#x}: {}", idx, offset, source)
+            },
+            TransducerError::Write{ idx, offset, ref source } => {
+                write!(f, "Failed to write element {} at offset {:#x}: {}", idx, offset, source)
+            },
+            TransducerError::StraddlesBoundary{ offset, len, boundary } => {
+                write!(f, "{} bytes at offset {:#x} straddle the segment boundary at {:#x}", len, offset, boundary)
+            },
+            TransducerError::NotYetAvailable{ idx, available } => {
+                write!(f, "Element {} has not arrived yet; only {} elements are available", idx, available)
+            },
+            TransducerError::Hole{ offset, len } => {
+                write!(f, "{} bytes at offset {:#x} are an unmapped hole", len, offset)
+            },
+            TransducerError::Misaligned{ offset, align } => {
+                write!(f, "Table at offset {:#x} is not aligned to {} bytes", offset, align)
+            },
+            #[cfg(feature = "checksum")]
+            TransducerError::ChecksumMismatch{ expected, actual } => {
+                write!(f, "Checksum mismatch: expected {}, found {}", expected, actual)
+            },
+            TransducerError::Io(ref err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl error::Error for TransducerError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            TransducerError::Parse{ ref source, .. } => Some(&**source),
+            TransducerError::Write{ ref source, .. } => Some(&**source),
+            TransducerError::Io(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TransducerError {
+    fn from(err: io::Error) -> Self {
+        TransducerError::Io(err)
+    }
 }
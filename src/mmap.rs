@@ -0,0 +1,114 @@
+use std::borrow::Cow;
+#[cfg(feature = "scroll")]
+use std::fmt::Debug;
+#[cfg(feature = "scroll")]
+use std::fs::File;
+use std::ops::Range;
+#[cfg(feature = "scroll")]
+use std::path::Path;
+
+use memmap2::Mmap;
+#[cfg(unix)]
+use memmap2::Advice;
+#[cfg(feature = "scroll")]
+use scroll::{self, ctx};
+#[cfg(feature = "scroll")]
+use scroll::ctx::SizeWith;
+
+use {OwnedTransducer, Source, TransducerError};
+use source::overflow;
+#[cfg(feature = "scroll")]
+use ScrollTransducer;
+
+/// A lazy transducer which owns a memory map of its input source, so that giant on-disk tables can
+/// be transduced without reading them into memory, and without the map's lifetime leaking into the
+/// caller's types.
+///
+/// It is an [OwnedTransducer](struct.OwnedTransducer.html) whose bytes are the memory map, with
+/// hints for the OS about how the map will be read.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::{ScrollTransducer, Endian};
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let path = std::env::temp_dir().join("lazy_transducer_mmap_example");
+/// std::fs::write(&path, [1u8, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]).unwrap();
+///
+/// let lt = ScrollTransducer::<u32>::from_file(&path, 3, Endian::Little).unwrap();
+/// assert_eq!(lt.get(2), Some(3));
+/// lt.advise_sequential().unwrap();
+/// lt.prefetch(0..12).unwrap();
+/// assert!(lt.prefetch(8..16).is_err());
+/// let sum: u32 = lt.into_par_iter().sum();
+/// assert_eq!(sum, 6);
+/// # }
+/// ```
+pub type MmapTransducer<Output, Ctx = ()> = OwnedTransducer<Output, Ctx, Mmap>;
+
+impl<Output, Ctx: Copy> MmapTransducer<Output, Ctx> {
+    /// Advise the OS that the memory map will be read sequentially, e.g., before iterating over
+    /// every element, so that it reads ahead aggressively and drops pages soon after they are read.
+    ///
+    /// Like the other hints, this does nothing on platforms without `madvise`.
+    pub fn advise_sequential(&self) -> Result<(), TransducerError> {
+        #[cfg(unix)]
+        self.bytes.advise(Advice::Sequential)?;
+        Ok(())
+    }
+    /// Advise the OS that the memory map will be read in random order, e.g., before looking up
+    /// scattered elements, or a parallel sweep whose threads each touch a different part of the
+    /// map, so that it doesn't waste I/O reading ahead.
+    pub fn advise_random(&self) -> Result<(), TransducerError> {
+        #[cfg(unix)]
+        self.bytes.advise(Advice::Random)?;
+        Ok(())
+    }
+    /// Advise the OS that the bytes in `range` of the memory map will be needed soon, so that it
+    /// starts reading them in the background, returning an error if the range is not in the map.
+    pub fn prefetch(&self, range: Range<usize>) -> Result<(), TransducerError> {
+        if self.bytes.get(range.clone()).is_none() {
+            return Err(overflow(range.start, range.end.saturating_sub(range.start), self.bytes.len()))
+        }
+        #[cfg(unix)]
+        {
+            if !range.is_empty() {
+                self.bytes.advise_range(Advice::WillNeed, range.start, range.end - range.start)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "scroll")]
+impl<'a, Output, Ctx, E> ScrollTransducer<'a, Output, Ctx>
+    where Ctx: Copy,
+          Output: for<'b> ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>,
+          E: From<scroll::Error> + Debug,
+{
+    /// Memory map the file at `path`, and create a new scroll-based lazy transducer over `count`
+    /// elements at its start, returning an error if the file can't be mapped or the elements do
+    /// not fit; see [MmapTransducer](type.MmapTransducer.html).
+    ///
+    /// The file must not be modified while it is mapped; if it is truncated, accessing an element
+    /// may crash the process.
+    pub fn from_file<P: AsRef<Path>>(path: P, count: usize, ctx: Ctx) -> Result<MmapTransducer<Output, Ctx>, TransducerError> {
+        let file = File::open(path)?;
+        let map = unsafe { Mmap::map(&file)? };
+        Self::from_shared(map, count, ctx)
+    }
+}
+
+impl Source for Mmap {
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self[..].read_bytes(offset, len)
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+
+use rmp_serde;
+use serde::de::DeserializeOwned;
+
+use TransducerError;
+
+/// Reads the big endian length of `size` bytes at `offset`, or `None` if it is truncated
+#[inline]
+fn length(bytes: &[u8], offset: usize, size: usize) -> Option<usize> {
+    let bytes = bytes.get(offset..offset.checked_add(size)?)?;
+    Some(bytes.iter().fold(0, |len, byte| len << 8 | *byte as usize))
+}
+
+/// Returns the end of the MessagePack value starting at `start`.
+///
+/// Rather than recursing into arrays and maps, this counts how many values are still pending, so
+/// that deeply nested input cannot overflow the stack.
+fn skip_value(bytes: &[u8], start: usize) -> Result<usize, &'static str> {
+    const TRUNCATED: &str = "truncated MessagePack value";
+    let mut offset = start;
+    let mut pending = 1usize;
+    while pending > 0 {
+        let marker = *bytes.get(offset).ok_or(TRUNCATED)?;
+        offset += 1;
+        pending -= 1;
+        // the number of nested values and payload bytes following the marker
+        let (values, payload) = match marker {
+            0x00..=0x7f | 0xc0 | 0xc2 | 0xc3 | 0xe0..=0xff => (0, 0),
+            0x80..=0x8f => (2 * (marker & 0x0f) as usize, 0),
+            0x90..=0x9f => ((marker & 0x0f) as usize, 0),
+            0xa0..=0xbf => (0, (marker & 0x1f) as usize),
+            0xc1 => return Err("reserved MessagePack marker 0xc1"),
+            0xc4..=0xc6 => {
+                let size = 1 << (marker - 0xc4);
+                let len = length(bytes, offset, size).ok_or(TRUNCATED)?;
+                (0, size + len)
+            },
+            0xc7..=0xc9 => {
+                let size = 1 << (marker - 0xc7);
+                let len = length(bytes, offset, size).ok_or(TRUNCATED)?;
+                // the extension type
+                (0, size + 1 + len)
+            },
+            0xca => (0, 4),
+            0xcb => (0, 8),
+            0xcc..=0xcf => (0, 1 << (marker - 0xcc)),
+            0xd0..=0xd3 => (0, 1 << (marker - 0xd0)),
+            0xd4..=0xd8 => (0, 1 + (1 << (marker - 0xd4))),
+            0xd9..=0xdb => {
+                let size = 1 << (marker - 0xd9);
+                let len = length(bytes, offset, size).ok_or(TRUNCATED)?;
+                (0, size + len)
+            },
+            0xdc | 0xdd => {
+                let size = 2 << (marker - 0xdc);
+                (length(bytes, offset, size).ok_or(TRUNCATED)?, size)
+            },
+            0xde | 0xdf => {
+                let size = 2 << (marker - 0xde);
+                (2 * length(bytes, offset, size).ok_or(TRUNCATED)?, size)
+            },
+        };
+        offset = offset.checked_add(payload).filter(|end| *end <= bytes.len()).ok_or(TRUNCATED)?;
+        // every pending value is at least one byte, so more than remain can't all be present
+        pending = pending.checked_add(values).filter(|pending| *pending <= bytes.len() - offset).ok_or(TRUNCATED)?;
+    }
+    Ok(offset)
+}
+
+/// A lazy transducer over a stream of concatenated [MessagePack](https://msgpack.org) values,
+/// e.g., a log of events, which deserializes a value only when it is accessed.
+///
+/// A single pass at construction scans the boundary of every value, without deserializing it, and
+/// records where it starts in an index, so that afterwards, accessing a value is O(1), and the
+/// values can be deserialized in parallel, via [rmp-serde](https://docs.rs/rmp-serde).
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::MsgPackTransducer;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// // [1, "hi"], [2, "bye"], and [300, ""]
+/// let stream = [0x92, 0x01, 0xa2, b'h', b'i', 0x92, 0x02, 0xa3, b'b', b'y', b'e', 0x92, 0xcd, 0x01, 0x2c, 0xa0];
+/// let lt: MsgPackTransducer<(u32, String)> = MsgPackTransducer::new(&stream).unwrap();
+/// assert_eq!(lt.len(), 3);
+/// assert_eq!(lt.offset_of(2), Some(11));
+/// assert_eq!(lt.get(1).unwrap().unwrap(), (2, "bye".to_string()));
+/// let total: u32 = lt.into_par_iter().map(|value| value.unwrap().0).sum();
+/// assert_eq!(total, 303);
+/// # }
+/// ```
+pub struct MsgPackTransducer<'a, T> {
+    bytes: &'a [u8],
+    /// The offset of every value, plus the end of the last value as a final entry
+    offsets: Vec<usize>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> fmt::Debug for MsgPackTransducer<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MsgPackTransducer")
+            .field("bytes", &self.bytes)
+            .field("offsets", &self.offsets)
+            .finish()
+    }
+}
+
+impl<'a, T> Clone for MsgPackTransducer<'a, T> {
+    fn clone(&self) -> Self {
+        MsgPackTransducer { bytes: self.bytes, offsets: self.offsets.clone(), _marker: PhantomData }
+    }
+}
+
+impl<'a, T: DeserializeOwned> MsgPackTransducer<'a, T> {
+    /// Create a new lazy transducer over the MessagePack values concatenated in `bytes`, returning
+    /// a `Parse` error with the index and offset of the first value which is truncated, or uses the
+    /// reserved marker.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        let mut offsets = vec![0];
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let idx = offsets.len() - 1;
+            offset = skip_value(bytes, offset).map_err(|msg| {
+                TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) }
+            })?;
+            offsets.push(offset);
+        }
+        Ok(MsgPackTransducer { bytes, offsets, _marker: PhantomData })
+    }
+    /// How many values are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th value, or `None` if the index is greater than the
+    /// number of values.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len() { None } else { Some(self.offsets[idx]) }
+    }
+    /// Returns the encoded bytes of the `idx`th value, or `None` if the index is greater than the
+    /// number of values.
+    pub fn value(&self, idx: usize) -> Option<&'a [u8]> {
+        let offset = self.offset_of(idx)?;
+        Some(&self.bytes[offset..self.offsets[idx + 1]])
+    }
+    /// Deserialize a value out of the lazy transducer, returning `None` if the index is greater
+    /// than the number of values in this lazy transducer, and a `Parse` error if it fails to
+    /// deserialize.
+    pub fn get(&self, idx: usize) -> Option<Result<T, TransducerError>> {
+        self.value(idx).map(|value| {
+            rmp_serde::from_slice(value).map_err(|err| TransducerError::Parse{ idx, offset: self.offsets[idx], source: Box::new(err) })
+        })
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the deserialized values, borrowing this one.
+    impl['a, T] MsgPackTransducer<'a, T> => <'b> Result<T, TransducerError>
+    where [T: DeserializeOwned]
+    parallel where [T: DeserializeOwned + Send + Sync]
+}
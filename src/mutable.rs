@@ -0,0 +1,129 @@
+use std::fmt::Debug;
+
+use rayon::prelude::*;
+
+use TransducerError;
+use lazy_transducer::total_size;
+
+/// A mutable counterpart of a lazy transducer, whose transducer receives a mutable window of
+/// `stride` bytes for every element, enabling lazy, indexable, and parallel in-place
+/// transformations, e.g., byte-swapping a whole table.
+///
+/// The windows never overlap, since every element is exactly `stride` bytes apart, so they can be
+/// safely transduced in parallel with [par_iter_mut](#method.par_iter_mut).
+///
+/// The bytes can be borrowed, e.g., a `&mut [u8]`, or owned, e.g., a `Vec<u8>`, which is returned
+/// by [into_inner](#method.into_inner); the transducer receives the `(window, ctx)` pair, use `()`
+/// if you don't need a context.
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::MutLazyTransducer;
+///
+/// let mut bytes = vec![0, 0, 0, 1, 0, 0, 0, 2, 0xff];
+/// {
+///   let mut lt = MutLazyTransducer::new(&mut bytes[..], 2, 4, (), |(window, ()), _idx| {
+///     window.reverse();
+///   }).unwrap();
+///   lt.par_apply();
+/// }
+/// assert_eq!(bytes, vec![1, 0, 0, 0, 2, 0, 0, 0, 0xff]);
+///
+/// // elements are transduced lazily, and can return a value
+/// let mut lt = MutLazyTransducer::new(bytes, 2, 4, 10, |(window, scale), idx| {
+///   window[0] *= scale;
+///   (idx, window[0])
+/// }).unwrap();
+/// assert_eq!(lt.get_mut(1), Some((1, 20)));
+/// assert_eq!(lt.get_mut(2), None);
+/// assert_eq!(lt.into_inner(), vec![1, 0, 0, 0, 20, 0, 0, 0, 0xff]);
+/// ```
+pub struct MutLazyTransducer<B, Output, Ctx = ()> {
+    bytes: B,
+    count: usize,
+    stride: usize,
+    ctx: Ctx,
+    transducer: fn((&mut [u8], Ctx), usize) -> Output,
+}
+
+impl<B, Output, Ctx> MutLazyTransducer<B, Output, Ctx>
+    where B: AsRef<[u8]> + AsMut<[u8]>,
+          Ctx: Copy,
+{
+    /// Create a new mutable lazy transducer over `count` elements in `bytes`, which are `stride`
+    /// bytes apart, returning an error if they do not fit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn new(bytes: B, count: usize, stride: usize, ctx: Ctx, transducer: fn((&mut [u8], Ctx), usize) -> Output) -> Result<Self, TransducerError> {
+        assert!(stride != 0, "stride must be non-zero");
+        let src_size = bytes.as_ref().len();
+        if total_size(count, stride)? > src_size {
+            return Err(TransducerError::ElementOverflow{ nelements: count, sizeof_element: stride, stride, src_size })
+        }
+        Ok(MutLazyTransducer { bytes, count, stride, ctx, transducer })
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.count
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// The bytes, including any changes made by the transducer so far
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes.as_ref()
+    }
+    /// Returns the bytes, consuming this lazy transducer.
+    pub fn into_inner(self) -> B {
+        self.bytes
+    }
+    /// The windows of every element
+    fn windows(&mut self) -> &mut [u8] {
+        &mut self.bytes.as_mut()[..self.count * self.stride]
+    }
+    /// Transduce the `idx`th element, returning `None` if the index is greater than the number of
+    /// elements in this lazy transducer.
+    pub fn get_mut(&mut self, idx: usize) -> Option<Output> {
+        if idx >= self.count {
+            return None
+        }
+        let start = idx * self.stride;
+        let window = &mut self.bytes.as_mut()[start..start + self.stride];
+        Some((self.transducer)((window, self.ctx), idx))
+    }
+    /// Returns an iterator which transduces every element in order.
+    pub fn iter_mut<'b>(&'b mut self) -> impl Iterator<Item = Output> + 'b {
+        let (stride, ctx, transducer) = (self.stride, self.ctx, self.transducer);
+        self.windows().chunks_mut(stride).enumerate().map(move |(idx, window)| transducer((window, ctx), idx))
+    }
+    /// Returns a parallel iterator which transduces every element.
+    pub fn par_iter_mut<'b>(&'b mut self) -> impl IndexedParallelIterator<Item = Output> + 'b
+        where Ctx: Send + Sync,
+              Output: Send,
+    {
+        let (stride, ctx, transducer) = (self.stride, self.ctx, self.transducer);
+        self.windows().par_chunks_mut(stride).enumerate().map(move |(idx, window)| transducer((window, ctx), idx))
+    }
+    /// Transduce every element in parallel, discarding the outputs, e.g., when the transducer only
+    /// transforms its window in place.
+    pub fn par_apply(&mut self)
+        where Ctx: Send + Sync,
+              Output: Send,
+    {
+        self.par_iter_mut().for_each(drop)
+    }
+}
+
+impl<B, Output, Ctx: Debug> Debug for MutLazyTransducer<B, Output, Ctx> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("MutLazyTransducer")
+            .field("count", &self.count)
+            .field("stride", &self.stride)
+            .field("ctx", &self.ctx)
+            .finish()
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+use std::io;
+
+use nom;
+use nom::IResult;
+
+use TransducerError;
+
+/// A `Parse` error for the `idx`th record, at `offset`
+fn malformed(idx: usize, offset: usize, msg: String) -> TransducerError {
+    TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) }
+}
+
+/// Why the parser failed
+fn describe<I>(err: nom::Err<nom::error::Error<I>>) -> String {
+    match err {
+        nom::Err::Incomplete(_) => "incomplete record".to_string(),
+        nom::Err::Error(err) | nom::Err::Failure(err) => format!("{:?} failed", err.code),
+    }
+}
+
+/// A lazy transducer over the records of any grammar written with [nom](https://docs.rs/nom),
+/// given a parser for one record, which re-parses a record only when it is accessed.
+///
+/// Construction runs the parser over the whole input once, recording where every record starts in
+/// an index; afterwards, accessing record `i` re-runs the parser on the bytes of that record, so
+/// the parsed records never need to be stored, and can be parsed again in parallel. The parser
+/// should therefore be a plain function, or a closure which doesn't mutate its environment, and
+/// shouldn't need to look past the end of a record.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate nom;
+/// use lazy_transducer::NomTransducer;
+/// use nom::bytes::complete::{tag, take_while1};
+/// use nom::character::complete::{alpha1, digit1};
+/// use nom::sequence::{separated_pair, terminated};
+/// use nom::IResult;
+///
+/// // "key=value;"
+/// fn pair(input: &[u8]) -> IResult<&[u8], (&[u8], &[u8])> {
+///     terminated(separated_pair(alpha1, tag("="), take_while1(|byte: u8| byte != b';')), tag(";"))(input)
+/// }
+///
+/// # fn main() {
+/// let lt = NomTransducer::new(&b"host=example.org;port=8080;user=m4b;"[..], pair).unwrap();
+/// assert_eq!(lt.len(), 3);
+/// assert_eq!(lt.get(1).unwrap().unwrap(), (&b"port"[..], &b"8080"[..]));
+/// assert_eq!(lt.offset_of(2), Some(27));
+///
+/// // a parse error reports the index and offset of the record
+/// let err = NomTransducer::new(&b"1;2;x;"[..], |input| terminated(digit1, tag(";"))(input)).unwrap_err();
+/// assert_eq!(err.to_string(), "Failed to parse element 2 at offset 0x4: Digit failed");
+/// # }
+/// ```
+pub struct NomTransducer<'a, F> {
+    bytes: &'a [u8],
+    parser: F,
+    /// The offset of every record, plus the end of the last record as a final entry
+    offsets: Vec<usize>,
+}
+
+impl<'a, F: Clone> Clone for NomTransducer<'a, F> {
+    fn clone(&self) -> Self {
+        NomTransducer { bytes: self.bytes, parser: self.parser.clone(), offsets: self.offsets.clone() }
+    }
+}
+
+impl<'a, F> fmt::Debug for NomTransducer<'a, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NomTransducer")
+            .field("bytes", &self.bytes.len())
+            .field("offsets", &self.offsets)
+            .finish()
+    }
+}
+
+impl<'a, O, F: Fn(&'a [u8]) -> IResult<&'a [u8], O>> NomTransducer<'a, F> {
+    /// Create a new lazy transducer over the records of `bytes`, which are parsed back to back with
+    /// `parser` until the input is exhausted, returning a `Parse` error with the index and offset
+    /// of the first record which fails to parse, is incomplete, or is empty.
+    pub fn new(bytes: &'a [u8], parser: F) -> Result<Self, TransducerError> {
+        let mut offsets = vec![0];
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let idx = offsets.len() - 1;
+            let (rest, _) = parser(&bytes[offset..]).map_err(|err| malformed(idx, offset, describe(err)))?;
+            // a parser which consumes nothing would loop forever
+            if rest.len() == bytes.len() - offset {
+                return Err(malformed(idx, offset, "parser consumed no input".to_string()))
+            }
+            offset = bytes.len() - rest.len();
+            offsets.push(offset);
+        }
+        Ok(NomTransducer { bytes, parser, offsets })
+    }
+    /// How many records are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th record, or `None` if the index is greater than the
+    /// number of records.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len() { None } else { Some(self.offsets[idx]) }
+    }
+    /// Returns the raw bytes of the `idx`th record, or `None` if the index is greater than the
+    /// number of records.
+    pub fn record(&self, idx: usize) -> Option<&'a [u8]> {
+        if idx >= self.len() { None } else { Some(&self.bytes[self.offsets[idx]..self.offsets[idx + 1]]) }
+    }
+    /// Parse a record out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of records in this lazy transducer, and a `Parse` error if the parser fails on
+    /// the bytes of the record, e.g., because it needs to look past the end of it.
+    pub fn get(&self, idx: usize) -> Option<Result<O, TransducerError>> {
+        let record = self.record(idx)?;
+        Some((self.parser)(record).map(|(_, record)| record).map_err(|err| malformed(idx, self.offsets[idx], describe(err))))
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the parsed records, borrowing this one.
+    impl['a, O, F] NomTransducer<'a, F> => <'b> Result<O, TransducerError>
+    where [O: 'a, F: Fn(&'a [u8]) -> IResult<&'a [u8], O>]
+    parallel where [O: 'a + Send + Sync, F: Fn(&'a [u8]) -> IResult<&'a [u8], O> + Sync]
+}
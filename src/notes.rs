@@ -0,0 +1,127 @@
+use std::io;
+
+use scroll::{Endian, Pread};
+
+use TransducerError;
+
+/// The size in bytes of the header of a note: its name size, descriptor size, and type
+const HEADER_LEN: usize = 12;
+
+/// Reads the `u32` of a header at `offset`, which must be in bounds
+#[inline]
+fn word(bytes: &[u8], offset: usize, endian: Endian) -> u32 {
+    bytes.pread_with(offset, endian).unwrap()
+}
+
+/// A note of a [NoteTransducer](struct.NoteTransducer.html), as found in an ELF `PT_NOTE` segment
+/// or `SHT_NOTE` section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Note<'a> {
+    /// The type, whose meaning depends on the name, e.g., `NT_GNU_BUILD_ID` is 3 for `GNU`
+    pub n_type: u32,
+    /// The name of the owner, without its NUL terminator, e.g., `GNU`
+    pub name: &'a [u8],
+    /// The descriptor
+    pub desc: &'a [u8],
+}
+
+/// A lazy transducer over records of a fixed header followed by variable size payloads, which are
+/// each padded to an alignment, like the notes of an ELF file, yielding the type, name, and
+/// descriptor of every note.
+///
+/// A single pass at construction reads the sizes in every header, and records where the note
+/// starts in an index, so that afterwards, accessing a note is O(1). The payloads are padded to
+/// 4 bytes in most notes, but to 8 bytes in, e.g., GNU property notes of 64-bit ELF files; the
+/// padding of the last note may be missing.
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::{NoteTransducer, Note, Endian};
+///
+/// let mut bytes = vec![4, 0, 0, 0, 3, 0, 0, 0, 3, 0, 0, 0];
+/// bytes.extend_from_slice(b"GNU\0\xab\xcd\xef\0");
+/// bytes.extend_from_slice(&[3, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]);
+/// bytes.extend_from_slice(b"Go\0\0");
+/// let lt = NoteTransducer::new(&bytes, Endian::Little, 4).unwrap();
+/// assert_eq!(lt.len(), 2);
+/// assert_eq!(lt.get(0), Some(Note { n_type: 3, name: b"GNU", desc: &[0xab, 0xcd, 0xef] }));
+/// assert_eq!(lt.offset_of(1), Some(20));
+/// assert_eq!(lt.get(1).unwrap().name, b"Go");
+/// ```
+#[derive(Debug, Clone)]
+pub struct NoteTransducer<'a> {
+    bytes: &'a [u8],
+    endian: Endian,
+    align: usize,
+    /// The offset of the header of every note
+    offsets: Vec<usize>,
+}
+
+impl<'a> NoteTransducer<'a> {
+    /// Create a new lazy transducer over the notes in `bytes`, whose headers are in `endian` byte
+    /// order, and whose payloads are padded to `align` bytes, returning an `InvalidInput` I/O error
+    /// if `align` is not a power of two, and a `Parse` error with the index and offset of the first
+    /// note which is truncated.
+    pub fn new(bytes: &'a [u8], endian: Endian, align: usize) -> Result<Self, TransducerError> {
+        if !align.is_power_of_two() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "alignment must be a power of two").into())
+        }
+        let padded = |end: usize| end.checked_add(align - 1).map(|end| end & !(align - 1));
+        let mut offsets = Vec::new();
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let idx = offsets.len();
+            let malformed = |msg| TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) };
+            if bytes.len() - offset < HEADER_LEN {
+                return Err(malformed("truncated note header"))
+            }
+            let (namesz, descsz) = (word(bytes, offset, endian) as usize, word(bytes, offset + 4, endian) as usize);
+            // the header is in bounds, but the name size is untrusted
+            let desc = (offset + HEADER_LEN).checked_add(namesz).and_then(padded);
+            let end = desc.and_then(|desc| desc.checked_add(descsz)).filter(|end| *end <= bytes.len()).ok_or_else(|| malformed("truncated note"))?;
+            offsets.push(offset);
+            offset = padded(end).map_or(bytes.len(), |next| next.min(bytes.len()));
+        }
+        Ok(NoteTransducer { bytes, endian, align, offsets })
+    }
+    /// The alignment of the payloads
+    pub fn align(&self) -> usize {
+        self.align
+    }
+    /// How many notes are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+    /// Whether this lazy transducer has no notes
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+    /// Returns the byte offset of the header of the `idx`th note, or `None` if the index is greater
+    /// than the number of notes.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.offsets.get(idx).cloned()
+    }
+    /// Get a note out of the lazy transducer, returning `None` if the index is greater than the
+    /// number of notes in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Note<'a>> {
+        let offset = self.offset_of(idx)?;
+        let header = |field| word(self.bytes, offset + field, self.endian);
+        let (namesz, descsz, n_type) = (header(0) as usize, header(4) as usize, header(8));
+        let name = &self.bytes[offset + HEADER_LEN..offset + HEADER_LEN + namesz];
+        let desc = (offset + HEADER_LEN + namesz + self.align - 1) & !(self.align - 1);
+        Some(Note {
+            n_type,
+            name: name.split_last().filter(|(nul, _)| **nul == 0).map_or(name, |(_, name)| name),
+            desc: &self.bytes[desc..desc + descsz],
+        })
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the notes, borrowing this one.
+    impl['a] NoteTransducer<'a> => <'b> Note<'a>
+    where []
+    parallel where []
+}
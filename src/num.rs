@@ -0,0 +1,143 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+
+use rayon::prelude::*;
+use scroll::Endian;
+
+use {IntoIter, IntoParIter, LazyTransducer};
+
+/// A primitive number which can be decoded from bytes in either endianness; see
+/// [NumTransducer](struct.NumTransducer.html).
+pub trait Num: Copy + Send + Sync + 'static {
+    /// The size in bytes of the number
+    const SIZE: usize;
+    /// Decode a number from the first `SIZE` bytes of `bytes`, in `endian` byte order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` is shorter than `SIZE`.
+    fn from_bytes(bytes: &[u8], endian: Endian) -> Self;
+}
+
+macro_rules! num {
+    ($($num:ty),*) => {
+        $(
+            impl Num for $num {
+                const SIZE: usize = mem::size_of::<$num>();
+                #[inline]
+                fn from_bytes(bytes: &[u8], endian: Endian) -> Self {
+                    let mut buf = [0; mem::size_of::<$num>()];
+                    buf.copy_from_slice(&bytes[..Self::SIZE]);
+                    if endian.is_little() { <$num>::from_le_bytes(buf) } else { <$num>::from_be_bytes(buf) }
+                }
+            }
+        )*
+    }
+}
+
+num!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+/// A lazy transducer decoding the bytes of its input source as an array of numbers, in either
+/// endianness, without a custom transducer function.
+///
+/// Every complete number in the bytes is an element; any trailing bytes which are too few for
+/// another number are ignored. See the aliases, e.g., [U32s](type.U32s.html).
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::{U16s, F32s, Endian};
+///
+/// let bytes = [0u8, 1, 0, 2, 0, 3, 0xff];
+/// let shorts = U16s::new(&bytes, Endian::Big);
+/// assert_eq!(shorts.len(), 3);
+/// assert_eq!(shorts.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+///
+/// let floats = F32s::new(&[0, 0, 0x80, 0x3f], Endian::Little);
+/// assert_eq!(floats.get(0), Some(1.0));
+/// ```
+pub struct NumTransducer<'a, T> {
+    bytes: &'a [u8],
+    endian: Endian,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T> fmt::Debug for NumTransducer<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NumTransducer")
+            .field("bytes", &self.bytes)
+            .field("endian", &self.endian)
+            .finish()
+    }
+}
+
+/// A lazy transducer over an array of `u16`s; see [NumTransducer](struct.NumTransducer.html)
+pub type U16s<'a> = NumTransducer<'a, u16>;
+/// A lazy transducer over an array of `u32`s; see [NumTransducer](struct.NumTransducer.html)
+pub type U32s<'a> = NumTransducer<'a, u32>;
+/// A lazy transducer over an array of `u64`s; see [NumTransducer](struct.NumTransducer.html)
+pub type U64s<'a> = NumTransducer<'a, u64>;
+/// A lazy transducer over an array of `i16`s; see [NumTransducer](struct.NumTransducer.html)
+pub type I16s<'a> = NumTransducer<'a, i16>;
+/// A lazy transducer over an array of `i32`s; see [NumTransducer](struct.NumTransducer.html)
+pub type I32s<'a> = NumTransducer<'a, i32>;
+/// A lazy transducer over an array of `i64`s; see [NumTransducer](struct.NumTransducer.html)
+pub type I64s<'a> = NumTransducer<'a, i64>;
+/// A lazy transducer over an array of `f32`s; see [NumTransducer](struct.NumTransducer.html)
+pub type F32s<'a> = NumTransducer<'a, f32>;
+/// A lazy transducer over an array of `f64`s; see [NumTransducer](struct.NumTransducer.html)
+pub type F64s<'a> = NumTransducer<'a, f64>;
+
+impl<'a, T> Clone for NumTransducer<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for NumTransducer<'a, T> {}
+
+impl<'a, T: Num> NumTransducer<'a, T> {
+    /// Create a new lazy transducer over the numbers in `bytes`, in `endian` byte order.
+    pub fn new(bytes: &'a [u8], endian: Endian) -> Self {
+        NumTransducer { bytes, endian, _marker: PhantomData }
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.bytes.len() / T::SIZE
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Get an element out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of elements in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<T> {
+        if idx >= self.len() { None } else { Some(T::from_bytes(&self.bytes[idx * T::SIZE..], self.endian)) }
+    }
+    /// Returns a lazy transducer over the elements.
+    pub fn transducer(&self) -> LazyTransducer<'a, (&'a [u8], Endian), T> {
+        LazyTransducer::new((self.bytes, self.endian), self.len(), |(bytes, endian), idx| {
+            T::from_bytes(&bytes[idx * T::SIZE..], endian)
+        })
+    }
+}
+
+impl<'a, T: Num> IntoIterator for NumTransducer<'a, T> {
+    type Item = T;
+    type IntoIter = IntoIter<'a, (&'a [u8], Endian), T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.transducer().into_iter()
+    }
+}
+
+impl<'a, T: Num> IntoParallelIterator for NumTransducer<'a, T> {
+    type Iter = IntoParIter<'a, (&'a [u8], Endian), T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.transducer().into_par_iter()
+    }
+}
@@ -0,0 +1,177 @@
+use std::fmt::Debug;
+use std::ops::Deref;
+use std::sync::Arc;
+
+#[cfg(feature = "scroll")]
+use scroll::{self, ctx, Pread};
+#[cfg(feature = "scroll")]
+use scroll::ctx::SizeWith;
+
+use LazyTransducer;
+#[cfg(feature = "scroll")]
+use {ScrollTransducer, TransducerError};
+#[cfg(feature = "scroll")]
+use lazy_transducer::total_size;
+
+/// A lazy transducer which owns the bytes of its input source, so that it is `'static`, and can be
+/// returned from a function or stored in a struct without the caller keeping the bytes alive.
+///
+/// The bytes are a `Vec<u8>` by default, but can be anything which dereferences to bytes, e.g., an
+/// `Arc<Vec<u8>>` or an `Rc<[u8]>`, so that many transducers over the same file, e.g., its symbols,
+/// relocations, and strings, share one allocation, which is freed when the last of them is
+/// dropped; cloning such a transducer only bumps the reference count. See
+/// [SharedTransducer](type.SharedTransducer.html) and [from_shared](type.ScrollTransducer.html#method.from_shared).
+///
+/// The transducer receives the `(bytes, ctx)` pair, like a [Builder](struct.Builder.html) with a
+/// [context](struct.Builder.html#method.context); use `()` if you don't need a context.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{OwnedTransducer, ScrollTransducer, Endian};
+///
+/// fn load() -> OwnedTransducer<u16, Endian> {
+///     let bytes = vec![1u8, 0, 2, 0, 3, 0];
+///     ScrollTransducer::from_vec(bytes, 3, Endian::Little).unwrap()
+/// }
+///
+/// # fn main() {
+/// let lt = load();
+/// assert_eq!(lt.get(1), Some(2));
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// # }
+/// ```
+pub struct OwnedTransducer<Output, Ctx = (), B = Vec<u8>> {
+    pub(crate) bytes: B,
+    ctx: Ctx,
+    count: usize,
+    transducer: fn((&[u8], Ctx), usize) -> Output,
+}
+
+/// A lazy transducer sharing the bytes of its input source with other transducers; see
+/// [OwnedTransducer](struct.OwnedTransducer.html).
+pub type SharedTransducer<Output, Ctx = ()> = OwnedTransducer<Output, Ctx, Arc<Vec<u8>>>;
+
+impl<Output, Ctx, B> OwnedTransducer<Output, Ctx, B>
+    where Ctx: Copy,
+          B: Deref,
+          B::Target: AsRef<[u8]>,
+{
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.count
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// The bytes of the input source
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes.deref().as_ref()
+    }
+    /// Returns the bytes of the input source, consuming this lazy transducer.
+    pub fn into_bytes(self) -> B {
+        self.bytes
+    }
+    /// Get an element out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of elements in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Output> {
+        if idx >= self.count { None } else { Some((self.transducer)((self.bytes(), self.ctx), idx)) }
+    }
+    /// Returns a lazy transducer over the elements, borrowing the bytes.
+    pub fn transducer<'b>(&'b self) -> LazyTransducer<'b, (&'b [u8], Ctx), Output> {
+        LazyTransducer::new((self.bytes(), self.ctx), self.count, self.transducer)
+    }
+}
+
+impl<Output, Ctx: Clone, B: Clone> Clone for OwnedTransducer<Output, Ctx, B> {
+    fn clone(&self) -> Self {
+        OwnedTransducer { bytes: self.bytes.clone(), ctx: self.ctx.clone(), count: self.count, transducer: self.transducer }
+    }
+}
+
+impl<Output, Ctx, B> Debug for OwnedTransducer<Output, Ctx, B>
+    where Ctx: Debug,
+          B: Deref,
+          B::Target: AsRef<[u8]>,
+{
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("OwnedTransducer")
+            .field("len", &self.bytes.deref().as_ref().len())
+            .field("ctx", &self.ctx)
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+impl<'a, Output, Ctx: Copy> LazyTransducer<'a, (&'a [u8], Ctx), Output> {
+    /// Create a new lazy transducer over `count` elements of `bytes`, which it takes ownership of,
+    /// e.g., a `Vec<u8>` or an `Arc<Vec<u8>>`; see [OwnedTransducer](struct.OwnedTransducer.html).
+    ///
+    /// Like [new](#method.new), the count is not checked against the size of the bytes.
+    pub fn from_owned<B>(bytes: B, ctx: Ctx, count: usize, transducer: fn((&[u8], Ctx), usize) -> Output) -> OwnedTransducer<Output, Ctx, B>
+        where B: Deref,
+              B::Target: AsRef<[u8]>,
+    {
+        OwnedTransducer { bytes, ctx, count, transducer }
+    }
+}
+
+#[cfg(feature = "scroll")]
+impl<'a, Output, Ctx, E> ScrollTransducer<'a, Output, Ctx>
+    where Ctx: Copy,
+          Output: for<'b> ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>,
+          E: From<scroll::Error> + Debug,
+{
+    /// Create a new scroll-based lazy transducer over `count` elements at the start of `bytes`,
+    /// which it takes ownership of, returning an error if the elements do not fit; see
+    /// [OwnedTransducer](struct.OwnedTransducer.html).
+    pub fn from_vec(bytes: Vec<u8>, count: usize, ctx: Ctx) -> Result<OwnedTransducer<Output, Ctx>, TransducerError> {
+        Self::from_shared(bytes, count, ctx)
+    }
+    /// Create a new scroll-based lazy transducer over `count` elements at the start of `bytes`,
+    /// which may be shared with other transducers, e.g., an `Arc<Vec<u8>>`, returning an error if
+    /// the elements do not fit; see [OwnedTransducer](struct.OwnedTransducer.html).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate lazy_transducer;
+    /// use lazy_transducer::{ScrollTransducer, SharedTransducer, Endian};
+    /// use std::sync::Arc;
+    ///
+    /// # fn main() {
+    /// let bytes = Arc::new(vec![1u8, 0, 2, 0, 3, 0, 4, 0]);
+    /// let shorts: SharedTransducer<u16, Endian> = ScrollTransducer::from_shared(bytes.clone(), 4, Endian::Little).unwrap();
+    /// let ints: SharedTransducer<u32, Endian> = ScrollTransducer::from_shared(bytes.clone(), 2, Endian::Little).unwrap();
+    /// let copy = ints.clone();
+    /// assert_eq!(Arc::strong_count(&bytes), 4);
+    /// drop(bytes);
+    /// assert_eq!(shorts.get(3), Some(4));
+    /// assert_eq!(copy.get(1), Some(0x40003));
+    /// # }
+    /// ```
+    pub fn from_shared<B>(bytes: B, count: usize, ctx: Ctx) -> Result<OwnedTransducer<Output, Ctx, B>, TransducerError>
+        where B: Deref,
+              B::Target: AsRef<[u8]>,
+    {
+        let sizeof_element = Output::size_with(&ctx);
+        let src_size = bytes.deref().as_ref().len();
+        if total_size(count, sizeof_element)? > src_size {
+            return Err(TransducerError::ElementOverflow{ nelements: count, sizeof_element, stride: sizeof_element, src_size })
+        }
+        // we unwrap because we bounds checked on creation
+        Ok(LazyTransducer::from_owned(bytes, ctx, count, |(bytes, ctx), idx| {
+            bytes.pread_with(idx * Output::size_with(&ctx), ctx).unwrap()
+        }))
+    }
+}
+
+borrowed_transducer! {
+    iterators impl[Output, Ctx, B] OwnedTransducer<Output, Ctx, B> => <'b> Output, (&'b [u8], Ctx)
+    where [Ctx: Copy, B: Deref, B::Target: AsRef<[u8]>]
+    parallel where [Output: Send + Sync, Ctx: Copy + Send + Sync, B: Deref, B::Target: AsRef<[u8]>]
+}
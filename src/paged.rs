@@ -0,0 +1,196 @@
+use std::borrow::Cow;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use {Source, TransducerError};
+use cache::BlockCache;
+use source::overflow;
+
+/// The number of pages cached by [new](struct.PagedSource.html#method.new)
+const DEFAULT_CAPACITY: usize = 64;
+
+/// An input source for inputs which can't be memory mapped, e.g., files on network filesystems or
+/// special devices, which reads fixed-size pages on demand into a bounded cache.
+///
+/// A paged source is shared by reference as the input of a lazy transducer, whose transducer
+/// accesses the bytes of an element via [with_bytes](#method.with_bytes) (or
+/// [pread_with](trait.Source.html#method.pread_with)); if the page size is a multiple of the element size, so that
+/// no element straddles two pages, accessing an element reads at most one page.
+///
+/// The cache is not locked while a page is read, so threads which hit the cache never wait on a
+/// read; threads which miss the same page at once may each read it, and the first one is cached.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::{PagedSource, ScrollTransducer, Endian};
+/// use rayon::prelude::*;
+/// use std::io::Cursor;
+///
+/// # fn main() {
+/// let bytes: Vec<u8> = (0..64u32).flat_map(|n| n.to_le_bytes().to_vec()).collect();
+/// let source = PagedSource::new(Cursor::new(bytes), 16).unwrap();
+/// let lt: ScrollTransducer<u32, _, _> = ScrollTransducer::parse_with(&source, 64, Endian::Little).unwrap();
+///
+/// assert_eq!(lt.get(9), Some(9));
+/// assert_eq!(source.page_reads(), 1);
+///
+/// let sum: u32 = lt.into_par_iter().sum();
+/// assert_eq!(sum, 2016);
+/// // every page is cached
+/// assert_eq!(source.resident_bytes(), 256);
+/// # }
+/// ```
+pub struct PagedSource<R> {
+    reader: Mutex<R>,
+    /// The most recently used pages, which are locked separately from the reader so that cache
+    /// hits never wait on a read
+    pages: Mutex<BlockCache>,
+    page_size: usize,
+    capacity: usize,
+    len: u64,
+    page_reads: AtomicUsize,
+}
+
+impl<R> ::std::fmt::Debug for PagedSource<R> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        let pages = self.pages.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        f.debug_struct("PagedSource")
+            .field("len", &self.len)
+            .field("page_size", &self.page_size)
+            .field("capacity", &self.capacity)
+            .field("memory_budget", &pages.budget())
+            .field("resident_bytes", &pages.resident())
+            .field("page_reads", &self.page_reads.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl<R: Read + Seek> PagedSource<R> {
+    /// Create a new paged source over `reader` with pages of `page_size` bytes, caching up to 64
+    /// pages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` is zero.
+    pub fn new(reader: R, page_size: usize) -> io::Result<Self> {
+        Self::with_capacity(reader, page_size, DEFAULT_CAPACITY)
+    }
+    /// Create a new paged source over `reader` with pages of `page_size` bytes, caching up to
+    /// `capacity` pages; when the cache is full, the least recently used page is evicted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` or `capacity` is zero.
+    pub fn with_capacity(mut reader: R, page_size: usize, capacity: usize) -> io::Result<Self> {
+        assert!(page_size != 0, "page size must be non-zero");
+        assert!(capacity != 0, "capacity must be non-zero");
+        let len = reader.seek(SeekFrom::End(0))?;
+        let pages = Mutex::new(BlockCache::new(capacity));
+        Ok(PagedSource { reader: Mutex::new(reader), pages, page_size, capacity, len, page_reads: AtomicUsize::new(0) })
+    }
+    /// Limit the cached pages to `budget` bytes in total, evicting the least recently used pages
+    /// first, so that walking a huge input source stays within a fixed amount of memory; if the
+    /// budget is smaller than a page, every access reads its pages again.
+    pub fn with_memory_budget(mut self, budget: usize) -> Self {
+        self.pages.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner()).set_budget(budget);
+        self
+    }
+    /// The size in bytes of the input source
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+    /// Whether the input source is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// The size in bytes of a page
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+    /// How many pages the input source spans, including a short last page
+    pub fn pages(&self) -> u64 {
+        if self.len == 0 { 0 } else { (self.len - 1) / self.page_size as u64 + 1 }
+    }
+    /// How many pages have been read from the underlying reader so far
+    pub fn page_reads(&self) -> usize {
+        self.page_reads.load(Ordering::Relaxed)
+    }
+    /// The maximum number of bytes of cached pages, which is unlimited unless set with
+    /// [with_memory_budget](#method.with_memory_budget)
+    pub fn memory_budget(&self) -> usize {
+        self.pages.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).budget()
+    }
+    /// The number of bytes of pages currently cached
+    pub fn resident_bytes(&self) -> usize {
+        self.pages.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).resident()
+    }
+    /// Returns the `n`th page, reading it if it isn't cached; the last page may be short.
+    ///
+    /// Returns an `OutOfBounds` error if the page is past the end of the input source.
+    pub fn page(&self, n: u64) -> Result<Arc<Vec<u8>>, TransducerError> {
+        let start = match n.checked_mul(self.page_size as u64) {
+            Some(start) if start < self.len => start,
+            _ => {
+                let clamp = |n: u64| ::std::cmp::min(n, usize::MAX as u64) as usize;
+                return Err(TransducerError::OutOfBounds{ idx: clamp(n), len: clamp(self.pages()) })
+            },
+        };
+        if let Some(page) = self.pages.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(n) {
+            return Ok(page)
+        }
+        let size = ::std::cmp::min(self.page_size as u64, self.len - start) as usize;
+        let mut page = vec![0; size];
+        {
+            let mut reader = self.reader.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            reader.seek(SeekFrom::Start(start))?;
+            reader.read_exact(&mut page)?;
+        }
+        self.page_reads.fetch_add(1, Ordering::Relaxed);
+        let page = Arc::new(page);
+        self.pages.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(n, page.clone());
+        Ok(page)
+    }
+    /// Call `f` with the `len` bytes starting at `offset`, returning an error if they are not all
+    /// in the input source.
+    ///
+    /// The bytes are borrowed straight out of the cache if they are in a single page, and are
+    /// otherwise copied out of every page they span.
+    pub fn with_bytes<T, F>(&self, offset: usize, len: usize, f: F) -> Result<T, TransducerError>
+        where F: FnOnce(&[u8]) -> T
+    {
+        let end = offset.checked_add(len)
+            .filter(|end| *end as u64 <= self.len)
+            .ok_or_else(|| overflow(offset, len, Source::len(self)))?;
+        if len == 0 {
+            return Ok(f(&[]))
+        }
+        let (first, last) = (offset / self.page_size, (end - 1) / self.page_size);
+        let start = offset % self.page_size;
+        if first == last {
+            let page = self.page(first as u64)?;
+            return Ok(f(&page[start..start + len]))
+        }
+        let mut bytes = Vec::with_capacity(len);
+        for n in first..=last {
+            let page = self.page(n as u64)?;
+            let from = if n == first { start } else { 0 };
+            let to = ::std::cmp::min(page.len(), len - bytes.len() + from);
+            bytes.extend_from_slice(&page[from..to]);
+        }
+        Ok(f(&bytes))
+    }
+}
+
+impl<R: Read + Seek> Source for PagedSource<R> {
+    fn len(&self) -> usize {
+        ::std::cmp::min(self.len, usize::MAX as u64) as usize
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self.with_bytes(offset, len, |bytes| Cow::Owned(bytes.to_vec()))
+    }
+}
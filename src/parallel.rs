@@ -0,0 +1,287 @@
+use std::cmp;
+use std::iter::Sum;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rayon::prelude::*;
+use rayon::iter::plumbing::{bridge_unindexed, UnindexedConsumer};
+use rayon::ThreadPool;
+
+use {LazyTransducer, ParIter, Producer};
+
+/// How many elements are transduced in parallel before being handed, in order, to the callback
+/// of [par_for_each_ordered](struct.LazyTransducer.html#method.par_for_each_ordered).
+const ORDERED_BATCH_SIZE: usize = 4096;
+
+/// A handle for cooperatively cancelling a parallel iteration from another thread, e.g. a UI or a
+/// timeout.
+///
+/// Clones of a token share the same cancellation state.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Create a new token which has not been cancelled
+    pub fn new() -> Self {
+        CancelToken::default()
+    }
+    /// Cancel every iteration using this token (or one of its clones); elements which are
+    /// already being transduced are finished, but no new ones are started.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed)
+    }
+    /// Whether this token has been cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A parallel iterator over the elements of a borrowed lazy transducer which stops early once
+/// its [CancelToken](struct.CancelToken.html) is cancelled.
+///
+/// Since the number of elements produced is not known in advance, this is an unindexed parallel
+/// iterator; consumers like `collect` return the (partial) results produced before cancellation.
+pub struct Cancellable<'b, 'a: 'b, Input: 'a + Copy, Output: 'a> {
+    lt: &'b LazyTransducer<'a, Input, Output>,
+    cancel: CancelToken,
+}
+
+impl<'b, 'a, Input: Sync + Send + Copy, Output: Send + Sync> ParallelIterator for Cancellable<'b, 'a, Input, Output> {
+    type Item = Output;
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+        where C: UnindexedConsumer<Self::Item> {
+        let producer = Producer { top: self.lt.count, lt: self.lt, current: 0, cancel: Some(self.cancel) };
+        bridge_unindexed(producer, consumer)
+    }
+}
+
+impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
+    where Input: 'a + Sync + Send + Copy,
+          Output: 'a + Send + Sync,
+{
+    /// Run `f` with a parallel iterator over this lazy transducer inside the given rayon `pool`,
+    /// instead of the global one, returning whatever `f` returns.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate lazy_transducer;
+    /// extern crate rayon;
+    /// use lazy_transducer::LazyTransducer;
+    /// use rayon::prelude::*;
+    /// use rayon::ThreadPoolBuilder;
+    ///
+    /// # fn main() {
+    /// let pool = ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+    /// let data = [1u64, 2, 3, 4];
+    /// let lt: LazyTransducer<&[u64], u64> = LazyTransducer::new(&data, 4, |input, idx| input[idx]);
+    /// let sum: u64 = lt.par_iter_in(&pool, |iter| iter.sum());
+    /// assert_eq!(sum, 10);
+    /// # }
+    /// ```
+    pub fn par_iter_in<'b, R, F>(&'b self, pool: &ThreadPool, f: F) -> R
+        where F: FnOnce(ParIter<'b, 'a, Input, Output>) -> R + Send,
+              R: Send,
+    {
+        pool.install(move || f(ParIter { lt: self }))
+    }
+    /// Transduce the elements in parallel, but invoke `f` with each index and element in ascending
+    /// index order, on the calling thread.
+    ///
+    /// Elements are transduced in batches and buffered until their turn comes, which is useful for
+    /// sinks like file writers that need a deterministic ordering.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let data = [3u32, 1, 4, 1, 5];
+    /// let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, 5, |input, idx| input[idx]);
+    /// let mut out = Vec::new();
+    /// lt.par_for_each_ordered(|idx, n| out.push((idx, n)));
+    /// assert_eq!(out, vec![(0, 3), (1, 1), (2, 4), (3, 1), (4, 5)]);
+    /// ```
+    pub fn par_for_each_ordered<F>(&self, mut f: F)
+        where F: FnMut(usize, Output)
+    {
+        let _: Result<(), ()> = self.try_par_for_each_ordered(|idx, output| {
+            f(idx, output);
+            Ok(())
+        });
+    }
+    /// Like [par_for_each_ordered](#method.par_for_each_ordered), but stops at the first error
+    /// returned by `f` and returns it; no further batches are transduced.
+    pub(crate) fn try_par_for_each_ordered<E, F>(&self, mut f: F) -> Result<(), E>
+        where F: FnMut(usize, Output) -> Result<(), E>
+    {
+        let mut batch = Vec::with_capacity(cmp::min(ORDERED_BATCH_SIZE, self.count));
+        let mut start = 0;
+        while start < self.count {
+            let end = cmp::min(start + ORDERED_BATCH_SIZE, self.count);
+            (start..end).into_par_iter().map(|idx| (self.transducer)(self.contents, idx)).collect_into_vec(&mut batch);
+            for (idx, output) in (start..end).zip(batch.drain(..)) {
+                f(idx, output)?;
+            }
+            start = end;
+        }
+        Ok(())
+    }
+    /// Collect every element into a `Vec` in parallel.
+    ///
+    /// The vector is allocated with exactly `len()` elements up front, and each element is written
+    /// directly into its slot, so no intermediate vectors are merged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let data = [1u16, 2, 3];
+    /// let lt: LazyTransducer<&[u16], u32> = LazyTransducer::new(&data, 3, |input, idx| input[idx] as u32);
+    /// let ns = lt.par_collect_vec();
+    /// assert_eq!(ns, vec![1, 2, 3]);
+    /// ```
+    pub fn par_collect_vec(&self) -> Vec<Output> {
+        let mut vec = Vec::with_capacity(self.count);
+        ParIter { lt: self }.collect_into_vec(&mut vec);
+        vec
+    }
+    /// Iterate over the elements in parallel until `cancel` is cancelled; see
+    /// [Cancellable](struct.Cancellable.html).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate lazy_transducer;
+    /// extern crate rayon;
+    /// use lazy_transducer::{LazyTransducer, CancelToken};
+    /// use rayon::prelude::*;
+    ///
+    /// # fn main() {
+    /// let data: Vec<u32> = (0..100_000).collect();
+    /// let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    /// let cancel = CancelToken::new();
+    /// let partial: Vec<u32> = lt.par_iter_cancellable(&cancel).map(|n| {
+    ///   if n == 500 { cancel.cancel(); }
+    ///   n
+    /// }).collect();
+    /// assert!(partial.len() < data.len());
+    /// # }
+    /// ```
+    pub fn par_iter_cancellable<'b>(&'b self, cancel: &CancelToken) -> Cancellable<'b, 'a, Input, Output> {
+        Cancellable { lt: self, cancel: cancel.clone() }
+    }
+    /// Sum every element in parallel.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let data = [1u32, 2, 3, 4];
+    /// let lt: LazyTransducer<&[u32], u64> = LazyTransducer::new(&data, 4, |input, idx| input[idx] as u64);
+    /// let total: u64 = lt.par_sum();
+    /// assert_eq!(total, 10);
+    /// ```
+    pub fn par_sum<S>(&self) -> S
+        where S: Send + Sum<Output> + Sum<S>
+    {
+        ParIter { lt: self }.sum()
+    }
+    /// Fold the elements in parallel: each thread folds its elements with `fold`, starting from a
+    /// value returned by `init`, and the partial results are then combined with `reduce`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let data = [3u8, 1, 4, 1, 5];
+    /// let lt: LazyTransducer<&[u8], u8> = LazyTransducer::new(&data, 5, |input, idx| input[idx]);
+    /// // count the odd elements
+    /// let odd = lt.par_fold(|| 0usize, |acc, n| acc + (n % 2) as usize, |a, b| a + b);
+    /// assert_eq!(odd, 4);
+    /// ```
+    pub fn par_fold<T, I, F, R>(&self, init: I, fold: F, reduce: R) -> T
+        where T: Send,
+              I: Fn() -> T + Sync + Send,
+              F: Fn(T, Output) -> T + Sync + Send,
+              R: Fn(T, T) -> T + Sync + Send,
+    {
+        ParIter { lt: self }.fold(&init, fold).reduce(&init, reduce)
+    }
+    /// Reduce the elements in parallel with `reduce`, returning `None` if there are no elements.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let data = [3u8, 1, 4, 1, 5];
+    /// let lt: LazyTransducer<&[u8], u8> = LazyTransducer::new(&data, 5, |input, idx| input[idx]);
+    /// assert_eq!(lt.par_reduce_with(|a, b| a.max(b)), Some(5));
+    /// ```
+    pub fn par_reduce_with<R>(&self, reduce: R) -> Option<Output>
+        where R: Fn(Output, Output) -> Output + Sync + Send
+    {
+        ParIter { lt: self }.reduce_with(reduce)
+    }
+    /// Search the elements in parallel, returning the element with the lowest index which matches
+    /// `predicate`; the search stops early once a match is found.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let targets = [0x1000u64, 0x2000, 0x3000, 0x2000];
+    /// let lt: LazyTransducer<&[u64], (usize, u64)> = LazyTransducer::new(&targets, 4, |input, idx| (idx, input[idx]));
+    /// assert_eq!(lt.par_find_first(|&(_, target)| target == 0x2000), Some((1, 0x2000)));
+    /// ```
+    pub fn par_find_first<P>(&self, predicate: P) -> Option<Output>
+        where P: Fn(&Output) -> bool + Sync + Send
+    {
+        ParIter { lt: self }.find_first(predicate)
+    }
+    /// Search the elements in parallel, returning the lowest index whose element matches
+    /// `predicate`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let targets = [0x1000u64, 0x2000, 0x3000, 0x2000];
+    /// let lt: LazyTransducer<&[u64], u64> = LazyTransducer::new(&targets, 4, |input, idx| input[idx]);
+    /// assert_eq!(lt.par_position(|target| *target == 0x2000), Some(1));
+    /// assert_eq!(lt.par_position(|target| *target == 0x4000), None);
+    /// ```
+    pub fn par_position<P>(&self, predicate: P) -> Option<usize>
+        where P: Fn(&Output) -> bool + Sync + Send
+    {
+        ParIter { lt: self }.position_first(|output| predicate(&output))
+    }
+    /// Call `f` on every element in parallel, returning an error it returns as soon as possible;
+    /// once any call fails, no new elements are transduced.
+    ///
+    /// Fallible transducers have the same method; see
+    /// [try_par_for_each](struct.TryLazyTransducer.html#method.try_par_for_each).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let data: Vec<u32> = (0..10_000).collect();
+    /// let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    /// let res = lt.try_par_for_each(|n| if n == 5000 { Err(n) } else { Ok(()) });
+    /// assert_eq!(res, Err(5000));
+    /// ```
+    pub fn try_par_for_each<E, F>(&self, f: F) -> Result<(), E>
+        where E: Send,
+              F: Fn(Output) -> Result<(), E> + Sync + Send
+    {
+        ParIter { lt: self }.try_for_each(f)
+    }
+}
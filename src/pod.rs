@@ -0,0 +1,111 @@
+use std::io;
+use std::mem;
+
+use bytemuck::{self, Pod};
+use rayon::prelude::*;
+use scroll::Endian;
+
+use {IntoIter, IntoParIter, LazyTransducer, TransducerError};
+
+/// A lazy transducer yielding `&T` views of the elements of a table of plain old data, without
+/// copying them, and without any `unsafe` in the caller's code.
+///
+/// Unlike a transducer which `transmute`s the bytes of each element, the table is validated once on
+/// creation: its size must be a whole number of elements, it must be aligned for `T`, and its
+/// endianness must be the target's, since a `&T` can't be byte swapped. Every bit pattern is a
+/// valid `T`, since it is [Pod](https://docs.rs/bytemuck/1/bytemuck/trait.Pod.html).
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{PodTransducer, Endian};
+///
+/// # fn main() {
+/// #[repr(align(4))]
+/// struct Aligned([u8; 12]);
+/// let bytes = Aligned([1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+///
+/// let lt = PodTransducer::<u32>::new(&bytes.0, Endian::Little).unwrap();
+/// assert_eq!(lt.len(), 3);
+/// assert_eq!(lt.into_iter().sum::<u32>(), 6);
+///
+/// assert!(PodTransducer::<u32>::new(&bytes.0[1..5], Endian::Little).is_err());
+/// assert!(PodTransducer::<u32>::new(&bytes.0[..10], Endian::Little).is_err());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PodTransducer<'a, T: 'a> {
+    table: &'a [T],
+}
+
+impl<'a, T> Clone for PodTransducer<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for PodTransducer<'a, T> {}
+
+impl<'a, T: Pod> PodTransducer<'a, T> {
+    /// Create a new lazy transducer over the elements of the `endian` table `bytes`, returning a
+    /// `Misaligned` error if it is not aligned for `T`, or an `Io` error with kind `InvalidData` if
+    /// it is not a whole number of elements, or `InvalidInput` if it is not in the target's
+    /// endianness.
+    pub fn new(bytes: &'a [u8], endian: Endian) -> Result<Self, TransducerError> {
+        if endian != Endian::default() && mem::size_of::<T>() > 1 {
+            let msg = format!("cannot view a {:?} endian table on a {:?} endian target", endian, Endian::default());
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg).into())
+        }
+        let align = mem::align_of::<T>();
+        if bytes.as_ptr() as usize & (align - 1) != 0 {
+            return Err(TransducerError::Misaligned{ offset: 0, align })
+        }
+        let size = mem::size_of::<T>();
+        if bytes.len().checked_rem(size) != Some(0) {
+            let msg = format!("{} bytes are not a whole number of {} byte elements", bytes.len(), size);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, msg).into())
+        }
+        Ok(PodTransducer { table: bytemuck::cast_slice(bytes) })
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+    /// Get an element out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of elements in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&'a T> {
+        self.table.get(idx)
+    }
+    /// The elements, as a slice
+    pub fn as_slice(&self) -> &'a [T] {
+        self.table
+    }
+    /// Returns a lazy transducer over the elements.
+    pub fn transducer(&self) -> LazyTransducer<'a, &'a [T], &'a T> {
+        LazyTransducer::new(self.table, self.table.len(), |table, idx| &table[idx])
+    }
+}
+
+impl<'a, T: Pod> IntoIterator for PodTransducer<'a, T> {
+    type Item = &'a T;
+    type IntoIter = IntoIter<'a, &'a [T], &'a T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.transducer().into_iter()
+    }
+}
+
+impl<'a, T: Pod + Sync> IntoParallelIterator for PodTransducer<'a, T> {
+    type Iter = IntoParIter<'a, &'a [T], &'a T>;
+    type Item = &'a T;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.transducer().into_par_iter()
+    }
+}
@@ -0,0 +1,131 @@
+use std::io;
+
+#[cfg(feature = "prost")]
+use prost::Message;
+
+#[cfg(feature = "prost")]
+use LazyTransducer;
+use {Leb128, TransducerError};
+
+/// A lazy transducer over a stream of length-delimited protobuf messages, i.e., every message is
+/// prefixed with its length as a varint, as written by `writeDelimitedTo` in Java or
+/// `encode_length_delimited` in prost.
+///
+/// A single scan at construction reads every length prefix, and records where the message lies in
+/// an index, so that afterwards, accessing a message is O(1). Messages are yielded as their
+/// encoded bytes, or, with the `prost` feature, decoded with [prost](https://docs.rs/prost).
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::LengthDelimited;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// // three messages, the second of which is empty
+/// let stream = [0x02, 0x08, 0x96, 0x00, 0x03, 0x12, 0x01, b'a'];
+/// let lt = LengthDelimited::new(&stream).unwrap();
+/// assert_eq!(lt.len(), 3);
+/// assert_eq!(lt.get(2), Some(&[0x12, 0x01, b'a'][..]));
+/// assert_eq!(lt.offset_of(2), Some(5));
+/// let total: usize = lt.into_par_iter().map(|message| message.len()).sum();
+/// assert_eq!(total, 5);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LengthDelimited<'a> {
+    bytes: &'a [u8],
+    /// The start and end offset of every message, excluding its length prefix
+    spans: Vec<(usize, usize)>,
+}
+
+impl<'a> LengthDelimited<'a> {
+    /// Create a new lazy transducer over the length-delimited messages in `bytes`, returning a
+    /// `Parse` error with the index and offset of the first message whose length prefix is too
+    /// long, or which is truncated.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        let mut spans = Vec::new();
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let idx = spans.len();
+            let malformed = |msg| TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) };
+            let prefix = bytes[offset..].iter().take(<u64 as Leb128>::MAX_LEN).position(|byte| byte & 0x80 == 0).ok_or_else(|| {
+                malformed(if bytes.len() - offset < <u64 as Leb128>::MAX_LEN { "truncated length prefix" } else { "length prefix is too long" })
+            })? + 1;
+            let start = offset + prefix;
+            let len = <u64 as Leb128>::decode(&bytes[offset..start]);
+            let end = (start as u64).checked_add(len).filter(|end| *end <= bytes.len() as u64).ok_or_else(|| malformed("truncated message"))?;
+            spans.push((start, end as usize));
+            offset = end as usize;
+        }
+        Ok(LengthDelimited { bytes, spans })
+    }
+    /// How many messages are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+    /// Whether this lazy transducer has no messages
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+    /// Returns the byte offset of the `idx`th message, after its length prefix, or `None` if the
+    /// index is greater than the number of messages.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.spans.get(idx).map(|&(start, _)| start)
+    }
+    /// Get the encoded bytes of a message out of the lazy transducer, returning `None` if the index
+    /// is greater than the number of messages in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&'a [u8]> {
+        self.spans.get(idx).map(|&(start, end)| &self.bytes[start..end])
+    }
+    /// Decode a message out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of messages in this lazy transducer, and a `Parse` error if it fails to decode.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// extern crate lazy_transducer;
+    /// extern crate prost;
+    /// use lazy_transducer::LengthDelimited;
+    /// use prost::Message;
+    ///
+    /// #[derive(Clone, PartialEq, Message)]
+    /// struct Point {
+    ///     #[prost(int32, tag = "1")]
+    ///     x: i32,
+    ///     #[prost(int32, tag = "2")]
+    ///     y: i32,
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut stream = Vec::new();
+    /// Point { x: 1, y: -2 }.encode_length_delimited(&mut stream).unwrap();
+    /// Point { x: 3, y: 4 }.encode_length_delimited(&mut stream).unwrap();
+    /// let lt = LengthDelimited::new(&stream).unwrap();
+    /// assert_eq!(lt.decode::<Point>(1).unwrap().unwrap(), Point { x: 3, y: 4 });
+    /// let xs: Vec<i32> = lt.messages::<Point>().into_iter().map(|point| point.unwrap().x).collect();
+    /// assert_eq!(xs, vec![1, 3]);
+    /// # }
+    /// ```
+    #[cfg(feature = "prost")]
+    pub fn decode<M: Message + Default>(&self, idx: usize) -> Option<Result<M, TransducerError>> {
+        self.get(idx).map(|message| {
+            M::decode(message).map_err(|err| TransducerError::Parse{ idx, offset: self.spans[idx].0, source: Box::new(err) })
+        })
+    }
+    /// Returns a lazy transducer over the decoded messages, borrowing this one.
+    #[cfg(feature = "prost")]
+    pub fn messages<'b, M: Message + Default>(&'b self) -> LazyTransducer<'b, &'b Self, Result<M, TransducerError>> {
+        LazyTransducer::new(self, self.len(), |lt, idx| lt.decode(idx).unwrap())
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the messages, borrowing this one.
+    impl['a] LengthDelimited<'a> => <'b> &'a [u8]
+    where []
+    parallel where []
+}
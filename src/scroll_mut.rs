@@ -0,0 +1,119 @@
+use std::error;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+
+use scroll::{self, ctx, Pread, Pwrite};
+use scroll::ctx::SizeWith;
+
+use {ScrollTransducer, TransducerError};
+use lazy_transducer::strided_size;
+
+/// A mutable view over a table of scroll-based elements, which can patch elements in place, e.g.,
+/// a relocation or a symbol, using the same index math as the [scroll transducer](type.ScrollTransducer.html)
+/// reading them.
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::{ScrollTransducerMut, Endian};
+///
+/// let mut bytes = vec![1, 0, 0, 0, 2, 0, 0, 0];
+/// {
+///   let mut lt: ScrollTransducerMut<u32> = ScrollTransducerMut::parse_with(&mut bytes, 2, Endian::Little).unwrap();
+///   lt.set(1, &0xdeadbeef).unwrap();
+///   assert_eq!(lt.get(1), Some(0xdeadbeef));
+///   assert!(lt.set(2, &0).is_err());
+/// }
+/// assert_eq!(bytes, vec![1, 0, 0, 0, 0xef, 0xbe, 0xad, 0xde]);
+/// ```
+pub struct ScrollTransducerMut<'a, Output, Ctx = scroll::Endian>
+    where Ctx: Copy,
+{
+    bytes: &'a mut [u8],
+    count: usize,
+    ctx: Ctx,
+    stride: usize,
+    _marker: PhantomData<Output>,
+}
+
+impl<'a, Output, Ctx: Copy + Debug> Debug for ScrollTransducerMut<'a, Output, Ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScrollTransducerMut")
+            .field("bytes", &self.bytes)
+            .field("count", &self.count)
+            .field("ctx", &self.ctx)
+            .field("stride", &self.stride)
+            .finish()
+    }
+}
+
+impl<'a, Output, Ctx> ScrollTransducerMut<'a, Output, Ctx>
+    where Ctx: Copy,
+          Output: SizeWith<Ctx>,
+{
+    /// Create a new mutable view over `count` elements in `bytes`, using the given context to
+    /// parse and write them; returns an error if the elements do not fit, like
+    /// [parse_with](type.ScrollTransducer.html#method.parse_with).
+    pub fn parse_with(bytes: &'a mut [u8], count: usize, ctx: Ctx) -> Result<Self, TransducerError> {
+        let stride = Output::size_with(&ctx);
+        Self::parse_strided(bytes, count, stride, ctx)
+    }
+    /// Create a new mutable view over `count` elements in `bytes` which are `stride` bytes apart,
+    /// like [parse_strided](type.ScrollTransducer.html#method.parse_strided).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn parse_strided(bytes: &'a mut [u8], count: usize, stride: usize, ctx: Ctx) -> Result<Self, TransducerError> {
+        assert!(stride != 0, "stride must be non-zero");
+        let sizeof_element = Output::size_with(&ctx);
+        if strided_size(count, stride, sizeof_element)? > bytes.len() {
+            return Err(TransducerError::ElementOverflow{ nelements: count, sizeof_element, stride, src_size: bytes.len() })
+        }
+        Ok(ScrollTransducerMut { bytes, count, ctx, stride, _marker: PhantomData })
+    }
+    /// How many elements are contained in this view
+    pub fn len(&self) -> usize {
+        self.count
+    }
+    /// Whether this view has no elements
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// Write `value` over the `idx`th element, returning an `OutOfBounds` error if the index is
+    /// greater than the number of elements, or a `Write` error if `value` could not be written.
+    pub fn set<E>(&mut self, idx: usize, value: &Output) -> Result<(), TransducerError>
+        where for<'b> &'b Output: ctx::TryIntoCtx<Ctx, Error = E>,
+              E: From<scroll::Error> + error::Error + Send + Sync + 'static,
+    {
+        if idx >= self.count {
+            return Err(TransducerError::OutOfBounds{ idx, len: self.count })
+        }
+        let offset = self.stride * idx;
+        self.bytes.pwrite_with(value, offset, self.ctx).map(|_| ())
+            .map_err(|err| TransducerError::Write{ idx, offset, source: Box::new(err) })
+    }
+}
+
+impl<'a, Output, Ctx, E> ScrollTransducerMut<'a, Output, Ctx>
+    where Ctx: Copy,
+          for<'b> Output: ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>,
+          E: From<scroll::Error> + Debug,
+{
+    /// Returns a scroll transducer reading the current elements, borrowing this view.
+    pub fn transducer<'b>(&'b self) -> ScrollTransducer<'b, Output, Ctx> {
+        ScrollTransducer::parse_strided(self.bytes, self.count, self.stride, self.ctx).expect("bounds checked on creation")
+    }
+    /// Get the `idx`th element, returning `None` if the index is greater than the number of
+    /// elements.
+    ///
+    /// The element is parsed in place, so interleaving `get` and `set` does not re-check the
+    /// bounds of the whole table on every call.
+    pub fn get(&self, idx: usize) -> Option<Output> {
+        if idx >= self.count {
+            return None
+        }
+        // we unwrap because we bounds checked on creation
+        Some(self.bytes.pread_with(self.stride * idx, self.ctx).unwrap())
+    }
+}
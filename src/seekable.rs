@@ -0,0 +1,273 @@
+use std::borrow::Cow;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use zstd::{bulk, stream, zstd_safe};
+
+use {Source, TransducerError};
+use cache::BlockCache;
+use source::overflow;
+use lazy_transducer::range_containing;
+
+/// The number of decompressed frames cached by [new](struct.ZstdSource.html#method.new)
+const DEFAULT_CAPACITY: usize = 4;
+/// The magic number of the skippable frame containing the seek table
+const SEEK_TABLE_MAGIC: u32 = 0x184D_2A5E;
+/// The magic number at the very end of the seek table
+const SEEKABLE_MAGIC: u32 = 0x8F92_EAB1;
+/// The size of the seek table footer: the number of frames, the descriptor, and the magic number
+const FOOTER_SIZE: usize = 9;
+
+fn invalid(msg: &str) -> TransducerError {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_owned()).into()
+}
+
+#[inline]
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+/// Returns the frame index of the seek table at the end of `bytes`, as the compressed and
+/// decompressed size of every frame, or `None` if there is no seek table
+fn seek_table(bytes: &[u8]) -> Option<Result<Vec<(usize, usize)>, TransducerError>> {
+    if bytes.len() < 8 + FOOTER_SIZE || u32_at(bytes, bytes.len() - 4) != SEEKABLE_MAGIC {
+        return None
+    }
+    let footer = &bytes[bytes.len() - FOOTER_SIZE..];
+    let nframes = u32_at(footer, 0) as usize;
+    let entry_size = if footer[4] & 0x80 != 0 { 12 } else { 8 };
+    let table = nframes.checked_mul(entry_size)
+        .and_then(|entries| entries.checked_add(8 + FOOTER_SIZE))
+        .filter(|table| *table <= bytes.len())
+        .map(|table| &bytes[bytes.len() - table..]);
+    let table = match table {
+        Some(table) if u32_at(table, 0) == SEEK_TABLE_MAGIC && u32_at(table, 4) as usize == table.len() - 8 => table,
+        _ => return Some(Err(invalid("malformed zstd seek table"))),
+    };
+    Some(Ok(table[8..table.len() - FOOTER_SIZE].chunks(entry_size).map(|entry| {
+        (u32_at(entry, 0) as usize, u32_at(entry, 4) as usize)
+    }).collect()))
+}
+
+/// Returns the compressed and decompressed size of every frame in `bytes`, by scanning them
+fn scan_frames(bytes: &[u8]) -> Result<Vec<(usize, usize)>, TransducerError> {
+    let mut frames = Vec::new();
+    let mut offset = 0;
+    while offset < bytes.len() {
+        let rest = &bytes[offset..];
+        let size = zstd_safe::find_frame_compressed_size(rest).map_err(|code| invalid(zstd_safe::get_error_name(code)))?;
+        let frame = &rest[..size];
+        let decompressed_size = match zstd_safe::get_frame_content_size(frame) {
+            Ok(Some(decompressed_size)) => decompressed_size as usize,
+            // the frame doesn't record its size, so we have to decompress it once to find out
+            Ok(None) => stream::decode_all(frame)?.len(),
+            Err(_) => return Err(invalid("malformed zstd frame")),
+        };
+        frames.push((size, decompressed_size));
+        offset += size;
+    }
+    Ok(frames)
+}
+
+/// A compressed input source over zstd frames, e.g., in the
+/// [seekable format](https://github.com/facebook/zstd/blob/dev/contrib/seekable_format/zstd_seekable_compression_format.md),
+/// where reading an element only decompresses the frame containing it, for O(frame) random access.
+///
+/// The frame index is loaded from the seek table if there is one, and is otherwise built by
+/// scanning the frame headers (decompressing any frames which don't record their size once).
+/// A small LRU cache of decompressed frames is kept, and elements may straddle frames.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// extern crate zstd;
+/// use lazy_transducer::{ZstdSource, ScrollTransducer, Endian};
+///
+/// # fn main() {
+/// // four frames, each containing 256 little endian u16s
+/// let (mut bytes, mut entries) = (Vec::new(), Vec::new());
+/// for frame in 0..4u16 {
+///     let data: Vec<u8> = (frame * 256..(frame + 1) * 256).flat_map(|n| n.to_le_bytes().to_vec()).collect();
+///     let compressed = zstd::bulk::compress(&data, 3).unwrap();
+///     entries.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+///     entries.extend_from_slice(&(data.len() as u32).to_le_bytes());
+///     bytes.extend(compressed);
+/// }
+///
+/// // the frame index is built by scanning the frames
+/// let source = ZstdSource::new(&bytes).unwrap();
+/// assert_eq!(source.frames(), 4);
+/// let lt: ScrollTransducer<u16, _, _> = ScrollTransducer::parse_with(&source, 1024, Endian::Little).unwrap();
+/// assert_eq!(lt.get(1000), Some(1000));
+/// assert_eq!(source.decompressions(), 1);
+///
+/// // or loaded from the seek table, a skippable frame at the end
+/// bytes.extend_from_slice(&0x184D2A5Eu32.to_le_bytes());
+/// bytes.extend_from_slice(&(entries.len() as u32 + 9).to_le_bytes());
+/// bytes.extend(entries);
+/// bytes.extend_from_slice(&4u32.to_le_bytes());
+/// bytes.push(0);
+/// bytes.extend_from_slice(&0x8F92EAB1u32.to_le_bytes());
+/// let source = ZstdSource::new(&bytes).unwrap();
+/// assert_eq!(source.len(), 2048);
+/// assert_eq!(source.frames(), 4);
+/// # }
+/// ```
+pub struct ZstdSource<'a> {
+    bytes: &'a [u8],
+    /// The compressed offset of each frame, plus the end of the last frame as a final entry
+    frames: Vec<usize>,
+    /// The decompressed offset of each frame, plus the total decompressed size as a final entry
+    starts: Vec<usize>,
+    /// The most recently used decompressed frames
+    cache: Mutex<BlockCache>,
+    capacity: usize,
+    decompressions: AtomicUsize,
+}
+
+impl<'a> ::std::fmt::Debug for ZstdSource<'a> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("ZstdSource")
+            .field("len", &self.len())
+            .field("frames", &self.frames())
+            .field("capacity", &self.capacity)
+            .field("memory_budget", &self.memory_budget())
+            .field("resident_bytes", &self.resident_bytes())
+            .field("decompressions", &self.decompressions())
+            .finish()
+    }
+}
+
+impl<'a> ZstdSource<'a> {
+    /// Create a new compressed source over the zstd frames in `bytes`, caching up to 4
+    /// decompressed frames, returning an error if the frames or seek table are malformed.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        Self::with_capacity(bytes, DEFAULT_CAPACITY)
+    }
+    /// Create a new compressed source over the zstd frames in `bytes`, caching up to `capacity`
+    /// decompressed frames; see [new](#method.new).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_capacity(bytes: &'a [u8], capacity: usize) -> Result<Self, TransducerError> {
+        assert!(capacity != 0, "capacity must be non-zero");
+        let sizes = match seek_table(bytes) {
+            Some(table) => table?,
+            None => scan_frames(bytes)?,
+        };
+        let mut frames = Vec::with_capacity(sizes.len() + 1);
+        let mut starts = Vec::with_capacity(sizes.len() + 1);
+        let (mut offset, mut start): (usize, usize) = (0, 0);
+        for (size, decompressed_size) in sizes {
+            frames.push(offset);
+            starts.push(start);
+            offset = offset.checked_add(size).filter(|offset| *offset <= bytes.len()).ok_or(invalid("zstd frame is past the end of the input source"))?;
+            start = start.checked_add(decompressed_size).ok_or(invalid("decompressed size overflows"))?;
+        }
+        frames.push(offset);
+        starts.push(start);
+        Ok(ZstdSource { bytes, frames, starts, cache: Mutex::new(BlockCache::new(capacity)), capacity, decompressions: AtomicUsize::new(0) })
+    }
+    /// Limit the cached decompressed frames to `budget` bytes in total, evicting the least
+    /// recently used frames first, so that walking a huge input source stays within a fixed amount
+    /// of memory; a frame larger than the budget is decompressed every time it is accessed.
+    pub fn with_memory_budget(mut self, budget: usize) -> Self {
+        self.cache.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner()).set_budget(budget);
+        self
+    }
+    /// The maximum number of bytes of cached decompressed frames, which is unlimited unless set
+    /// with [with_memory_budget](#method.with_memory_budget)
+    pub fn memory_budget(&self) -> usize {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).budget()
+    }
+    /// The number of bytes of decompressed frames currently cached
+    pub fn resident_bytes(&self) -> usize {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).resident()
+    }
+    /// The decompressed size in bytes of the input source
+    pub fn len(&self) -> usize {
+        self.starts[self.starts.len() - 1]
+    }
+    /// Whether the input source is empty when decompressed
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// How many frames the input source contains
+    pub fn frames(&self) -> usize {
+        self.frames.len() - 1
+    }
+    /// How many frames have been decompressed so far
+    pub fn decompressions(&self) -> usize {
+        self.decompressions.load(Ordering::Relaxed)
+    }
+    /// Returns the `n`th frame, decompressed, decompressing it if it isn't cached.
+    pub fn frame(&self, n: usize) -> Result<Arc<Vec<u8>>, TransducerError> {
+        if n >= self.frames() {
+            return Err(TransducerError::OutOfBounds{ idx: n, len: self.frames() })
+        }
+        let size = self.starts[n + 1] - self.starts[n];
+        if size == 0 {
+            // e.g., a skippable frame
+            return Ok(Arc::new(Vec::new()))
+        }
+        if let Some(frame) = self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(n as u64) {
+            return Ok(frame)
+        }
+        // the cache isn't locked while decompressing, so other frames can be accessed meanwhile
+        let frame = bulk::decompress(&self.bytes[self.frames[n]..self.frames[n + 1]], size)?;
+        if frame.len() != size {
+            return Err(invalid("frame size does not match the index"))
+        }
+        self.decompressions.fetch_add(1, Ordering::Relaxed);
+        let frame = Arc::new(frame);
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).insert(n as u64, frame.clone());
+        Ok(frame)
+    }
+    /// The frame containing the decompressed byte at `offset`, which must be in bounds
+    fn frame_of(&self, offset: usize) -> usize {
+        // this skips over any empty frames, e.g., skippable frames
+        range_containing(&self.starts, offset, |&start| start).expect("the offset is in bounds")
+    }
+    /// Call `f` with the `len` decompressed bytes starting at `offset`, returning an error if they
+    /// are not all in the input source.
+    ///
+    /// The bytes are borrowed straight out of the cache if they are in a single frame, and are
+    /// otherwise copied out of every frame they span.
+    pub fn with_bytes<T, F>(&self, offset: usize, len: usize, f: F) -> Result<T, TransducerError>
+        where F: FnOnce(&[u8]) -> T
+    {
+        let end = offset.checked_add(len)
+            .filter(|end| *end <= self.len())
+            .ok_or_else(|| overflow(offset, len, self.len()))?;
+        if len == 0 {
+            return Ok(f(&[]))
+        }
+        let (first, last) = (self.frame_of(offset), self.frame_of(end - 1));
+        if first == last {
+            let start = offset - self.starts[first];
+            let frame = self.frame(first)?;
+            return Ok(f(&frame[start..start + len]))
+        }
+        let mut bytes = Vec::with_capacity(len);
+        for n in first..=last {
+            let frame = self.frame(n)?;
+            let from = offset.saturating_sub(self.starts[n]);
+            let to = ::std::cmp::min(frame.len(), end - self.starts[n]);
+            bytes.extend_from_slice(&frame[from..to]);
+        }
+        Ok(f(&bytes))
+    }
+}
+
+impl<'a> Source for ZstdSource<'a> {
+    fn len(&self) -> usize {
+        ZstdSource::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self.with_bytes(offset, len, |bytes| Cow::Owned(bytes.to_vec()))
+    }
+}
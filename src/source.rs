@@ -0,0 +1,119 @@
+use std::borrow::Cow;
+#[cfg(feature = "scroll")]
+use std::error;
+
+#[cfg(feature = "scroll")]
+use scroll::{self, ctx, Pread};
+#[cfg(feature = "scroll")]
+use scroll::ctx::SizeWith;
+
+use TransducerError;
+
+/// A random access byte source, e.g., a byte slice, a memory map, a
+/// [PagedSource](struct.PagedSource.html), or a compressed source.
+///
+/// Implementing this for a new backend is all that's needed to parse lazy transducers out of it,
+/// e.g., a [ScrollTransducer](type.ScrollTransducer.html) is parsed out of any source, and
+/// [new_checked](struct.LazyTransducer.html#method.new_checked) bounds checks any source.
+///
+/// Sources are addressed by `usize` offsets, so on 32-bit hosts a source cannot be larger than
+/// 4 GiB; addressing larger sources by `u64` offsets is not supported yet.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{Source, ScrollTransducer, TransducerError, Endian};
+/// use std::borrow::Cow;
+///
+/// /// A source of `len` bytes which are all `byte`
+/// struct Repeat { byte: u8, len: usize }
+///
+/// impl Source for Repeat {
+///     fn len(&self) -> usize {
+///         self.len
+///     }
+///     fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+///         if offset + len > self.len {
+///             return Err(TransducerError::OutOfBounds{ idx: offset + len, len: self.len })
+///         }
+///         Ok(Cow::Owned(vec![self.byte; len]))
+///     }
+/// }
+///
+/// # fn main() {
+/// let source = Repeat { byte: 1, len: 8 };
+/// let lt: ScrollTransducer<u32, _, _> = ScrollTransducer::parse_with(&source, 2, Endian::Big).unwrap();
+/// assert_eq!(lt.get(1), Some(0x01010101));
+/// assert_eq!(source.pread_with::<u32, _, _>(0, 4, Endian::Big).unwrap(), 0x01010101);
+/// assert!(ScrollTransducer::<u32, _, _>::parse_with(&source, 3, Endian::Big).is_err());
+/// # }
+/// ```
+pub trait Source {
+    /// The size in bytes of the source
+    fn len(&self) -> usize;
+    /// Whether the source is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the `len` bytes starting at `offset`, or an error if they are not all in the source;
+    /// the bytes are borrowed if the source can do so, and are otherwise copied.
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError>;
+    /// Parse the `idx`th element, which is at `offset`, with scroll, returning an error if its
+    /// bytes are not all in the source, or a `Parse` error with `idx` and `offset` if it fails to
+    /// parse.
+    #[cfg(feature = "scroll")]
+    fn pread_with<Output, Ctx, E>(&self, idx: usize, offset: usize, ctx: Ctx) -> Result<Output, TransducerError>
+        where Ctx: Copy,
+              Output: for<'b> ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>,
+              E: From<scroll::Error> + error::Error + Send + Sync + 'static,
+    {
+        let bytes = self.read_bytes(offset, Output::size_with(&ctx))?;
+        Pread::pread_with(&*bytes, 0, ctx).map_err(|err| TransducerError::Parse{ idx, offset, source: Box::new(err) })
+    }
+}
+
+/// The error for reading `len` bytes at `offset` of a source of `src_size` bytes
+pub(crate) fn overflow(offset: usize, len: usize, src_size: usize) -> TransducerError {
+    TransducerError::ElementOverflow{ nelements: 1, sizeof_element: len, stride: len, src_size: src_size.saturating_sub(offset) }
+}
+
+impl Source for [u8] {
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        offset.checked_add(len)
+            .and_then(|end| self.get(offset..end))
+            .map(Cow::Borrowed)
+            .ok_or_else(|| overflow(offset, len, <[u8]>::len(self)))
+    }
+}
+
+impl Source for Vec<u8> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self.as_slice().read_bytes(offset, len)
+    }
+}
+
+impl<const N: usize> Source for [u8; N] {
+    fn len(&self) -> usize {
+        N
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self[..].read_bytes(offset, len)
+    }
+}
+
+impl<S: Source + ?Sized> Source for &S {
+    fn len(&self) -> usize {
+        Source::len(*self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        (**self).read_bytes(offset, len)
+    }
+}
@@ -0,0 +1,134 @@
+use std::borrow::Cow;
+use std::cmp;
+use std::io;
+
+use {Source, TransducerError};
+use source::overflow;
+
+/// An input source over a sparse address space, e.g., the memory of a core dump or the extents of a
+/// sparse file, made of `(offset, bytes)` segments with unmapped holes between them.
+///
+/// Reading bytes in a hole is a `Hole` error by default, or returns a fill byte if one is set with
+/// [with_fill](#method.with_fill), so that transducers can index across the whole address space.
+/// Bytes spanning adjacent segments, or a segment and a filled hole, are stitched together.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{SparseSource, ScrollTransducer, Endian};
+///
+/// # fn main() {
+/// let (text, data) = ([1u8, 0, 2, 0], [5u8, 0]);
+/// let source = SparseSource::new(12, vec![(0, &text[..]), (8, &data[..])]).unwrap();
+/// assert!(source.read(4, 2).is_err());
+///
+/// let source = source.with_fill(0);
+/// let lt: ScrollTransducer<u16, _, _> = ScrollTransducer::parse_with(&source, 6, Endian::Little).unwrap();
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![1, 2, 0, 0, 5, 0]);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseSource<'a> {
+    /// The segments, sorted by offset
+    segments: Vec<(usize, &'a [u8])>,
+    len: usize,
+    fill: Option<u8>,
+}
+
+impl<'a> SparseSource<'a> {
+    /// Create a new sparse source over an address space of `len` bytes, with the bytes of each
+    /// `(offset, bytes)` segment at its offset, returning an error if the segments overlap or
+    /// extend past `len`.
+    pub fn new(len: usize, mut segments: Vec<(usize, &'a [u8])>) -> Result<Self, TransducerError> {
+        segments.sort_by_key(|&(offset, _)| offset);
+        let mut mapped = 0;
+        for &(offset, bytes) in &segments {
+            if offset < mapped {
+                let msg = format!("segment at offset {:#x} overlaps the previous segment", offset);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, msg).into())
+            }
+            mapped = offset.checked_add(bytes.len())
+                .filter(|end| *end <= len)
+                .ok_or_else(|| overflow(offset, bytes.len(), len))?;
+        }
+        Ok(SparseSource { segments, len, fill: None })
+    }
+    /// Read every byte in a hole as `fill`, rather than returning a `Hole` error.
+    pub fn with_fill(mut self, fill: u8) -> Self {
+        self.fill = Some(fill);
+        self
+    }
+    /// The size in bytes of the address space
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Whether the address space is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// The `(offset, bytes)` segments, sorted by offset
+    pub fn segments(&self) -> &[(usize, &'a [u8])] {
+        &self.segments
+    }
+    /// Whether the byte at `offset` is in a segment
+    pub fn is_mapped(&self, offset: usize) -> bool {
+        self.segments.get(self.segment_after(offset)).is_some_and(|&(start, _)| start <= offset)
+    }
+    /// The index of the first segment which ends after `offset`
+    fn segment_after(&self, offset: usize) -> usize {
+        self.segments.partition_point(|&(start, bytes)| start + bytes.len() <= offset)
+    }
+    /// Returns the `len` bytes starting at `offset`, returning an error if they are not all in the
+    /// address space, or any of them are in a hole and there is no fill byte.
+    ///
+    /// The bytes are borrowed if they are in a single segment, and are otherwise copied.
+    pub fn read(&self, offset: usize, len: usize) -> Result<Cow<'a, [u8]>, TransducerError> {
+        let end = offset.checked_add(len)
+            .filter(|end| *end <= self.len)
+            .ok_or_else(|| overflow(offset, len, self.len))?;
+        if len == 0 {
+            return Ok(Cow::Borrowed(&[]))
+        }
+        let first = self.segment_after(offset);
+        if let Some(&(start, bytes)) = self.segments.get(first) {
+            if start <= offset && end <= start + bytes.len() {
+                return Ok(Cow::Borrowed(&bytes[offset - start..end - start]))
+            }
+        }
+        let mut read = Vec::with_capacity(len);
+        for n in first..=self.segments.len() {
+            let pos = offset + read.len();
+            if pos == end {
+                break
+            }
+            let (start, bytes) = self.segments.get(n).cloned().unwrap_or((self.len, &[]));
+            if pos < start {
+                // the hole runs from the end of the previous segment to the start of this one
+                let fill = self.fill.ok_or_else(|| {
+                    let hole = self.segments[..n].last().map_or(0, |&(start, bytes)| start + bytes.len());
+                    TransducerError::Hole{ offset: hole, len: start - hole }
+                })?;
+                let gap = cmp::min(start, end) - pos;
+                read.resize(read.len() + gap, fill);
+            }
+            if start >= end {
+                break
+            }
+            let from = offset + read.len() - start;
+            let to = cmp::min(bytes.len(), end - start);
+            read.extend_from_slice(&bytes[from..to]);
+        }
+        Ok(Cow::Owned(read))
+    }
+}
+
+impl<'a> Source for SparseSource<'a> {
+    fn len(&self) -> usize {
+        SparseSource::len(self)
+    }
+    fn read_bytes<'s>(&'s self, offset: usize, len: usize) -> Result<Cow<'s, [u8]>, TransducerError> {
+        self.read(offset, len)
+    }
+}
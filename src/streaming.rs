@@ -0,0 +1,163 @@
+use std::fmt::Debug;
+use std::io::{self, Read};
+
+#[cfg(feature = "scroll")]
+use scroll::{self, ctx};
+#[cfg(feature = "scroll")]
+use scroll::ctx::SizeWith;
+
+use {TransducerError, TryLazyTransducer};
+#[cfg(feature = "scroll")]
+use {ScrollTransducer, Source};
+
+/// How many bytes are read from the stream at a time
+const CHUNK_SIZE: usize = 8192;
+
+/// The fallible transducer of a [StreamingTransducer](struct.StreamingTransducer.html), which
+/// receives the `(bytes, ctx)` pair of the buffered bytes
+pub type StreamingTransducerFn<Output, Ctx> = fn((&[u8], Ctx), usize) -> Result<Output, TransducerError>;
+
+/// A lazy transducer over a stream, e.g., a pipe or a socket, whose total number of elements isn't
+/// known up front.
+///
+/// Incoming bytes are buffered by [fill](#method.fill) (or handed over with [push](#method.push)),
+/// and the number of [available](#method.available_len) elements grows as they arrive; getting an
+/// element which hasn't fully arrived yet is a `NotYetAvailable` error, and getting one past the
+/// end of a finished stream is an `OutOfBounds` error.
+///
+/// The transducer is fallible, so that an element which arrived but is malformed is an error too,
+/// e.g., a `Parse` error. It receives the `(bytes, ctx)` pair of the buffered bytes, like an
+/// [OwnedTransducer](struct.OwnedTransducer.html); use `()` if you don't need a context.
+///
+/// # Example
+///
+#[cfg_attr(feature = "scroll", doc = "```rust")]
+#[cfg_attr(not(feature = "scroll"), doc = "```rust,ignore")]
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{ScrollTransducer, StreamingTransducer, Endian};
+///
+/// # fn main() {
+/// let mut lt: StreamingTransducer<_, u16, _> = ScrollTransducer::from_stream(&[1u8, 0, 2][..], Endian::Little);
+/// assert_eq!(lt.available_len(), 0);
+/// assert!(lt.get(0).is_err());
+///
+/// while lt.fill().unwrap() != 0 {}
+/// assert!(lt.is_finished());
+/// // the trailing byte of the second element never arrived
+/// assert_eq!(lt.available_len(), 1);
+/// assert_eq!(lt.get(0).unwrap(), 1);
+///
+/// lt.push(&[0, 3, 0]);
+/// assert_eq!(lt.transducer().into_iter().collect::<Result<Vec<_>, _>>().unwrap(), vec![1, 2, 3]);
+/// # }
+/// ```
+pub struct StreamingTransducer<R, Output, Ctx = ()> {
+    reader: R,
+    buffer: Vec<u8>,
+    ctx: Ctx,
+    stride: usize,
+    finished: bool,
+    transducer: StreamingTransducerFn<Output, Ctx>,
+}
+
+impl<R, Output, Ctx: Copy> StreamingTransducer<R, Output, Ctx> {
+    /// Create a new lazy transducer over the elements of `reader`, which are `stride` bytes apart;
+    /// nothing is read until [fill](#method.fill) is called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stride` is zero.
+    pub fn new(reader: R, stride: usize, ctx: Ctx, transducer: StreamingTransducerFn<Output, Ctx>) -> Self {
+        assert!(stride != 0, "stride must be non-zero");
+        StreamingTransducer { reader, buffer: Vec::new(), ctx, stride, finished: false, transducer }
+    }
+    /// How many elements have fully arrived so far
+    pub fn available_len(&self) -> usize {
+        self.buffer.len() / self.stride
+    }
+    /// Whether the stream has ended, i.e., no more elements will arrive from the reader
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+    /// The bytes which have arrived so far
+    pub fn bytes(&self) -> &[u8] {
+        &self.buffer
+    }
+    /// Append `bytes` to the buffered bytes, e.g., when the caller reads the stream itself.
+    pub fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+    /// Get the `idx`th element, returning a `NotYetAvailable` error if it hasn't fully arrived yet,
+    /// an `OutOfBounds` error if the stream has ended before it, or the error of the transducer.
+    pub fn get(&self, idx: usize) -> Result<Output, TransducerError> {
+        let available = self.available_len();
+        if idx < available {
+            (self.transducer)((&self.buffer, self.ctx), idx)
+        } else if self.finished {
+            Err(TransducerError::OutOfBounds{ idx, len: available })
+        } else {
+            Err(TransducerError::NotYetAvailable{ idx, available })
+        }
+    }
+    /// Returns a fallible lazy transducer over the elements which have arrived so far, borrowing the buffer.
+    pub fn transducer<'b>(&'b self) -> TryLazyTransducer<'b, (&'b [u8], Ctx), Output, TransducerError> {
+        TryLazyTransducer::new((&self.buffer, self.ctx), self.available_len(), self.transducer)
+    }
+}
+
+impl<R: Read, Output, Ctx: Copy> StreamingTransducer<R, Output, Ctx> {
+    /// Read the next chunk of bytes from the stream, blocking until some arrive, and return how
+    /// many did; zero means the stream has ended.
+    pub fn fill(&mut self) -> Result<usize, TransducerError> {
+        if self.finished {
+            return Ok(0)
+        }
+        let len = self.buffer.len();
+        self.buffer.resize(len + CHUNK_SIZE, 0);
+        loop {
+            match self.reader.read(&mut self.buffer[len..]) {
+                Ok(read) => {
+                    self.buffer.truncate(len + read);
+                    self.finished = read == 0;
+                    return Ok(read)
+                },
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.buffer.truncate(len);
+                    return Err(err.into())
+                },
+            }
+        }
+    }
+}
+
+impl<R, Output, Ctx: Debug> Debug for StreamingTransducer<R, Output, Ctx> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("StreamingTransducer")
+            .field("buffered", &self.buffer.len())
+            .field("ctx", &self.ctx)
+            .field("stride", &self.stride)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+#[cfg(feature = "scroll")]
+impl<'a, Output, Ctx, E> ScrollTransducer<'a, Output, Ctx>
+    where Ctx: Copy,
+          Output: for<'b> ctx::TryFromCtx<'b, Ctx, Error = E> + SizeWith<Ctx>,
+          E: From<scroll::Error> + Debug,
+{
+    /// Create a new scroll-based lazy transducer over the elements of the stream `reader`, whose
+    /// number isn't known up front; see [StreamingTransducer](struct.StreamingTransducer.html).
+    ///
+    /// An element which fails to parse is a `Parse` error, with its index and offset.
+    pub fn from_stream<R>(reader: R, ctx: Ctx) -> StreamingTransducer<R, Output, Ctx>
+        where E: ::std::error::Error + Send + Sync + 'static,
+    {
+        // only elements which have fully arrived are transduced, so reading their bytes can't fail
+        StreamingTransducer::new(reader, Output::size_with(&ctx), ctx, |(bytes, ctx), idx| {
+            Source::pread_with(bytes, idx, idx * Output::size_with(&ctx), ctx)
+        })
+    }
+}
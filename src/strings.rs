@@ -0,0 +1,118 @@
+use std::str;
+
+use scroll::{self, Endian, Pread};
+use scroll::ctx::StrCtx;
+
+use TransducerError;
+use lazy_transducer::capacity_for;
+
+/// The integer type (and endianness) of the byte length which precedes every string in a table of
+/// length-prefixed strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthPrefix {
+    /// A `u8` byte length
+    U8,
+    /// A `u16` byte length
+    U16(Endian),
+    /// A `u32` byte length
+    U32(Endian),
+}
+
+/// A lazy transducer over a table of strings laid out back to back, which are either
+/// delimiter-terminated or fixed-length (parsed with a scroll
+/// [StrCtx](https://docs.rs/scroll/0.12/scroll/ctx/enum.StrCtx.html)), or length-prefixed.
+///
+/// Since the strings vary in size, a single pass at construction validates every string and
+/// records where it lies in an index, so that afterwards, accessing a string is O(1); see also
+/// [VariableTransducer](struct.VariableTransducer.html).
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate scroll;
+/// use lazy_transducer::{StringTransducer, LengthPrefix, Endian};
+/// use scroll::ctx::StrCtx;
+///
+/// # fn main() {
+/// let terminated = b"libc.so.6\0libm.so.6\0";
+/// let lt = StringTransducer::parse(terminated, 2, StrCtx::Delimiter(0)).unwrap();
+/// assert_eq!(lt.get(1), Some("libm.so.6"));
+///
+/// let prefixed = b"\x02\x00hi\x05\x00there";
+/// let lt = StringTransducer::parse_prefixed(prefixed, 2, LengthPrefix::U16(Endian::Little)).unwrap();
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec!["hi", "there"]);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StringTransducer<'a> {
+    bytes: &'a [u8],
+    /// The start and end offset of every string, excluding any delimiter or prefix
+    spans: Vec<(usize, usize)>,
+}
+
+impl<'a> StringTransducer<'a> {
+    fn index<F>(bytes: &'a [u8], count: usize, mut parse: F) -> Result<Self, TransducerError>
+        where F: FnMut(&mut usize) -> Result<(usize, &'a str), scroll::Error>
+    {
+        let mut spans = Vec::with_capacity(capacity_for(count, bytes.len(), 1));
+        let mut offset = 0;
+        for idx in 0..count {
+            let element_offset = offset;
+            let (start, string) = parse(&mut offset).map_err(|err| TransducerError::Parse{ idx, offset: element_offset, source: Box::new(err) })?;
+            spans.push((start, start + string.len()));
+        }
+        Ok(StringTransducer { bytes, spans })
+    }
+    /// Create a new lazy transducer over `count` strings, which are delimiter-terminated or
+    /// fixed-length according to `ctx`, returning a `Parse` error with the index and offset of
+    /// the first string which is not valid UTF-8 or does not fit.
+    pub fn parse(bytes: &'a [u8], count: usize, ctx: StrCtx) -> Result<Self, TransducerError> {
+        Self::index(bytes, count, |offset| {
+            let start = *offset;
+            bytes.gread_with::<&str>(offset, ctx).map(|string| (start, string))
+        })
+    }
+    /// Create a new lazy transducer over `count` strings, each of which is preceded by its byte
+    /// length, returning a `Parse` error with the index and offset of the first string which is
+    /// not valid UTF-8 or does not fit.
+    pub fn parse_prefixed(bytes: &'a [u8], count: usize, prefix: LengthPrefix) -> Result<Self, TransducerError> {
+        Self::index(bytes, count, |offset| {
+            let len = match prefix {
+                LengthPrefix::U8 => bytes.gread::<u8>(offset)? as usize,
+                LengthPrefix::U16(endian) => bytes.gread_with::<u16>(offset, endian)? as usize,
+                LengthPrefix::U32(endian) => bytes.gread_with::<u32>(offset, endian)? as usize,
+            };
+            let start = *offset;
+            bytes.gread_with::<&str>(offset, StrCtx::Length(len)).map(|string| (start, string))
+        })
+    }
+    /// How many strings are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+    /// Whether this lazy transducer has no strings
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+    /// Returns the byte offset of the `idx`th string, excluding any length prefix, or `None` if the
+    /// index is greater than the number of strings.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.spans.get(idx).map(|&(start, _)| start)
+    }
+    /// Get a string out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of strings in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&'a str> {
+        // every string was validated when building the index, so this only fails if the index is
+        // out of bounds
+        self.spans.get(idx).and_then(|&(start, end)| str::from_utf8(&self.bytes[start..end]).ok())
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the strings, borrowing this one.
+    impl['a] StringTransducer<'a> => <'b> &'a str
+    where []
+    parallel where []
+}
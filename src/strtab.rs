@@ -0,0 +1,96 @@
+use std::io;
+use std::str;
+
+use TransducerError;
+use lazy_transducer::range_containing;
+
+/// A lazy transducer over a string table, i.e., a blob of NUL-terminated strings, such as an ELF
+/// `.strtab` or `.dynstr` section, whose strings can be accessed both by index and by byte offset.
+///
+/// A single pass at construction validates every string and records where it lies in an index, so
+/// that afterwards, accessing a string by index is O(1), and by byte offset is O(log n).
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::StrTableTransducer;
+///
+/// let strtab = b"\0main\0.rela.text\0";
+/// let lt = StrTableTransducer::new(strtab).unwrap();
+/// assert_eq!(lt.len(), 3);
+/// assert_eq!(lt.get(1), Some("main"));
+/// // ELF shares suffixes, so a name may start in the middle of a string
+/// assert_eq!(lt.get_at_offset(11), Some(".text"));
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec!["", "main", ".rela.text"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StrTableTransducer<'a> {
+    bytes: &'a [u8],
+    /// The start and end offset of every string, excluding its terminator
+    spans: Vec<(usize, usize)>,
+}
+
+impl<'a> StrTableTransducer<'a> {
+    /// Create a new lazy transducer over the NUL-terminated strings in `bytes`, returning a `Parse`
+    /// error with the index and offset of the first string which is not valid UTF-8, or is not
+    /// terminated.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        let mut spans = Vec::new();
+        let mut start = 0;
+        while start < bytes.len() {
+            let idx = spans.len();
+            let end = bytes[start..].iter().position(|byte| *byte == 0).map(|len| start + len).ok_or_else(|| {
+                let err = io::Error::new(io::ErrorKind::InvalidData, "unterminated string");
+                TransducerError::Parse{ idx, offset: start, source: Box::new(err) }
+            })?;
+            str::from_utf8(&bytes[start..end]).map_err(|err| TransducerError::Parse{ idx, offset: start, source: Box::new(err) })?;
+            spans.push((start, end));
+            start = end + 1;
+        }
+        Ok(StrTableTransducer { bytes, spans })
+    }
+    /// How many strings are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+    /// Whether this lazy transducer has no strings
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+    /// Returns the byte offset of the `idx`th string, or `None` if the index is greater than the
+    /// number of strings.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.spans.get(idx).map(|&(start, _)| start)
+    }
+    /// Returns the index of the string starting at byte `offset`, or `None` if no string starts
+    /// there.
+    pub fn index_of(&self, offset: usize) -> Option<usize> {
+        self.spans.binary_search_by_key(&offset, |&(start, _)| start).ok()
+    }
+    /// Get a string out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of strings in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&'a str> {
+        // every string was validated when building the index, so this only fails if the index is
+        // out of bounds
+        self.spans.get(idx).and_then(|&(start, end)| str::from_utf8(&self.bytes[start..end]).ok())
+    }
+    /// Get the string at byte `offset` out of the lazy transducer, i.e., the bytes up to the next
+    /// NUL, which may be the tail of a longer string; returns `None` if the offset is past the end
+    /// of the table, or is not on a character boundary.
+    pub fn get_at_offset(&self, offset: usize) -> Option<&'a str> {
+        let idx = range_containing(&self.spans, offset, |&(start, _)| start)?;
+        let (start, end) = self.spans[idx];
+        if offset > end {
+            return None
+        }
+        self.get(idx)?.get(offset - start..)
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the strings, borrowing this one.
+    impl['a] StrTableTransducer<'a> => <'b> &'a str
+    where []
+    parallel where []
+}
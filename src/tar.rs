@@ -0,0 +1,225 @@
+use std::borrow::Cow;
+use std::io;
+use std::ops::Range;
+use std::str;
+
+use TransducerError;
+
+/// The size of a tar header, and the granularity of its payloads
+const BLOCK: usize = 512;
+
+/// Parses a numeric header field, which is either octal ASCII padded with NULs or spaces, or, as
+/// GNU tar writes large numbers, big endian base-256 flagged by the high bit of its first byte
+fn number(field: &[u8]) -> Result<u64, &'static str> {
+    const TOO_LARGE: &str = "number is too large";
+    if field.first().is_some_and(|byte| byte & 0x80 != 0) {
+        let first = (field[0] & 0x7f) as u64;
+        return field[1..].iter().try_fold(first, |n, byte| n.checked_mul(256).map(|n| n | *byte as u64).ok_or(TOO_LARGE))
+    }
+    let start = field.iter().position(|byte| *byte != b' ' && *byte != 0).unwrap_or(field.len());
+    let end = field[start..].iter().position(|byte| *byte == b' ' || *byte == 0).map_or(field.len(), |len| start + len);
+    field[start..end].iter().try_fold(0u64, |n, byte| match *byte {
+        b'0'..=b'7' => n.checked_mul(8).map(|n| n | (byte - b'0') as u64).ok_or(TOO_LARGE),
+        _ => Err("invalid octal number"),
+    })
+}
+
+/// Returns `field` up to its first NUL
+fn cstr(field: &[u8]) -> &[u8] {
+    field.iter().position(|byte| *byte == 0).map_or(field, |len| &field[..len])
+}
+
+/// Returns the span of the value of the `path` record of the pax extended header at `payload`
+fn pax_path(bytes: &[u8], payload: usize, size: usize) -> Result<Option<(usize, usize)>, &'static str> {
+    const MALFORMED: &str = "malformed pax header";
+    let (mut offset, end) = (payload, payload + size);
+    let mut path = None;
+    // every record is "<length> <key>=<value>\n", where the length counts the whole record
+    while offset < end {
+        let space = bytes[offset..end].iter().position(|byte| *byte == b' ').ok_or(MALFORMED)?;
+        let len: usize = str::from_utf8(&bytes[offset..offset + space]).ok().and_then(|len| len.parse().ok()).ok_or(MALFORMED)?;
+        if len <= space + 1 || len > end - offset {
+            return Err(MALFORMED)
+        }
+        let record = &bytes[offset + space + 1..offset + len];
+        let eq = record.iter().position(|byte| *byte == b'=').ok_or(MALFORMED)?;
+        if &record[..eq] == b"path" && record.last() == Some(&b'\n') {
+            let value = offset + space + 1 + eq + 1;
+            path = Some((value, offset + len - 1));
+        }
+        offset += len;
+    }
+    Ok(path)
+}
+
+/// The header and payload of an entry of a [TarTransducer](struct.TarTransducer.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TarEntry<'a> {
+    /// The path of the entry, which is only owned if it is joined from the ustar prefix and name
+    pub path: Cow<'a, [u8]>,
+    /// The permission bits
+    pub mode: u32,
+    /// The id of the owner
+    pub uid: u64,
+    /// The id of the group
+    pub gid: u64,
+    /// The modification time, in seconds since the Unix epoch
+    pub mtime: u64,
+    /// The type flag, e.g., `b'0'` for a regular file, `b'5'` for a directory, or `b'2'` for a
+    /// symbolic link
+    pub kind: u8,
+    /// The target of a link
+    pub link_name: &'a [u8],
+    /// The contents of the entry
+    pub data: &'a [u8],
+}
+
+/// An indexed entry of a [TarTransducer](struct.TarTransducer.html)
+#[derive(Debug, Clone)]
+struct Entry {
+    /// The offset of the header
+    header: usize,
+    /// The span of a path from a preceding GNU long name or pax header, which overrides the
+    /// header's
+    path: Option<(usize, usize)>,
+}
+
+/// A lazy transducer over the entries of a tar archive, which parses the header of an entry only
+/// when it is accessed, so that an archive can be listed or extracted lazily, and in parallel.
+///
+/// A single pass at construction verifies the checksum of every 512-byte header, skips over the
+/// payloads, and records where every entry lies in an index, so that afterwards, accessing an
+/// entry is O(1). Both ustar and GNU archives are supported, including long paths from GNU long
+/// name and pax extended headers, which are not entries themselves.
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::TarTransducer;
+///
+/// // a header for a 5 byte file, with its checksum
+/// let mut tar = vec![0; 1536];
+/// tar[..9].copy_from_slice(b"hello.txt");
+/// tar[100..107].copy_from_slice(b"0000644");
+/// tar[124..135].copy_from_slice(b"00000000005");
+/// tar[156] = b'0';
+/// tar[148..156].copy_from_slice(b"        ");
+/// let checksum: u32 = tar[..512].iter().map(|byte| *byte as u32).sum();
+/// tar[148..155].copy_from_slice(format!("{:06o}\0", checksum).as_bytes());
+/// tar[512..517].copy_from_slice(b"hello");
+///
+/// let lt = TarTransducer::new(&tar).unwrap();
+/// assert_eq!(lt.len(), 1);
+/// let entry = lt.get(0).unwrap().unwrap();
+/// assert_eq!(&*entry.path, b"hello.txt");
+/// assert_eq!(entry.mode, 0o644);
+/// assert_eq!(entry.data, b"hello");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TarTransducer<'a> {
+    bytes: &'a [u8],
+    entries: Vec<Entry>,
+}
+
+impl<'a> TarTransducer<'a> {
+    /// Create a new lazy transducer over the entries of the tar archive in `bytes`, up to its end
+    /// of archive marker, returning a `Parse` error with the index and offset of the first entry
+    /// whose header has a bad checksum or size, or whose payload is truncated.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        let mut entries = Vec::new();
+        let mut path = None;
+        let mut offset = 0;
+        while offset + BLOCK <= bytes.len() {
+            let header = &bytes[offset..offset + BLOCK];
+            if header.iter().all(|byte| *byte == 0) {
+                break
+            }
+            let idx = entries.len();
+            let malformed = |msg| TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) };
+            // the checksum is the sum of the header, with the checksum itself as spaces
+            let sum = header.iter().map(|byte| *byte as u64).sum::<u64>() - header[148..156].iter().map(|byte| *byte as u64).sum::<u64>() + 8 * b' ' as u64;
+            if number(&header[148..156]).map_err(malformed)? != sum {
+                return Err(malformed("header checksum mismatch"))
+            }
+            let data = offset + BLOCK;
+            let size = number(&header[124..136]).map_err(malformed)?;
+            if size > (bytes.len() - data) as u64 {
+                return Err(malformed("truncated entry"))
+            }
+            let size = size as usize;
+            match header[156] {
+                // a GNU long name for the next entry
+                b'L' => path = Some((data, data + cstr(&bytes[data..data + size]).len())),
+                // a pax extended header for the next entry
+                b'x' => path = pax_path(bytes, data, size).map_err(malformed)?.or(path),
+                // a pax global header, or GNU long link name, neither of which is supported
+                b'g' | b'K' => (),
+                _ => entries.push(Entry { header: offset, path: path.take() }),
+            }
+            offset = data + size.div_ceil(BLOCK) * BLOCK;
+        }
+        Ok(TarTransducer { bytes, entries })
+    }
+    /// How many entries are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    /// Whether this lazy transducer has no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    /// Returns the byte offset of the header of the `idx`th entry, or `None` if the index is
+    /// greater than the number of entries.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.entries.get(idx).map(|entry| entry.header)
+    }
+    /// Returns the contents of the `idx`th entry, without parsing the rest of its header, or
+    /// `None` if the index is greater than the number of entries.
+    pub fn data(&self, idx: usize) -> Option<&'a [u8]> {
+        let header = self.offset_of(idx)?;
+        // the size was validated when building the index
+        let size = number(&self.bytes[header + 124..header + 136]).unwrap() as usize;
+        Some(&self.bytes[header + BLOCK..header + BLOCK + size])
+    }
+    /// Parse an entry out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of entries in this lazy transducer, and a `Parse` error if a numeric field of its
+    /// header is malformed.
+    pub fn get(&self, idx: usize) -> Option<Result<TarEntry<'a>, TransducerError>> {
+        self.entries.get(idx).map(|entry| self.parse(idx, entry))
+    }
+    fn parse(&self, idx: usize, entry: &Entry) -> Result<TarEntry<'a>, TransducerError> {
+        let header = &self.bytes[entry.header..entry.header + BLOCK];
+        let field = |range: Range<usize>| number(&header[range]).map_err(|msg| {
+            TransducerError::Parse{ idx, offset: entry.header, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) }
+        });
+        let path = match entry.path {
+            Some((start, end)) => Cow::Borrowed(&self.bytes[start..end]),
+            None => {
+                let (name, prefix) = (cstr(&header[..100]), cstr(&header[345..500]));
+                if &header[257..262] == b"ustar" && !prefix.is_empty() {
+                    Cow::Owned([prefix, b"/", name].concat())
+                } else {
+                    Cow::Borrowed(name)
+                }
+            },
+        };
+        Ok(TarEntry {
+            path,
+            mode: field(100..108)? as u32,
+            uid: field(108..116)?,
+            gid: field(116..124)?,
+            mtime: field(136..148)?,
+            kind: header[156],
+            link_name: cstr(&header[157..257]),
+            // we unwrap because the index is in bounds
+            data: self.data(idx).unwrap(),
+        })
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the parsed entries, borrowing this one.
+    impl['a] TarTransducer<'a> => <'b> Result<TarEntry<'a>, TransducerError>
+    where []
+    parallel where []
+}
@@ -0,0 +1,133 @@
+use std::io;
+
+use scroll::Endian;
+
+use TransducerError;
+
+/// The layout of the header which precedes the value of every record in a
+/// [TlvTransducer](struct.TlvTransducer.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TlvSpec {
+    /// The size in bytes of the tag, from 1 to 8
+    pub tag_width: usize,
+    /// The size in bytes of the length, from 1 to 8
+    pub len_width: usize,
+    /// The byte order of the tag and the length
+    pub endian: Endian,
+    /// Whether the length counts the header as well as the value
+    pub len_includes_header: bool,
+}
+
+impl TlvSpec {
+    /// The size in bytes of the header of every record
+    pub fn header_len(&self) -> usize {
+        self.tag_width + self.len_width
+    }
+}
+
+/// Reads the unsigned integer of `bytes.len()` bytes in `endian` byte order
+#[inline]
+fn uint(bytes: &[u8], endian: Endian) -> u64 {
+    if endian.is_little() {
+        bytes.iter().rev().fold(0, |n, byte| n << 8 | *byte as u64)
+    } else {
+        bytes.iter().fold(0, |n, byte| n << 8 | *byte as u64)
+    }
+}
+
+/// A lazy transducer over back to back type-length-value records, e.g., the options of a firmware
+/// image or a network protocol, yielding the tag and value of every record.
+///
+/// The widths and byte order of the tag and length, and whether the length counts the header, are
+/// described by a [TlvSpec](struct.TlvSpec.html). A single pass at construction reads every header
+/// and records where the record starts in an index, so that afterwards, accessing a record is O(1).
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::{TlvTransducer, TlvSpec, Endian};
+///
+/// // a one byte tag, and a big endian two byte length of the value only
+/// let spec = TlvSpec { tag_width: 1, len_width: 2, endian: Endian::Big, len_includes_header: false };
+/// let bytes = [0x01, 0x00, 0x02, 0xbe, 0xef, 0x7f, 0x00, 0x00, 0x02, 0x00, 0x01, 0x2a];
+/// let lt = TlvTransducer::new(&bytes, spec).unwrap();
+/// assert_eq!(lt.len(), 3);
+/// assert_eq!(lt.get(0), Some((1, &[0xbe, 0xef][..])));
+/// assert_eq!(lt.get(1), Some((0x7f, &[][..])));
+/// assert_eq!(lt.offset_of(2), Some(8));
+/// let tags: Vec<u64> = lt.into_iter().map(|(tag, _)| tag).collect();
+/// assert_eq!(tags, vec![1, 0x7f, 2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TlvTransducer<'a> {
+    bytes: &'a [u8],
+    spec: TlvSpec,
+    /// The offset of every record, plus the end of the last record as a final entry
+    offsets: Vec<usize>,
+}
+
+impl<'a> TlvTransducer<'a> {
+    /// Create a new lazy transducer over the records in `bytes` laid out according to `spec`,
+    /// returning an `InvalidInput` I/O error if a width in `spec` is not from 1 to 8, and a `Parse`
+    /// error with the index and offset of the first record which is truncated, or whose length is
+    /// smaller than its header.
+    pub fn new(bytes: &'a [u8], spec: TlvSpec) -> Result<Self, TransducerError> {
+        if spec.tag_width == 0 || spec.tag_width > 8 || spec.len_width == 0 || spec.len_width > 8 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "tag and length widths must be from 1 to 8 bytes").into())
+        }
+        let header_len = spec.header_len();
+        let mut offsets = vec![0];
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let idx = offsets.len() - 1;
+            let malformed = |msg| TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) };
+            let header = bytes.get(offset..offset + header_len).ok_or_else(|| malformed("truncated record header"))?;
+            let len = uint(&header[spec.tag_width..], spec.endian);
+            let record_len = if spec.len_includes_header {
+                len.checked_sub(header_len as u64).ok_or_else(|| malformed("record length is smaller than its header"))?;
+                len
+            } else {
+                len.saturating_add(header_len as u64)
+            };
+            if record_len > (bytes.len() - offset) as u64 {
+                return Err(malformed("truncated record"))
+            }
+            offset += record_len as usize;
+            offsets.push(offset);
+        }
+        Ok(TlvTransducer { bytes, spec, offsets })
+    }
+    /// The layout of the records
+    pub fn spec(&self) -> TlvSpec {
+        self.spec
+    }
+    /// How many records are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th record, i.e., of its header, or `None` if the index
+    /// is greater than the number of records.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len() { None } else { Some(self.offsets[idx]) }
+    }
+    /// Get the tag and value of a record out of the lazy transducer, returning `None` if the index
+    /// is greater than the number of records in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<(u64, &'a [u8])> {
+        let offset = self.offset_of(idx)?;
+        let value = offset + self.spec.header_len();
+        let tag = uint(&self.bytes[offset..offset + self.spec.tag_width], self.spec.endian);
+        Some((tag, &self.bytes[value..self.offsets[idx + 1]]))
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the records, borrowing this one.
+    impl['a] TlvTransducer<'a> => <'b> (u64, &'a [u8])
+    where []
+    parallel where []
+}
@@ -0,0 +1,334 @@
+use std::fmt;
+
+use rayon::prelude::*;
+use rayon::iter::Either;
+
+use {IntoIter, IntoParIter, Iter, LazyTransducer, ParIter};
+
+/// A fallible lazy transducer, for when extracting an element can fail, e.g., when parsing
+/// untrusted bytes.
+///
+/// The transducer returns a `Result`, so accessing an element returns a `Result`, and the iterators
+/// yield `Result`s; in addition, `try_fold` and `try_for_each` (and their parallel counterparts)
+/// stop at the first error and return it.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate scroll;
+/// use lazy_transducer::TryLazyTransducer;
+/// use scroll::Pread;
+///
+/// # fn main() {
+/// let bytes = [1u8, 0, 0, 0, 2, 0, 0, 0, 3, 0];
+/// // a lying count: there are only 2 complete u32s
+/// let lt: TryLazyTransducer<&[u8], u32, scroll::Error> = TryLazyTransducer::new(&bytes[..], 3, |input, idx| {
+///     input.pread_with::<u32>(idx * 4, scroll::LE)
+/// });
+///
+/// assert_eq!(lt.get(1).unwrap().unwrap(), 2);
+/// assert!(lt.get(2).unwrap().is_err());
+///
+/// let mut sum = 0;
+/// let res = lt.try_for_each(|n| { sum += n; Ok(()) });
+/// assert!(res.is_err());
+/// assert_eq!(sum, 3);
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TryLazyTransducer<'a, Input, Output, Error>
+    where Input: 'a + Copy,
+          Output: 'a,
+          Error: 'a,
+{
+    lt: LazyTransducer<'a, Input, Result<Output, Error>>,
+}
+
+impl<'a, Input, Output, Error> TryLazyTransducer<'a, Input, Output, Error>
+    where Input: 'a + Copy,
+          Output: 'a,
+          Error: 'a,
+{
+    /// Create a new fallible lazy transducer with `count` elements in `contents`, using
+    /// `transducer` to extract them.
+    pub fn new(contents: Input,
+               count: usize,
+               transducer: fn(Input, usize) -> Result<Output, Error>)
+               -> Self
+    {
+        TryLazyTransducer { lt: LazyTransducer::new(contents, count, transducer) }
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.lt.count
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.lt.is_empty()
+    }
+    /// Get an element out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of elements in this lazy transducer, and the error if the element could not be
+    /// extracted.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Result<Output, Error>> {
+        self.lt.get(idx)
+    }
+    /// Iterate over the results of this lazy transducer without copying it.
+    pub fn iter<'b>(&'b self) -> Iter<'b, 'a, Input, Result<Output, Error>> {
+        self.lt.iter()
+    }
+    /// Returns the underlying lazy transducer, whose elements are `Result`s
+    pub fn into_inner(self) -> LazyTransducer<'a, Input, Result<Output, Error>> {
+        self.lt
+    }
+    /// Partition every element into the successfully extracted values, and the failures along with
+    /// their index; see [partition_results](struct.LazyTransducer.html#method.partition_results).
+    pub fn partition_results(&self) -> (Vec<Output>, Vec<(usize, Error)>) {
+        self.lt.partition_results()
+    }
+    /// Fold every element into an accumulator with `f`, returning the first error encountered,
+    /// either from extracting an element or from `f`.
+    pub fn try_fold<T, F>(&self, init: T, mut f: F) -> Result<T, Error>
+        where F: FnMut(T, Output) -> Result<T, Error>
+    {
+        self.iter().try_fold(init, |acc, output| output.and_then(|output| f(acc, output)))
+    }
+    /// Call `f` on every element, returning the first error encountered, either from extracting
+    /// an element or from `f`.
+    pub fn try_for_each<F>(&self, mut f: F) -> Result<(), Error>
+        where F: FnMut(Output) -> Result<(), Error>
+    {
+        self.iter().try_for_each(|output| output.and_then(&mut f))
+    }
+}
+
+impl<'a, Input, Output, Error> TryLazyTransducer<'a, Input, Output, Error>
+    where Input: 'a + Sync + Send + Copy,
+          Output: 'a + Send + Sync,
+          Error: 'a + Send + Sync,
+{
+    /// Fold the elements in parallel, returning an error encountered (either from extracting an
+    /// element, or from `fold` or `reduce`) as soon as possible; see
+    /// [par_fold](struct.LazyTransducer.html#method.par_fold).
+    pub fn try_par_fold<T, I, F, R>(&self, init: I, fold: F, reduce: R) -> Result<T, Error>
+        where T: Send,
+              I: Fn() -> T + Sync + Send,
+              F: Fn(T, Output) -> Result<T, Error> + Sync + Send,
+              R: Fn(T, T) -> Result<T, Error> + Sync + Send,
+    {
+        self.lt.par_iter()
+            .try_fold(&init, |acc, output| output.and_then(|output| fold(acc, output)))
+            .try_reduce(&init, reduce)
+    }
+    /// Partition every element in parallel into the successfully extracted values, and the failures
+    /// along with their index; see [partition_results](#method.partition_results).
+    pub fn par_partition_results(&self) -> (Vec<Output>, Vec<(usize, Error)>) {
+        self.lt.par_partition_results()
+    }
+    /// Eagerly extract every element in parallel, returning a report of every element which
+    /// failed, if any; access to the elements afterwards remains lazy.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::TryLazyTransducer;
+    ///
+    /// let data = [1u32, 0, 3, 0];
+    /// let lt: TryLazyTransducer<&[u32], u32, &'static str> = TryLazyTransducer::new(&data, 4, |input, idx| {
+    ///     if input[idx] == 0 { Err("zero") } else { Ok(input[idx]) }
+    /// });
+    /// let report = lt.validate().unwrap_err();
+    /// assert_eq!(report.errors(), &[(1, "zero"), (3, "zero")]);
+    /// ```
+    pub fn validate(&self) -> Result<(), ValidationReport<Error>> {
+        let errors: Vec<(usize, Error)> = self.lt.par_iter()
+            .enumerate()
+            .filter_map(|(idx, output)| output.err().map(|err| (idx, err)))
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationReport { errors })
+        }
+    }
+    /// Eagerly extract the elements in parallel, returning only the index and error of the first
+    /// element which fails; workers stop as soon as an element before theirs has failed.
+    ///
+    /// This is much faster than [validate](#method.validate) for a simple "is this input sane?"
+    /// check; however the work is split, the failure with the lowest index is returned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::TryLazyTransducer;
+    ///
+    /// let data: Vec<u32> = (0..100_000).collect();
+    /// let lt: TryLazyTransducer<&[u32], u32, &'static str> = TryLazyTransducer::new(&data, data.len(), |input, idx| {
+    ///     if input[idx] == 1234 { Err("corrupt") } else { Ok(input[idx]) }
+    /// });
+    /// assert_eq!(lt.validate_fail_fast(), Err((1234, "corrupt")));
+    /// ```
+    pub fn validate_fail_fast(&self) -> Result<(), (usize, Error)> {
+        let failure = self.lt.par_iter()
+            .enumerate()
+            .find_first(|(_, output)| output.is_err());
+        match failure {
+            Some((idx, Err(err))) => Err((idx, err)),
+            _ => Ok(()),
+        }
+    }
+    /// Call `f` on every element in parallel, returning an error encountered (either from
+    /// extracting an element, or from `f`) as soon as possible.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::TryLazyTransducer;
+    ///
+    /// let data = [1u32, 2, 0, 4];
+    /// let lt: TryLazyTransducer<&[u32], u32, String> = TryLazyTransducer::new(&data, 4, |input, idx| {
+    ///     match input[idx] {
+    ///         0 => Err(format!("element {} is zero", idx)),
+    ///         n => Ok(n),
+    ///     }
+    /// });
+    /// let res = lt.try_par_for_each(|n| { println!("{}", n); Ok(()) });
+    /// assert_eq!(res, Err("element 2 is zero".to_string()));
+    /// ```
+    pub fn try_par_for_each<F>(&self, f: F) -> Result<(), Error>
+        where F: Fn(Output) -> Result<(), Error> + Sync + Send
+    {
+        self.lt.par_iter().try_for_each(|output| output.and_then(&f))
+    }
+}
+
+impl<'a, Input, T, E> LazyTransducer<'a, Input, Result<T, E>>
+    where Input: 'a + Copy,
+          T: 'a,
+          E: 'a,
+{
+    /// Partition every `Result` element into the successful values, and the failures along with
+    /// their index, in one pass.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::LazyTransducer;
+    ///
+    /// let data = ["1", "two", "3"];
+    /// let lt: LazyTransducer<&[&str], Result<u32, std::num::ParseIntError>> =
+    ///     LazyTransducer::new(&data, 3, |input, idx| input[idx].parse());
+    /// let (numbers, errors) = lt.partition_results();
+    /// assert_eq!(numbers, vec![1, 3]);
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].0, 1);
+    /// ```
+    pub fn partition_results(&self) -> (Vec<T>, Vec<(usize, E)>) {
+        let mut oks = Vec::new();
+        let mut errs = Vec::new();
+        for (idx, result) in self.iter().enumerate() {
+            match result {
+                Ok(output) => oks.push(output),
+                Err(err) => errs.push((idx, err)),
+            }
+        }
+        (oks, errs)
+    }
+}
+
+impl<'a, Input, T, E> LazyTransducer<'a, Input, Result<T, E>>
+    where Input: 'a + Sync + Send + Copy,
+          T: 'a + Send + Sync,
+          E: 'a + Send + Sync,
+{
+    /// Partition every `Result` element in parallel into the successful values, and the failures
+    /// along with their index; both are in ascending index order, as with
+    /// [partition_results](#method.partition_results).
+    pub fn par_partition_results(&self) -> (Vec<T>, Vec<(usize, E)>) {
+        self.par_iter().enumerate().partition_map(|(idx, result)| {
+            match result {
+                Ok(output) => Either::Left(output),
+                Err(err) => Either::Right((idx, err)),
+            }
+        })
+    }
+}
+
+/// The elements which failed to be extracted during [validation](struct.TryLazyTransducer.html#method.validate),
+/// in ascending index order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationReport<Error> {
+    errors: Vec<(usize, Error)>,
+}
+
+impl<Error> ValidationReport<Error> {
+    /// The index of every failing element, along with its error
+    pub fn errors(&self) -> &[(usize, Error)] {
+        &self.errors
+    }
+    /// Returns the index and error of every failing element
+    pub fn into_errors(self) -> Vec<(usize, Error)> {
+        self.errors
+    }
+}
+
+impl<Error: fmt::Display> fmt::Display for ValidationReport<Error> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} element(s) failed validation", self.errors.len())?;
+        for &(idx, ref err) in &self.errors {
+            write!(f, "\n  {}: {}", idx, err)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, Input: Copy, Output, Error> Clone for TryLazyTransducer<'a, Input, Output, Error> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, Input: Copy, Output, Error> Copy for TryLazyTransducer<'a, Input, Output, Error> {}
+
+impl<'a, Input: Copy, Output, Error> From<LazyTransducer<'a, Input, Result<Output, Error>>> for TryLazyTransducer<'a, Input, Output, Error> {
+    fn from(lt: LazyTransducer<'a, Input, Result<Output, Error>>) -> Self {
+        TryLazyTransducer { lt }
+    }
+}
+
+impl<'a, Input: Copy, Output, Error> IntoIterator for TryLazyTransducer<'a, Input, Output, Error> {
+    type Item = Result<Output, Error>;
+    type IntoIter = IntoIter<'a, Input, Result<Output, Error>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lt.into_iter()
+    }
+}
+
+impl<'a, 'b, Input: Copy, Output, Error> IntoIterator for &'b TryLazyTransducer<'a, Input, Output, Error> {
+    type Item = Result<Output, Error>;
+    type IntoIter = Iter<'b, 'a, Input, Result<Output, Error>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.lt.iter()
+    }
+}
+
+impl<'a, Input: Sync + Copy + Send, Output: Send + Sync, Error: Send + Sync> IntoParallelIterator for TryLazyTransducer<'a, Input, Output, Error> {
+    type Iter = IntoParIter<'a, Input, Result<Output, Error>>;
+    type Item = Result<Output, Error>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.lt.into_par_iter()
+    }
+}
+
+impl<'a, 'b, Input: Sync + Copy + Send, Output: Send + Sync, Error: Send + Sync> IntoParallelIterator for &'b TryLazyTransducer<'a, Input, Output, Error> {
+    type Iter = ParIter<'b, 'a, Input, Result<Output, Error>>;
+    type Item = Result<Output, Error>;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.lt.par_iter()
+    }
+}
@@ -0,0 +1,139 @@
+use std::char::{self, DecodeUtf16Error};
+
+use scroll::{self, Endian, Pread};
+
+use {LazyTransducer, LengthPrefix, TransducerError};
+use lazy_transducer::capacity_for;
+
+/// A lazy transducer over a table of UTF-16 strings, e.g., the names in a PE resource directory,
+/// which are either length-prefixed and laid out back to back, or NUL-terminated at given
+/// offsets.
+///
+/// A single pass at construction records where every string lies, so that afterwards, accessing a
+/// string is O(1), but the strings are only decoded when accessed: strictly, returning a `Parse`
+/// error for unpaired surrogates, or lossily, replacing them with `U+FFFD`.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// use lazy_transducer::{Utf16Transducer, LengthPrefix, Endian};
+///
+/// # fn main() {
+/// // "hi", and a lone surrogate between "o" and "k", each preceded by its length in code units
+/// let prefixed = b"\x02\x00h\x00i\x00\x03\x00o\x00\x00\xd8k\x00";
+/// let lt = Utf16Transducer::parse_prefixed(prefixed, 2, LengthPrefix::U16(Endian::Little), Endian::Little).unwrap();
+/// assert_eq!(lt.get(0).unwrap().unwrap(), "hi");
+/// assert!(lt.get(1).unwrap().is_err());
+/// assert_eq!(lt.lossy().into_iter().collect::<Vec<_>>(), vec!["hi", "o\u{fffd}k"]);
+///
+/// let terminated = b"\x00a\x00b\x00\x00\x00c\x00\x00";
+/// let lt = Utf16Transducer::parse_terminated(terminated, &[0, 6], Endian::Big).unwrap();
+/// assert_eq!(lt.get_lossy(1), Some("c".to_string()));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Utf16Transducer<'a> {
+    bytes: &'a [u8],
+    endian: Endian,
+    /// The start and end offset of every string, excluding any terminator or prefix
+    spans: Vec<(usize, usize)>,
+}
+
+/// Reads the length prefix at `offset`, returning the span of the code units it counts, and
+/// advancing `offset` past them
+fn prefixed_span(bytes: &[u8], offset: &mut usize, prefix: LengthPrefix) -> Result<(usize, usize), scroll::Error> {
+    let units = match prefix {
+        LengthPrefix::U8 => bytes.gread::<u8>(offset)? as usize,
+        LengthPrefix::U16(endian) => bytes.gread_with::<u16>(offset, endian)? as usize,
+        LengthPrefix::U32(endian) => bytes.gread_with::<u32>(offset, endian)? as usize,
+    };
+    let start = *offset;
+    let end = units.checked_mul(2)
+        .and_then(|len| start.checked_add(len))
+        .filter(|end| *end <= bytes.len())
+        .ok_or(scroll::Error::TooBig{ size: units.saturating_mul(2), len: bytes.len() - start })?;
+    *offset = end;
+    Ok((start, end))
+}
+
+impl<'a> Utf16Transducer<'a> {
+    /// Create a new lazy transducer over `count` UTF-16 strings in `endian` byte order, each of
+    /// which is preceded by its length in UTF-16 code units (not bytes), returning a `Parse` error
+    /// with the index and offset of the first string which does not fit.
+    pub fn parse_prefixed(bytes: &'a [u8], count: usize, prefix: LengthPrefix, endian: Endian) -> Result<Self, TransducerError> {
+        let mut spans = Vec::with_capacity(capacity_for(count, bytes.len(), 1));
+        let mut offset = 0;
+        for idx in 0..count {
+            let element_offset = offset;
+            let span = prefixed_span(bytes, &mut offset, prefix)
+                .map_err(|err| TransducerError::Parse{ idx, offset: element_offset, source: Box::new(err) })?;
+            spans.push(span);
+        }
+        Ok(Utf16Transducer { bytes, endian, spans })
+    }
+    /// Create a new lazy transducer over the UTF-16 strings in `endian` byte order starting at each
+    /// of `offsets`, which are terminated by a NUL code unit, returning a `Parse` error with the
+    /// index and offset of the first string which is unterminated.
+    pub fn parse_terminated(bytes: &'a [u8], offsets: &[usize], endian: Endian) -> Result<Self, TransducerError> {
+        let spans = offsets.iter().enumerate().map(|(idx, &offset)| {
+            bytes.get(offset..)
+                .and_then(|string| string.chunks_exact(2).position(|unit| unit == [0, 0]))
+                .map(|units| (offset, offset + units * 2))
+                .ok_or_else(|| {
+                    let err = scroll::Error::Custom(format!("unterminated UTF-16 string at offset {:#x}", offset));
+                    TransducerError::Parse{ idx, offset, source: Box::new(err) }
+                })
+        }).collect::<Result<_, _>>()?;
+        Ok(Utf16Transducer { bytes, endian, spans })
+    }
+    /// How many strings are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+    /// Whether this lazy transducer has no strings
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+    /// Returns the byte offset of the `idx`th string, excluding any length prefix, or `None` if the
+    /// index is greater than the number of strings.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.spans.get(idx).map(|&(start, _)| start)
+    }
+    /// Decodes the `idx`th string, which must be in bounds
+    fn decode(&self, idx: usize) -> impl Iterator<Item = Result<char, DecodeUtf16Error>> + 'a {
+        let (start, end) = self.spans[idx];
+        let little = self.endian.is_little();
+        char::decode_utf16(self.bytes[start..end].chunks_exact(2).map(move |unit| {
+            if little { u16::from_le_bytes([unit[0], unit[1]]) } else { u16::from_be_bytes([unit[0], unit[1]]) }
+        }))
+    }
+    /// Decode a string out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of strings in this lazy transducer, and a `Parse` error if it contains an
+    /// unpaired surrogate.
+    pub fn get(&self, idx: usize) -> Option<Result<String, TransducerError>> {
+        self.offset_of(idx).map(|offset| {
+            self.decode(idx).collect::<Result<String, _>>()
+                .map_err(|err| TransducerError::Parse{ idx, offset, source: Box::new(err) })
+        })
+    }
+    /// Decode a string out of the lazy transducer like [get](#method.get), but replacing any
+    /// unpaired surrogates with `U+FFFD`.
+    pub fn get_lossy(&self, idx: usize) -> Option<String> {
+        if idx >= self.len() {
+            return None
+        }
+        Some(self.decode(idx).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)).collect())
+    }
+    /// Returns a lazy transducer over the lossily decoded strings, borrowing this one.
+    pub fn lossy<'b>(&'b self) -> LazyTransducer<'b, &'b Self, String> {
+        LazyTransducer::new(self, self.len(), |lt, idx| lt.get_lossy(idx).unwrap())
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the strictly decoded strings, borrowing this one.
+    impl['a] Utf16Transducer<'a> => <'b> Result<String, TransducerError>
+    where []
+    parallel where []
+}
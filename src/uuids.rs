@@ -0,0 +1,128 @@
+use rayon::prelude::*;
+#[cfg(feature = "uuid")]
+use uuid::Uuid;
+
+use {IntoIter, IntoParIter, LazyTransducer};
+
+/// The size in bytes of a UUID
+const SIZE: usize = 16;
+
+/// How the 16 bytes of a UUID of a [UuidTransducer](struct.UuidTransducer.html) are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UuidLayout {
+    /// The bytes are in the order of the textual form, i.e., big endian, as in RFC 4122, and most
+    /// file formats and network protocols
+    Rfc4122,
+    /// The first three fields, of 4, 2, and 2 bytes, are little endian, as in a Microsoft `GUID`
+    /// struct, e.g., in GPT partition tables, COM, or the Windows registry
+    Guid,
+}
+
+/// A lazy transducer over a table of 16-byte UUIDs, e.g., the partition type GUIDs of a GPT, which
+/// yields every UUID as its bytes in RFC 4122 order, whatever its layout in the table, so that it
+/// compares and formats the same as its textual form.
+///
+/// Every complete UUID in the bytes is an element; any trailing bytes which are too few for
+/// another UUID are ignored. With the `uuid` feature, the UUIDs can also be yielded as `Uuid`s,
+/// via [uuids](#method.uuids).
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::{UuidTransducer, UuidLayout};
+///
+/// // the GPT partition type of an EFI system partition, C12A7328-F81F-11D2-BA4B-00A0C93EC93B
+/// let guid = [0x28, 0x73, 0x2a, 0xc1, 0x1f, 0xf8, 0xd2, 0x11, 0xba, 0x4b, 0x00, 0xa0, 0xc9, 0x3e, 0xc9, 0x3b];
+/// let lt = UuidTransducer::new(&guid, UuidLayout::Guid);
+/// assert_eq!(lt.len(), 1);
+/// let uuid = lt.get(0).unwrap();
+/// assert_eq!(uuid[..4], [0xc1, 0x2a, 0x73, 0x28]);
+/// // the last 8 bytes are never swapped
+/// assert_eq!(uuid[8..], guid[8..]);
+/// assert_eq!(UuidTransducer::new(&uuid, UuidLayout::Rfc4122).into_iter().next(), Some(uuid));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct UuidTransducer<'a> {
+    bytes: &'a [u8],
+    layout: UuidLayout,
+}
+
+impl<'a> UuidTransducer<'a> {
+    /// Create a new lazy transducer over the UUIDs in `bytes`, laid out as `layout`.
+    pub fn new(bytes: &'a [u8], layout: UuidLayout) -> Self {
+        UuidTransducer { bytes, layout }
+    }
+    /// The layout of the UUIDs
+    pub fn layout(&self) -> UuidLayout {
+        self.layout
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.bytes.len() / SIZE
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Get a UUID out of the lazy transducer, as its bytes in RFC 4122 order, returning `None` if
+    /// the index is greater than the number of elements in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<[u8; SIZE]> {
+        if idx >= self.len() {
+            return None
+        }
+        let mut uuid = [0; SIZE];
+        uuid.copy_from_slice(&self.bytes[idx * SIZE..(idx + 1) * SIZE]);
+        if self.layout == UuidLayout::Guid {
+            uuid[..4].reverse();
+            uuid[4..6].reverse();
+            uuid[6..8].reverse();
+        }
+        Some(uuid)
+    }
+    /// Get a UUID out of the lazy transducer as a `Uuid`, whose `Display` is its hyphenated textual
+    /// form, returning `None` if the index is greater than the number of elements in this lazy
+    /// transducer.
+    #[cfg(feature = "uuid")]
+    pub fn get_uuid(&self, idx: usize) -> Option<Uuid> {
+        self.get(idx).map(Uuid::from_bytes)
+    }
+    /// Returns a lazy transducer over the UUIDs, as their bytes in RFC 4122 order.
+    pub fn transducer(&self) -> LazyTransducer<'a, Self, [u8; SIZE]> {
+        LazyTransducer::new(*self, self.len(), |lt, idx| lt.get(idx).unwrap())
+    }
+    /// Returns a lazy transducer over the UUIDs, as `Uuid`s.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{UuidTransducer, UuidLayout};
+    ///
+    /// let bytes = [0x78, 0x56, 0x34, 0x12, 0x34, 0x12, 0x34, 0x12, 0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0];
+    /// let lt = UuidTransducer::new(&bytes, UuidLayout::Guid);
+    /// let uuids = lt.uuids().into_iter().map(|uuid| uuid.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(uuids, vec!["12345678-1234-1234-1234-56789abcdef0"]);
+    /// ```
+    #[cfg(feature = "uuid")]
+    pub fn uuids(&self) -> LazyTransducer<'a, Self, Uuid> {
+        LazyTransducer::new(*self, self.len(), |lt, idx| lt.get_uuid(idx).unwrap())
+    }
+}
+
+impl<'a> IntoIterator for UuidTransducer<'a> {
+    type Item = [u8; SIZE];
+    type IntoIter = IntoIter<'a, UuidTransducer<'a>, [u8; SIZE]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.transducer().into_iter()
+    }
+}
+
+impl<'a> IntoParallelIterator for UuidTransducer<'a> {
+    type Iter = IntoParIter<'a, UuidTransducer<'a>, [u8; SIZE]>;
+    type Item = [u8; SIZE];
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.transducer().into_par_iter()
+    }
+}
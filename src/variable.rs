@@ -0,0 +1,121 @@
+use std::error;
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+
+use scroll::{self, ctx, Pread};
+
+use {ScrollTransducer, TransducerError};
+use lazy_transducer::capacity_for;
+
+/// A scroll-based lazy transducer over variable-size elements, e.g., DWARF entries or version
+/// records.
+///
+/// A single sizing pass at construction records the byte offset of every element in an offset
+/// index (using the size each element reports while being parsed), so that afterwards, accessing
+/// an element is O(1), and the elements can be iterated in parallel, like any other lazy transducer.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate rayon;
+/// use lazy_transducer::{ScrollTransducer, VariableTransducer, Endian};
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// // three null terminated strings
+/// let bytes = b"one\0three\0five\0";
+/// let lt: VariableTransducer<&str, _> = ScrollTransducer::parse_variable(bytes, 3, Default::default()).unwrap();
+/// assert_eq!(lt.get(1), Some("three"));
+/// assert_eq!(lt.offset_of(2), Some(10));
+///
+/// let total: usize = lt.into_par_iter().map(|s| s.len()).sum();
+/// assert_eq!(total, 12);
+/// # }
+/// ```
+pub struct VariableTransducer<'a, Output, Ctx = scroll::Endian>
+    where Output: 'a,
+          Ctx: Copy,
+{
+    bytes: &'a [u8],
+    ctx: Ctx,
+    /// The offset of each element, plus the end of the last element as a final entry
+    offsets: Vec<usize>,
+    _marker: PhantomData<Output>,
+}
+
+impl<'a, Output, Ctx: Copy> Clone for VariableTransducer<'a, Output, Ctx> {
+    fn clone(&self) -> Self {
+        VariableTransducer { bytes: self.bytes, ctx: self.ctx, offsets: self.offsets.clone(), _marker: PhantomData }
+    }
+}
+
+impl<'a, Output, Ctx: Copy + Debug> Debug for VariableTransducer<'a, Output, Ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VariableTransducer")
+            .field("bytes", &self.bytes)
+            .field("ctx", &self.ctx)
+            .field("offsets", &self.offsets)
+            .finish()
+    }
+}
+
+impl<'a, Output, Ctx, E> VariableTransducer<'a, Output, Ctx>
+    where Ctx: Copy,
+          Output: 'a + ctx::TryFromCtx<'a, Ctx, Error = E>,
+          E: From<scroll::Error> + Debug,
+{
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th element, or `None` if the index is greater than the
+    /// number of elements.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        if idx >= self.len() { None } else { Some(self.offsets[idx]) }
+    }
+    /// Get an element out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of elements in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Output> {
+        // we unwrap because every element was parsed during the sizing pass
+        self.offset_of(idx).map(|offset| self.bytes.pread_with(offset, self.ctx).unwrap())
+    }
+}
+
+impl<'a, Output, Ctx, E> ScrollTransducer<'a, Output, Ctx>
+    where Ctx: Copy,
+          Output: 'a + ctx::TryFromCtx<'a, Ctx, Error = E>,
+          E: From<scroll::Error> + Debug,
+{
+    /// Create a new scroll-based lazy transducer over `count` variable-size elements, which are
+    /// laid out back to back from the start of `bytes`; see
+    /// [VariableTransducer](struct.VariableTransducer.html).
+    ///
+    /// Every element is parsed once here to learn its size, returning a `Parse` error with the
+    /// index and offset of the first element which is malformed or does not fit.
+    pub fn parse_variable(bytes: &'a [u8], count: usize, ctx: Ctx) -> Result<VariableTransducer<'a, Output, Ctx>, TransducerError>
+        where E: error::Error + Send + Sync + 'static
+    {
+        let mut offsets = Vec::with_capacity(capacity_for(count, bytes.len(), 1) + 1);
+        let mut offset = 0;
+        offsets.push(offset);
+        for idx in 0..count {
+            let start = offset;
+            bytes.gread_with::<Output>(&mut offset, ctx).map_err(|err| TransducerError::Parse{ idx, offset: start, source: Box::new(err) })?;
+            offsets.push(offset);
+        }
+        Ok(VariableTransducer { bytes, ctx, offsets, _marker: PhantomData })
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the elements, borrowing this one.
+    impl['a, Output, Ctx, E] VariableTransducer<'a, Output, Ctx> => <'b> Output
+    where [Ctx: Copy, Output: 'a + ctx::TryFromCtx<'a, Ctx, Error = E>, E: From<scroll::Error> + Debug]
+    parallel where [Ctx: Copy + Send + Sync, Output: 'a + ctx::TryFromCtx<'a, Ctx, Error = E> + Send + Sync, E: From<scroll::Error> + Debug]
+}
@@ -0,0 +1,189 @@
+use scroll::{self, Pread};
+
+use TransducerError;
+use lazy_transducer::{capacity_for, total_size};
+
+/// A parse function for the body of one kind of record, i.e., the bytes following its tag; it
+/// returns the element, and how many bytes of the body it consumed.
+pub type VariantParser<'a, Output> = fn(&'a [u8]) -> Result<(Output, usize), scroll::Error>;
+
+/// A registry of parse functions for heterogeneous records, selected by a leading tag byte, from
+/// which a [VariantTransducer](struct.VariantTransducer.html) is created.
+///
+/// # Example
+///
+/// ```rust
+/// extern crate lazy_transducer;
+/// extern crate scroll;
+/// use lazy_transducer::{Variants, VariantTransducer};
+/// use scroll::{Pread, LE};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Record {
+///     Byte(u8),
+///     Word(u32),
+/// }
+///
+/// # fn main() {
+/// let variants = Variants::new()
+///   .register(1, |body| Ok((Record::Byte(body.pread(0)?), 1)))
+///   .register(2, |body| Ok((Record::Word(body.pread_with(0, LE)?), 4)));
+///
+/// // variable-size records: the tag, followed by its body
+/// let bytes = [1, 0xff, 2, 0xef, 0xbe, 0xad, 0xde, 1, 7];
+/// let lt = variants.variable(&bytes, 3).unwrap();
+/// assert_eq!(lt.get(1), Some(Record::Word(0xdeadbeef)));
+/// assert_eq!(lt.get(2), Some(Record::Byte(7)));
+///
+/// // fixed-size records (a union), padded to 5 bytes
+/// let bytes = [2, 1, 0, 0, 0, 1, 9, 0, 0, 0];
+/// let lt = variants.fixed(&bytes, 2, 5).unwrap();
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![Record::Word(1), Record::Byte(9)]);
+/// # }
+/// ```
+pub struct Variants<'a, Output: 'a> {
+    parsers: Vec<Option<VariantParser<'a, Output>>>,
+}
+
+impl<'a, Output> Variants<'a, Output> {
+    /// Create a new registry without any variants
+    pub fn new() -> Self {
+        Variants { parsers: vec![None; 256] }
+    }
+    /// Register `parser` for records whose tag is `tag`, replacing any previous parser for it.
+    pub fn register(mut self, tag: u8, parser: VariantParser<'a, Output>) -> Self {
+        self.parsers[tag as usize] = Some(parser);
+        self
+    }
+    /// Create a lazy transducer over `count` records in `bytes`, each of which takes
+    /// `record_size` bytes, including its tag, i.e., a tagged union.
+    ///
+    /// Only the bounds are checked here; the records are parsed on demand.
+    pub fn fixed(&self, bytes: &'a [u8], count: usize, record_size: usize) -> Result<VariantTransducer<'a, Output>, TransducerError> {
+        if total_size(count, record_size)? > bytes.len() {
+            return Err(TransducerError::ElementOverflow{ nelements: count, sizeof_element: record_size, stride: record_size, src_size: bytes.len() })
+        }
+        let offsets = Offsets::Fixed { count, record_size };
+        Ok(VariantTransducer { bytes, parsers: self.parsers.clone(), offsets })
+    }
+    /// Create a lazy transducer over `count` variable-size records laid out back to back from
+    /// the start of `bytes`, each of which is its tag followed by however many bytes its parser
+    /// consumes.
+    ///
+    /// Every record is parsed once here to learn its size, returning a `Parse` error with the
+    /// index and offset of the first record which has an unknown tag, is malformed, or does not
+    /// fit; afterwards, accessing a record is O(1).
+    pub fn variable(&self, bytes: &'a [u8], count: usize) -> Result<VariantTransducer<'a, Output>, TransducerError> {
+        let mut offsets = Vec::with_capacity(capacity_for(count, bytes.len(), 1) + 1);
+        let mut offset = 0;
+        offsets.push(offset);
+        for idx in 0..count {
+            let parse_error = move |err| TransducerError::Parse{ idx, offset, source: Box::new(err) };
+            let (_, size) = parse(&self.parsers, bytes, offset, bytes.len()).map_err(parse_error)?;
+            offset = offset.checked_add(size).filter(|end| *end <= bytes.len())
+                .ok_or_else(|| parse_error(scroll::Error::TooBig{ size, len: bytes.len() - offset }))?;
+            offsets.push(offset);
+        }
+        Ok(VariantTransducer { bytes, parsers: self.parsers.clone(), offsets: Offsets::Variable(offsets) })
+    }
+}
+
+impl<'a, Output> Default for Variants<'a, Output> {
+    fn default() -> Self {
+        Variants::new()
+    }
+}
+
+impl<'a, Output> Clone for Variants<'a, Output> {
+    fn clone(&self) -> Self {
+        Variants { parsers: self.parsers.clone() }
+    }
+}
+
+/// Parse the record at `offset`, whose body must end before `end`, returning it and its size,
+/// including the tag
+fn parse<'a, Output>(parsers: &[Option<VariantParser<'a, Output>>], bytes: &'a [u8], offset: usize, end: usize) -> Result<(Output, usize), scroll::Error> {
+    let tag: u8 = bytes.pread(offset)?;
+    let parser = parsers[tag as usize].ok_or(scroll::Error::BadInput{ size: 1, msg: "unknown variant tag" })?;
+    let body = bytes.get(offset + 1..end).ok_or(scroll::Error::TooBig{ size: 1, len: end.saturating_sub(offset) })?;
+    let (output, size) = parser(body)?;
+    // the parser must not claim more bytes than its record has
+    if size > body.len() {
+        return Err(scroll::Error::TooBig{ size, len: body.len() })
+    }
+    Ok((output, size + 1))
+}
+
+#[derive(Debug, Clone)]
+enum Offsets {
+    Fixed { count: usize, record_size: usize },
+    /// The offset of each record, plus the end of the last record as a final entry
+    Variable(Vec<usize>),
+}
+
+/// A lazy transducer over heterogeneous records, selected by a leading tag byte, whose parse
+/// functions are registered in [Variants](struct.Variants.html).
+///
+/// Records are either fixed-size, like a tagged union, or variable-size, in which case an offset
+/// index is built at construction, like for a [VariableTransducer](struct.VariableTransducer.html).
+pub struct VariantTransducer<'a, Output: 'a> {
+    bytes: &'a [u8],
+    parsers: Vec<Option<VariantParser<'a, Output>>>,
+    offsets: Offsets,
+}
+
+impl<'a, Output> VariantTransducer<'a, Output> {
+    /// How many records are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        match self.offsets {
+            Offsets::Fixed { count, .. } => count,
+            Offsets::Variable(ref offsets) => offsets.len() - 1,
+        }
+    }
+    /// Whether this lazy transducer has no records
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th record's tag, or `None` if the index is greater than
+    /// the number of records.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.span_of(idx).map(|(start, _)| start)
+    }
+    /// The start and end offset of the `idx`th record, including its tag
+    fn span_of(&self, idx: usize) -> Option<(usize, usize)> {
+        if idx >= self.len() {
+            return None
+        }
+        match self.offsets {
+            Offsets::Fixed { record_size, .. } => Some((idx * record_size, (idx + 1) * record_size)),
+            Offsets::Variable(ref offsets) => Some((offsets[idx], offsets[idx + 1])),
+        }
+    }
+    /// Parse a record out of the lazy transducer, returning a `Parse` error with the record index
+    /// and byte offset if its tag is unknown or it is malformed, or an `OutOfBounds` error if the
+    /// index is greater than the number of records.
+    pub fn try_get(&self, idx: usize) -> Result<Output, TransducerError> {
+        let (offset, end) = self.span_of(idx).ok_or(TransducerError::OutOfBounds{ idx, len: self.len() })?;
+        parse(&self.parsers, self.bytes, offset, end)
+            .map(|(output, _)| output)
+            .map_err(|err| TransducerError::Parse{ idx, offset, source: Box::new(err) })
+    }
+    /// Get a record out of the lazy transducer, returning `None` if the index is greater than the
+    /// number of records.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a fixed-size record has an unknown tag, is malformed, or its body overruns the
+    /// record; see [try_get](#method.try_get).
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Output> {
+        self.span_of(idx).map(|(offset, end)| parse(&self.parsers, self.bytes, offset, end).unwrap().0)
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the records, borrowing this one.
+    impl['a, Output] VariantTransducer<'a, Output> => <'b> Output
+    where []
+    parallel where [Output: Send + Sync]
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+
+use TransducerError;
+use lazy_transducer::capacity_for;
+
+/// How many elements share a base offset in the index; since a varint is at most 10 bytes, the
+/// offset of every element relative to its base fits in a `u16`
+const BLOCK: usize = 1024;
+
+/// An integer which can be decoded from a LEB128 varint; see
+/// [VarintTransducer](struct.VarintTransducer.html).
+///
+/// Unsigned integers are decoded from ULEB128, which is also the encoding of protobuf varints, and
+/// signed integers from SLEB128.
+pub trait Leb128: Copy + Send + Sync + 'static {
+    /// The maximum size in bytes of an encoded integer
+    const MAX_LEN: usize;
+    /// Decode an integer from `bytes`, which are exactly one varint; any bits beyond the size of
+    /// the integer are ignored.
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+macro_rules! uleb128 {
+    ($($int:ty),*) => {
+        $(
+            impl Leb128 for $int {
+                const MAX_LEN: usize = (<$int>::BITS as usize).div_ceil(7);
+                #[inline]
+                fn decode(bytes: &[u8]) -> Self {
+                    bytes.iter().enumerate().fold(0, |n, (i, byte)| n | ((*byte & 0x7f) as $int) << (7 * i))
+                }
+            }
+        )*
+    }
+}
+
+macro_rules! sleb128 {
+    ($($int:ty),*) => {
+        $(
+            impl Leb128 for $int {
+                const MAX_LEN: usize = (<$int>::BITS as usize).div_ceil(7);
+                #[inline]
+                fn decode(bytes: &[u8]) -> Self {
+                    let n = bytes.iter().enumerate().fold(0, |n, (i, byte)| n | ((*byte & 0x7f) as $int) << (7 * i));
+                    let shift = 7 * bytes.len() as u32;
+                    // sign extend from the sign bit of the last byte
+                    if shift < <$int>::BITS && bytes[bytes.len() - 1] & 0x40 != 0 { n | !0 << shift } else { n }
+                }
+            }
+        )*
+    }
+}
+
+uleb128!(u32, u64);
+sleb128!(i32, i64);
+
+/// A lazy transducer over a stream of LEB128 varints, e.g., a DWARF expression's operands, or a
+/// packed protobuf field.
+///
+/// A single scan at construction records where every varint starts in a compact index of about 2
+/// bytes per element, so that afterwards, accessing a varint is O(1), and the varints can be
+/// decoded in parallel, like any other lazy transducer. The element type selects the encoding:
+/// `u32` and `u64` for ULEB128 or protobuf varints, and `i32` and `i64` for SLEB128.
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::VarintTransducer;
+///
+/// // 2, 127, 128, and 624485 in ULEB128
+/// let bytes = [0x02, 0x7f, 0x80, 0x01, 0xe5, 0x8e, 0x26];
+/// let lt = VarintTransducer::<u64>::new(&bytes).unwrap();
+/// assert_eq!(lt.len(), 4);
+/// assert_eq!(lt.get(3), Some(624485));
+///
+/// // 2, -1, and -123456 in SLEB128
+/// let bytes = [0x02, 0x7f, 0xc0, 0xbb, 0x78];
+/// let lt = VarintTransducer::<i64>::new(&bytes).unwrap();
+/// assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![2, -1, -123456]);
+/// ```
+pub struct VarintTransducer<'a, T> {
+    bytes: &'a [u8],
+    /// The offset of every `BLOCK`th element
+    bases: Vec<usize>,
+    /// The offset of every element relative to the base of its block, plus the end of the last
+    /// element as a final entry
+    offsets: Vec<u16>,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T> Clone for VarintTransducer<'a, T> {
+    fn clone(&self) -> Self {
+        VarintTransducer { bytes: self.bytes, bases: self.bases.clone(), offsets: self.offsets.clone(), _marker: PhantomData }
+    }
+}
+
+impl<'a, T> fmt::Debug for VarintTransducer<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VarintTransducer")
+            .field("bytes", &self.bytes)
+            .field("bases", &self.bases)
+            .field("offsets", &self.offsets)
+            .finish()
+    }
+}
+
+impl<'a, T: Leb128> VarintTransducer<'a, T> {
+    /// Create a new lazy transducer over every varint in `bytes`, returning a `Parse` error with
+    /// the index and offset of the first varint which is too long, or is truncated.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        Self::with_count(bytes, usize::MAX)
+    }
+    /// Create a new lazy transducer over the first `count` varints in `bytes`, or every varint if
+    /// there are fewer, returning a `Parse` error with the index and offset of the first varint
+    /// which is too long, or is truncated.
+    pub fn with_count(bytes: &'a [u8], count: usize) -> Result<Self, TransducerError> {
+        let mut bases = Vec::new();
+        let mut offsets = Vec::with_capacity(capacity_for(count, bytes.len(), 1) + 1);
+        let mut offset = 0;
+        while offsets.len() < count && offset < bytes.len() {
+            let idx = offsets.len();
+            if idx % BLOCK == 0 {
+                bases.push(offset);
+            }
+            offsets.push((offset - bases[idx / BLOCK]) as u16);
+            let len = bytes[offset..].iter().take(T::MAX_LEN).position(|byte| byte & 0x80 == 0).ok_or_else(|| {
+                let msg = if bytes.len() - offset < T::MAX_LEN { "truncated varint" } else { "varint is too long" };
+                TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) }
+            })?;
+            offset += len + 1;
+        }
+        let idx = offsets.len();
+        if idx % BLOCK == 0 {
+            bases.push(offset);
+        }
+        offsets.push((offset - bases[idx / BLOCK]) as u16);
+        Ok(VarintTransducer { bytes, bases, offsets, _marker: PhantomData })
+    }
+    /// How many elements are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+    /// Whether this lazy transducer has no elements
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Returns the byte offset of the `idx`th varint, or `None` if the index is greater than the
+    /// number of elements; the index of one past the last element is the end of the varints.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.offsets.get(idx).map(|offset| self.bases[idx / BLOCK] + *offset as usize)
+    }
+    /// Get an element out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of elements in this lazy transducer.
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<T> {
+        if idx >= self.len() {
+            return None
+        }
+        // we unwrap because every element and the end of the last one have an offset
+        let (start, end) = (self.offset_of(idx).unwrap(), self.offset_of(idx + 1).unwrap());
+        Some(T::decode(&self.bytes[start..end]))
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the elements, borrowing this one.
+    impl['a, T] VarintTransducer<'a, T> => <'b> T
+    where [T: Leb128]
+    parallel where [T: Leb128]
+}
@@ -0,0 +1,56 @@
+use std::io::Write;
+
+use scroll::IOwrite;
+use scroll::ctx::{IntoCtx, SizeWith};
+
+use {LazyTransducer, TransducerError};
+
+impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
+    where Input: 'a + Copy,
+          Output: 'a,
+{
+    /// Serialize every element, in order, into the sink `w` with the given `ctx`, without
+    /// collecting them first; i.e., the transducer is a streaming re-encoder.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lazy_transducer::{ScrollTransducer, Endian};
+    ///
+    /// let bytes = vec![1, 0, 0, 0, 2, 0, 0, 0];
+    /// let lt: ScrollTransducer<u32> = ScrollTransducer::parse_with(&bytes[..], 2, Endian::Little).unwrap();
+    /// let mut big_endian = Vec::new();
+    /// lt.write_all_into(&mut big_endian, Endian::Big).unwrap();
+    /// assert_eq!(big_endian, vec![0, 0, 0, 1, 0, 0, 0, 2]);
+    /// ```
+    pub fn write_all_into<W, Ctx>(&self, w: &mut W, ctx: Ctx) -> Result<(), TransducerError>
+        where W: Write,
+              Ctx: Copy,
+              Output: IntoCtx<Ctx> + SizeWith<Ctx>,
+    {
+        for output in self {
+            w.iowrite_with(output, ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, Input, Output> LazyTransducer<'a, Input, Output>
+    where Input: 'a + Sync + Send + Copy,
+          Output: 'a + Send + Sync,
+{
+    /// Serialize every element into the sink `w` like [write_all_into](#method.write_all_into),
+    /// but transduce the elements in parallel; they are still written in order, on the calling
+    /// thread, see [par_for_each_ordered](#method.par_for_each_ordered).
+    ///
+    /// Once a write fails, no further elements are transduced or written, and the error is
+    /// returned.
+    pub fn par_write_all_into<W, Ctx>(&self, w: &mut W, ctx: Ctx) -> Result<(), TransducerError>
+        where W: Write,
+              Ctx: Copy,
+              Output: IntoCtx<Ctx> + SizeWith<Ctx>,
+    {
+        self.try_par_for_each_ordered(|_, output| w.iowrite_with(output, ctx))
+            .map_err(TransducerError::from)
+    }
+}
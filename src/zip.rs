@@ -0,0 +1,247 @@
+use std::io;
+
+use TransducerError;
+use lazy_transducer::capacity_for;
+
+const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+const EOCD_LEN: usize = 22;
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x0706_4b50;
+const ZIP64_LOCATOR_LEN: usize = 20;
+const ZIP64_EOCD_SIGNATURE: u32 = 0x0606_4b50;
+const ZIP64_EOCD_LEN: usize = 56;
+const CENTRAL_SIGNATURE: u32 = 0x0201_4b50;
+const CENTRAL_LEN: usize = 46;
+const LOCAL_SIGNATURE: u32 = 0x0403_4b50;
+const LOCAL_LEN: usize = 30;
+/// The id of the extra field holding the 64-bit sizes and offset of an entry
+const ZIP64_EXTRA_ID: u16 = 0x0001;
+
+#[inline]
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+#[inline]
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+#[inline]
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    u32_at(bytes, offset) as u64 | (u32_at(bytes, offset + 4) as u64) << 32
+}
+
+fn invalid(msg: &'static str) -> TransducerError {
+    io::Error::new(io::ErrorKind::InvalidData, msg).into()
+}
+
+/// Returns the offset of the central directory and its number of entries, from the end of
+/// central directory record, or its ZIP64 counterpart
+fn central_directory(bytes: &[u8]) -> Result<(usize, usize), TransducerError> {
+    // the record is at the very end, unless the archive has a comment of at most 64 KiB
+    let min = bytes.len().saturating_sub(EOCD_LEN + u16::MAX as usize);
+    let eocd = (min..(bytes.len() + 1).saturating_sub(EOCD_LEN)).rev()
+        .find(|offset| u32_at(bytes, *offset) == EOCD_SIGNATURE && offset + EOCD_LEN + u16_at(bytes, offset + 20) as usize <= bytes.len())
+        .ok_or_else(|| invalid("no end of central directory record"))?;
+    if u16_at(bytes, eocd + 4) != 0 || u16_at(bytes, eocd + 6) != 0 {
+        return Err(invalid("multi-disk archives are not supported"))
+    }
+    let (count, start) = (u16_at(bytes, eocd + 10), u32_at(bytes, eocd + 16));
+    // a saturated field defers to the ZIP64 record, if there is one
+    let locator = eocd.checked_sub(ZIP64_LOCATOR_LEN).filter(|locator| u32_at(bytes, *locator) == ZIP64_LOCATOR_SIGNATURE);
+    match locator {
+        Some(locator) if count == u16::MAX || start == u32::MAX => {
+            let zip64 = u64_at(bytes, locator + 8) as usize;
+            if locator < ZIP64_EOCD_LEN || zip64 > locator - ZIP64_EOCD_LEN || u32_at(bytes, zip64) != ZIP64_EOCD_SIGNATURE {
+                return Err(invalid("no ZIP64 end of central directory record"))
+            }
+            Ok((u64_at(bytes, zip64 + 48) as usize, u64_at(bytes, zip64 + 32) as usize))
+        },
+        _ => Ok((start as usize, count as usize)),
+    }
+}
+
+/// A file header of the central directory of a [ZipTransducer](struct.ZipTransducer.html)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZipEntry<'a> {
+    /// The path of the entry, which is a directory if it ends with `/`
+    pub name: &'a [u8],
+    /// The general purpose flags, e.g., bit 0 is set if the entry is encrypted
+    pub flags: u16,
+    /// The compression method, e.g., 0 for stored, or 8 for deflated
+    pub method: u16,
+    /// The MS-DOS modification time
+    pub dos_time: u16,
+    /// The MS-DOS modification date
+    pub dos_date: u16,
+    /// The CRC-32 of the uncompressed contents
+    pub crc32: u32,
+    /// The size of the compressed contents
+    pub compressed_size: u64,
+    /// The size of the uncompressed contents
+    pub uncompressed_size: u64,
+    /// The byte offset of the local file header of the entry in the archive
+    pub local_header_offset: u64,
+    /// The host-dependent attributes, e.g., the Unix mode in the upper 16 bits
+    pub external_attributes: u32,
+    /// The extra fields
+    pub extra: &'a [u8],
+    /// The comment
+    pub comment: &'a [u8],
+}
+
+impl<'a> ZipEntry<'a> {
+    /// Whether this entry is a directory
+    pub fn is_dir(&self) -> bool {
+        self.name.last() == Some(&b'/')
+    }
+}
+
+/// A lazy transducer over the central directory of a ZIP archive, which parses a file header only
+/// when it is accessed, so that an archive can be listed, or its entries located, lazily and in
+/// parallel.
+///
+/// Construction locates the end of central directory record (or its ZIP64 counterpart), then
+/// records where every file header of the central directory lies in an index, so that afterwards,
+/// accessing an entry is O(1). Multi-disk archives are not supported.
+///
+/// # Example
+///
+/// ```rust
+/// use lazy_transducer::ZipTransducer;
+///
+/// // a stored, 2 byte file "a.txt"
+/// let mut zip = b"PK\x03\x04\x0a\0\0\0\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\x02\0\0\0\x05\0\0\0a.txthi".to_vec();
+/// zip.extend_from_slice(b"PK\x01\x02\x14\0\x0a\0\0\0\0\0\0\0\0\0\0\0\0\0\x02\0\0\0\x02\0\0\0\x05\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0a.txt");
+/// zip.extend_from_slice(b"PK\x05\x06\0\0\0\0\x01\0\x01\0\x33\0\0\0\x25\0\0\0\0\0");
+///
+/// let lt = ZipTransducer::new(&zip).unwrap();
+/// assert_eq!(lt.len(), 1);
+/// let entry = lt.get(0).unwrap().unwrap();
+/// assert_eq!(entry.name, b"a.txt");
+/// assert_eq!(entry.uncompressed_size, 2);
+/// assert_eq!(lt.compressed_data(0).unwrap().unwrap(), b"hi");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZipTransducer<'a> {
+    bytes: &'a [u8],
+    /// The offset of every file header of the central directory
+    offsets: Vec<usize>,
+}
+
+impl<'a> ZipTransducer<'a> {
+    /// Create a new lazy transducer over the central directory of the ZIP archive in `bytes`,
+    /// returning an `InvalidData` I/O error if its end of central directory record is missing, or
+    /// it spans multiple disks, and a `Parse` error with the index and offset of the first file
+    /// header which is malformed, or truncated.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, TransducerError> {
+        let (start, count) = central_directory(bytes)?;
+        let mut offsets = Vec::with_capacity(capacity_for(count, bytes.len(), CENTRAL_LEN));
+        let mut offset = start;
+        for idx in 0..count {
+            let malformed = |msg| TransducerError::Parse{ idx, offset, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) };
+            if bytes.len() < CENTRAL_LEN || offset > bytes.len() - CENTRAL_LEN {
+                return Err(malformed("truncated central directory file header"))
+            }
+            if u32_at(bytes, offset) != CENTRAL_SIGNATURE {
+                return Err(malformed("bad central directory file header signature"))
+            }
+            let len = CENTRAL_LEN + u16_at(bytes, offset + 28) as usize + u16_at(bytes, offset + 30) as usize + u16_at(bytes, offset + 32) as usize;
+            if len > bytes.len() - offset {
+                return Err(malformed("truncated central directory file header"))
+            }
+            offsets.push(offset);
+            offset += len;
+        }
+        Ok(ZipTransducer { bytes, offsets })
+    }
+    /// How many entries are contained in this lazy transducer
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+    /// Whether this lazy transducer has no entries
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+    /// Returns the byte offset of the central directory file header of the `idx`th entry, or
+    /// `None` if the index is greater than the number of entries.
+    pub fn offset_of(&self, idx: usize) -> Option<usize> {
+        self.offsets.get(idx).cloned()
+    }
+    /// Parse an entry out of the lazy transducer, returning `None` if the index is greater than
+    /// the number of entries in this lazy transducer, and a `Parse` error if its ZIP64 extra field
+    /// is missing or truncated.
+    pub fn get(&self, idx: usize) -> Option<Result<ZipEntry<'a>, TransducerError>> {
+        self.offsets.get(idx).map(|offset| self.parse(idx, *offset))
+    }
+    fn parse(&self, idx: usize, offset: usize) -> Result<ZipEntry<'a>, TransducerError> {
+        let bytes = self.bytes;
+        let (name_len, extra_len, comment_len) = (u16_at(bytes, offset + 28) as usize, u16_at(bytes, offset + 30) as usize, u16_at(bytes, offset + 32) as usize);
+        let name = offset + CENTRAL_LEN;
+        let (extra, comment) = (name + name_len, name + name_len + extra_len);
+        let mut entry = ZipEntry {
+            name: &bytes[name..extra],
+            flags: u16_at(bytes, offset + 8),
+            method: u16_at(bytes, offset + 10),
+            dos_time: u16_at(bytes, offset + 12),
+            dos_date: u16_at(bytes, offset + 14),
+            crc32: u32_at(bytes, offset + 16),
+            compressed_size: u32_at(bytes, offset + 20) as u64,
+            uncompressed_size: u32_at(bytes, offset + 24) as u64,
+            local_header_offset: u32_at(bytes, offset + 42) as u64,
+            external_attributes: u32_at(bytes, offset + 38),
+            extra: &bytes[extra..comment],
+            comment: &bytes[comment..comment + comment_len],
+        };
+        // the ZIP64 extra field holds, in order, whichever of these overflowed 32 bits
+        let mut extra = entry.extra;
+        let mut fields = [&mut entry.uncompressed_size, &mut entry.compressed_size, &mut entry.local_header_offset];
+        if fields.iter().any(|field| **field == u32::MAX as u64) {
+            let zip64 = loop {
+                if extra.len() < 4 {
+                    break None
+                }
+                let (id, len) = (u16_at(extra, 0), u16_at(extra, 2) as usize);
+                let data = extra.get(4..4 + len);
+                if id == ZIP64_EXTRA_ID {
+                    break data
+                }
+                extra = extra.get(4 + len..).unwrap_or(&[]);
+            };
+            let mut zip64 = zip64.unwrap_or(&[]);
+            for field in fields.iter_mut().filter(|field| ***field == u32::MAX as u64) {
+                if zip64.len() < 8 {
+                    let err = io::Error::new(io::ErrorKind::InvalidData, "missing or truncated ZIP64 extra field");
+                    return Err(TransducerError::Parse{ idx, offset, source: Box::new(err) })
+                }
+                **field = u64_at(zip64, 0);
+                zip64 = &zip64[8..];
+            }
+        }
+        Ok(entry)
+    }
+    /// Returns the compressed contents of the `idx`th entry, which follow its local file header,
+    /// or `None` if the index is greater than the number of entries, and a `Parse` error if the
+    /// local file header is malformed, or the contents are truncated.
+    pub fn compressed_data(&self, idx: usize) -> Option<Result<&'a [u8], TransducerError>> {
+        let offset = self.offset_of(idx)?;
+        Some(self.parse(idx, offset).and_then(|entry| {
+            let malformed = |msg| TransducerError::Parse{ idx, offset: entry.local_header_offset as usize, source: Box::new(io::Error::new(io::ErrorKind::InvalidData, msg)) };
+            let local = self.bytes.get(entry.local_header_offset as usize..).filter(|local| local.len() >= LOCAL_LEN).ok_or_else(|| malformed("truncated local file header"))?;
+            if u32_at(local, 0) != LOCAL_SIGNATURE {
+                return Err(malformed("bad local file header signature"))
+            }
+            let start = LOCAL_LEN + u16_at(local, 26) as usize + u16_at(local, 28) as usize;
+            (start as u64).checked_add(entry.compressed_size).filter(|end| *end <= local.len() as u64)
+                .map(|end| &local[start..end as usize])
+                .ok_or_else(|| malformed("truncated contents"))
+        }))
+    }
+}
+
+borrowed_transducer! {
+    /// Returns a lazy transducer over the parsed entries, borrowing this one.
+    impl['a] ZipTransducer<'a> => <'b> Result<ZipEntry<'a>, TransducerError>
+    where []
+    parallel where []
+}
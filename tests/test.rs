@@ -2,13 +2,30 @@ extern crate lazy_transducer;
 #[macro_use]
 extern crate scroll;
 extern crate rayon;
+#[cfg(feature = "pod")]
+extern crate bytemuck;
+#[cfg(feature = "bincode")]
+extern crate bincode;
+#[cfg(feature = "prost")]
+extern crate prost;
+#[cfg(feature = "arrow")]
+extern crate arrow_array;
+#[cfg(feature = "arrow")]
+extern crate arrow_ipc;
+#[cfg(feature = "nom")]
+extern crate nom;
+#[cfg(feature = "uuid")]
+extern crate uuid;
 
 use rayon::prelude::*;
-use lazy_transducer::{LazyTransducer, ScrollTransducer};
+use lazy_transducer::{CancelToken, LazyTransducer, TryLazyTransducer};
+#[cfg(feature = "scroll")]
+use lazy_transducer::ScrollTransducer;
 
 use std::mem::size_of;
 use std::str;
 use scroll::{Pread, IOwrite, LE, BE};
+#[cfg(feature = "scroll")]
 use std::io::Cursor;
 
 #[derive(Debug, Copy, Clone, Default, Pread, Pwrite, SizeWith, IOwrite, IOread)]
@@ -18,6 +35,7 @@ struct Derp {
     two: u16,
 }
 
+#[cfg(feature = "scroll")]
 #[test]
 fn parallel_scroll_transducer() {
     let derp = Derp::default();
@@ -28,19 +46,13 @@ fn parallel_scroll_transducer() {
     bytes.iowrite(derp).unwrap();
     bytes.iowrite(derp).unwrap();
     let bytes = bytes.into_inner();
-    let lt: ScrollTransducer<Derp, _> = ScrollTransducer::parse_with(&bytes, 5, LE).unwrap();
+    let lt: ScrollTransducer<Derp, _> = ScrollTransducer::parse_with(&bytes[..], 5, LE).unwrap();
     let derps: Vec<Derp> = lt.into_par_iter().map(|mut derp| { derp.one = 0xdeadbeef; derp }).collect();
     for d in derps {
         assert_eq!(d.one, 0xdeadbeef);
     }
 }
 
-#[derive(Debug, Default)]
-struct Derp2 {
-    one: usize,
-    two: f64,
-}
-
 fn get_str(input: &[u8; 15], pos: usize) -> &str {
     input.pread::<&str>(pos).unwrap()
 }
@@ -51,7 +63,6 @@ fn lifetime_transducer() {
     let lt: LazyTransducer<&[u8; 15], &str> = LazyTransducer::new(bytes, bytes.len(), get_str);
     for (idx, res) in lt.into_iter().enumerate() {
         println!("{}: {:?}", idx, res);
-        assert!(true);
     }
 }
 
@@ -94,7 +105,2105 @@ fn basic_transducer() {
         }
     }
 
-    let ns1: Vec<_> = lt.clone().into_par_iter().collect();
-    let ns2: Vec<_> = lt.clone().into_iter().collect();
+    let ns1: Vec<_> = lt.into_par_iter().collect();
+    let ns2: Vec<_> = lt.into_iter().collect();
     assert_eq!(ns1.len(), ns2.len());
 }
+
+#[test]
+fn macro_by_path_with_trailing_semicolon() {
+    let data = [1u32, 2, 3];
+    let lt: LazyTransducer<_, u32> = lazy_transducer::lazy_transducer!(&data; count = 3; |input, idx| input[idx] + 1;);
+    assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![2, 3, 4]);
+}
+
+#[test]
+fn zip_transducer() {
+    let xs = [1u32, 2, 3, 4];
+    let ys = [10u64, 20, 30];
+    let left: LazyTransducer<&[u32], u32> = LazyTransducer::new(&xs, xs.len(), |input, idx| input[idx]);
+    let right: LazyTransducer<&[u64], u64> = LazyTransducer::new(&ys, ys.len(), |input, idx| input[idx]);
+    let zipped = left.zip(right);
+    assert_eq!(zipped.len(), 3);
+    assert!(zipped.get(3).is_none());
+    let seq: Vec<_> = zipped.into_iter().collect();
+    let par: Vec<_> = zipped.into_par_iter().collect();
+    assert_eq!(seq, vec![(1, 10), (2, 20), (3, 30)]);
+    assert_eq!(seq, par);
+}
+
+#[test]
+fn mapped_transducer() {
+    let data: Vec<u32> = (0..1000).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let squares = lt.map(|n| n as u64 * n as u64);
+    assert_eq!(squares.len(), 1000);
+    assert_eq!(squares.get(999), Some(998_001));
+    assert!(squares.get(1000).is_none());
+    // maps compose, and the result is still parallel
+    let strings = squares.map(|n| n.to_string());
+    let par: Vec<String> = strings.into_par_iter().collect();
+    assert_eq!(par[12], "144");
+    assert_eq!(par, strings.into_iter().collect::<Vec<_>>());
+    let empty: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, 0, |input, idx| input[idx]);
+    assert!(empty.map(|n| n + 1).get(0).is_none());
+}
+
+#[test]
+fn filtered_transducer() {
+    let data: Vec<u32> = (0..1000).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let threes = lt.filter_indexed(|n| n % 3 == 0);
+    let par_threes = lt.par_filter_indexed(|n| n % 3 == 0);
+    assert_eq!(threes.len(), 334);
+    assert_eq!(par_threes.len(), 334);
+    for (i, n) in threes.into_iter().enumerate() {
+        assert_eq!(n, i as u32 * 3);
+        assert_eq!(threes.index_of(i), Some(i * 3));
+    }
+    assert!(threes.get(334).is_none());
+    let ns: Vec<_> = par_threes.into_par_iter().collect();
+    assert_eq!(ns, (0..1000).filter(|n| n % 3 == 0).collect::<Vec<_>>());
+    assert!(lt.filter_indexed(|_| false).is_empty());
+    // a count which is a multiple of the word size has no partial word
+    let words: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, 128, |input, idx| input[idx]);
+    let all = words.filter_indexed(|_| true);
+    assert_eq!(all.len(), 128);
+    assert_eq!(all.index_of(127), Some(127));
+    assert!(all.index_of(128).is_none());
+}
+
+#[test]
+fn windowed_transducer() {
+    let data = [1u8, 2, 3, 4];
+    let lt: LazyTransducer<&[u8], u8> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let windows: Vec<Vec<u8>> = lt.windows(2).into_iter().map(|w| w.into_iter().collect()).collect();
+    assert_eq!(windows, vec![vec![1, 2], vec![2, 3], vec![3, 4]]);
+    assert_eq!(lt.windows(4).len(), 1);
+    assert_eq!(lt.windows(5).len(), 0);
+    assert!(lt.windows(5).get(0).is_none());
+}
+
+#[test]
+fn reversed_transducer() {
+    let data = [1u16, 2, 3, 4, 5];
+    let lt: LazyTransducer<&[u16], u16> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let reversed: Vec<_> = lt.into_iter().rev().collect();
+    assert_eq!(reversed, vec![5, 4, 3, 2, 1]);
+    assert_eq!(lt.into_iter().rfind(|n| n % 2 == 0), Some(4));
+    let mut iter = lt.into_iter();
+    assert_eq!(iter.next(), Some(1));
+    assert_eq!(iter.next_back(), Some(5));
+    assert_eq!(iter.next_back(), Some(4));
+    assert_eq!(iter.next(), Some(2));
+    assert_eq!(iter.next(), Some(3));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn exact_size_hint() {
+    let data = [1u16, 2, 3];
+    let lt: LazyTransducer<&[u16], u16> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let mut iter = lt.into_iter();
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    iter.next();
+    iter.next_back();
+    assert_eq!(iter.size_hint(), (1, Some(1)));
+    iter.next();
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn exact_size_len_after_partial_consumption() {
+    let data = [1u16, 2, 3, 4];
+    let lt: LazyTransducer<&[u16], u16> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let mut iter = lt.into_iter();
+    assert_eq!(iter.len(), 4);
+    iter.next();
+    assert_eq!(iter.len(), 3);
+    iter.next_back();
+    assert_eq!(iter.len(), 2);
+    let rest: Vec<_> = iter.by_ref().collect();
+    assert_eq!(rest, vec![2, 3]);
+    assert_eq!(iter.len(), 0);
+
+    let mut iter = (&lt).into_iter();
+    iter.next();
+    let pairs: Vec<_> = iter.zip(lt).collect();
+    assert_eq!(pairs, vec![(2, 1), (3, 2), (4, 3)]);
+}
+
+#[test]
+fn random_access_nth_and_last() {
+    let data: Vec<u32> = (0..100).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let mut iter = lt.into_iter();
+    assert_eq!(iter.nth(10), Some(10));
+    assert_eq!(iter.next(), Some(11));
+    assert_eq!(iter.nth(87), Some(99));
+    assert_eq!(iter.next(), None);
+    let mut iter = lt.into_iter();
+    assert_eq!(iter.nth(100), None);
+    assert_eq!(iter.next(), None);
+    assert_eq!(lt.into_iter().last(), Some(99));
+    let mut iter = lt.into_iter();
+    iter.next_back();
+    assert_eq!(iter.last(), Some(98));
+    assert_eq!(lt.windows(101).into_iter().last().map(|w| w.len()), None);
+}
+
+#[test]
+fn borrowed_iter_matches_into_iter() {
+    let data: Vec<u32> = (0..10).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    assert_eq!(lt.iter().collect::<Vec<_>>(), lt.into_iter().collect::<Vec<_>>());
+    assert_eq!(lt.iter().rev().collect::<Vec<_>>(), lt.into_iter().rev().collect::<Vec<_>>());
+    let mut iter = lt.iter();
+    assert_eq!(iter.len(), 10);
+    assert_eq!(iter.nth(3), Some(3));
+    assert_eq!(iter.next_back(), Some(9));
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    assert_eq!(iter.nth(5), None);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(lt.iter().last(), Some(9));
+    let empty: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, 0, |input, idx| input[idx]);
+    assert_eq!(empty.iter().next(), None);
+    assert_eq!(empty.iter().last(), None);
+    // `for` over a reference borrows the transducer, which is still usable afterwards
+    let mut sum = 0;
+    for n in &lt {
+        sum += n;
+    }
+    assert_eq!(sum, 45);
+    assert_eq!(lt.get(9), Some(9));
+}
+
+#[test]
+fn indexed_parallel_transducer() {
+    let data: Vec<u32> = (0..1000).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let mut ns = Vec::new();
+    lt.into_par_iter().collect_into_vec(&mut ns);
+    assert_eq!(ns, data);
+    let enumerated: Vec<(usize, u32)> = lt.into_par_iter().enumerate().collect();
+    assert!(enumerated.iter().all(|&(i, n)| i as u32 == n));
+    assert_eq!(lt.into_par_iter().position_any(|n| n == 777), Some(777));
+    let sums: Vec<u32> = lt.into_par_iter().zip(lt.into_par_iter().rev()).map(|(a, b)| a + b).collect();
+    assert!(sums.iter().all(|&n| n == 999));
+}
+
+#[test]
+fn borrowed_parallel_iteration() {
+    struct Table<'a> {
+        entries: LazyTransducer<'a, &'a [u32], u32>,
+    }
+    let data: Vec<u32> = (0..1000).collect();
+    let table = Table { entries: LazyTransducer::new(&data, data.len(), |input, idx| input[idx]) };
+    // iterating by reference neither moves nor exhausts the stored transducer
+    let sum: u32 = table.entries.par_iter().sum();
+    assert_eq!(sum, 499_500);
+    assert_eq!(table.entries.par_iter().len(), 1000);
+    let odd: Vec<u32> = table.entries.par_iter().filter(|n| n % 2 == 1).collect();
+    assert_eq!(odd.len(), 500);
+    let mut ns = Vec::new();
+    table.entries.par_iter().rev().collect_into_vec(&mut ns);
+    assert_eq!(ns.first(), Some(&999));
+    assert_eq!(ns.last(), Some(&0));
+}
+
+#[test]
+fn parallel_iteration_in_a_thread_pool() {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(2).thread_name(|i| format!("lt-pool-{}", i)).build().unwrap();
+    let data: Vec<u32> = (0..10_000).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    // every element is transduced on one of the pool's threads
+    let threads: Vec<String> = lt.par_iter_in(&pool, |iter| {
+        iter.map(|_| std::thread::current().name().unwrap_or("").to_string()).collect()
+    });
+    assert_eq!(threads.len(), data.len());
+    assert!(threads.iter().all(|name| name.starts_with("lt-pool-")));
+    let max = lt.par_iter_in(&pool, |iter| iter.max());
+    assert_eq!(max, Some(9999));
+}
+
+#[test]
+fn preallocated_parallel_collect() {
+    let data: Vec<u32> = (0..100_000).collect();
+    let lt: LazyTransducer<&[u32], u64> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx] as u64 * 2);
+    let ns = lt.par_collect_vec();
+    assert_eq!(ns.len(), data.len());
+    // the vector is allocated once, with exactly enough room
+    assert_eq!(ns.capacity(), data.len());
+    assert!(ns.iter().enumerate().all(|(i, n)| *n == i as u64 * 2));
+    let empty: LazyTransducer<&[u32], u64> = LazyTransducer::new(&data, 0, |input, idx| input[idx] as u64);
+    assert!(empty.par_collect_vec().is_empty());
+}
+
+#[test]
+fn parallel_reductions() {
+    let data: Vec<u32> = (1..=10_000).collect();
+    let lt: LazyTransducer<&[u32], u64> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx] as u64);
+    assert_eq!(lt.par_sum::<u64>(), 50_005_000);
+    let (min, max) = lt.par_fold(|| (u64::MAX, 0), |(min, max), n| (min.min(n), max.max(n)),
+                                 |a, b| (a.0.min(b.0), a.1.max(b.1)));
+    assert_eq!((min, max), (1, 10_000));
+    assert_eq!(lt.par_reduce_with(|a, b| a.max(b)), Some(10_000));
+    let empty: LazyTransducer<&[u32], u64> = LazyTransducer::new(&data, 0, |input, idx| input[idx] as u64);
+    assert_eq!(empty.par_sum::<u64>(), 0);
+    assert_eq!(empty.par_fold(|| 7u64, |acc, n| acc + n, |a, b| a.max(b)), 7);
+    assert_eq!(empty.par_reduce_with(|a, b| a + b), None);
+}
+
+#[test]
+fn parallel_searches_find_the_lowest_index() {
+    // every element from 5000 onwards matches, so only the lowest index is correct
+    let data: Vec<u32> = (0..100_000).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    for _ in 0..10 {
+        assert_eq!(lt.par_find_first(|n| *n >= 5000), Some(5000));
+        assert_eq!(lt.par_position(|n| *n >= 5000), Some(5000));
+    }
+    assert_eq!(lt.par_find_first(|n| *n == 0), Some(0));
+    assert_eq!(lt.par_position(|n| *n == 99_999), Some(99_999));
+    assert_eq!(lt.par_find_first(|n| *n > 100_000), None);
+    assert_eq!(lt.par_position(|n| *n > 100_000), None);
+}
+
+#[test]
+fn try_for_each_stops_at_the_first_error() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    let data: Vec<u32> = (0..1_000_000).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let mut seen = 0;
+    assert_eq!(lt.try_for_each(|n| if n == 10 { Err(n) } else { seen += 1; Ok(()) }), Err(10));
+    assert_eq!(seen, 10);
+    assert_eq!(lt.try_for_each(|_| Ok::<(), ()>(())), Ok(()));
+    // once the first element fails, outstanding work is abandoned
+    let calls = AtomicUsize::new(0);
+    let res = lt.try_par_for_each(|n| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        if n == 0 { Err(n) } else { Ok(()) }
+    });
+    assert_eq!(res, Err(0));
+    assert!(calls.load(Ordering::Relaxed) < data.len());
+    assert_eq!(lt.try_par_for_each(|_| Ok::<(), ()>(())), Ok(()));
+    // a failure to extract an element stops a fallible transducer too
+    let fallible: TryLazyTransducer<&[u32], u32, u32> = TryLazyTransducer::new(&data, data.len(), |input, idx| {
+        if idx == 500_000 { Err(input[idx]) } else { Ok(input[idx]) }
+    });
+    assert_eq!(fallible.try_par_for_each(|_| Ok(())), Err(500_000));
+    assert_eq!(fallible.try_for_each(|n| if n == 3 { Err(n) } else { Ok(()) }), Err(3));
+}
+
+#[test]
+fn ordered_parallel_for_each() {
+    let data: Vec<u32> = (0..10_000).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let mut expected = 0;
+    lt.par_for_each_ordered(|idx, n| {
+        assert_eq!(idx, expected);
+        assert_eq!(n as usize, idx);
+        expected += 1;
+    });
+    assert_eq!(expected, data.len());
+}
+
+#[test]
+fn cancelled_parallel_iteration() {
+    let data: Vec<u32> = (0..100_000).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    // an uncancelled token yields every element
+    let cancel = CancelToken::new();
+    assert!(!cancel.is_cancelled());
+    assert_eq!(lt.par_iter_cancellable(&cancel).count(), data.len());
+    // cancelling a clone cancels the original
+    cancel.clone().cancel();
+    assert!(cancel.is_cancelled());
+    assert_eq!(lt.par_iter_cancellable(&cancel).count(), 0);
+    // cancelling mid-iteration returns the elements produced so far
+    let cancel = CancelToken::new();
+    let partial: Vec<u32> = lt.par_iter_cancellable(&cancel).map(|n| {
+        if n == 100 { cancel.cancel(); }
+        n
+    }).collect();
+    assert!(partial.contains(&100));
+    assert!(partial.len() < data.len());
+    let empty: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, 0, |input, idx| input[idx]);
+    assert_eq!(empty.par_iter_cancellable(&CancelToken::new()).count(), 0);
+}
+
+#[test]
+fn fallible_transducer() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    let lt: TryLazyTransducer<&[u8], u16, scroll::Error> = TryLazyTransducer::new(&bytes, 128, |input, idx| {
+        input.pread_with::<u16>(idx * 2, LE)
+    });
+    let sum = lt.try_par_fold(|| 0u64, |acc, n| Ok(acc + n as u64), |a, b| Ok(a + b)).unwrap();
+    let expected: u64 = lt.into_iter().map(|n| n.unwrap() as u64).sum();
+    assert_eq!(sum, expected);
+    assert_eq!(lt.try_fold(0u64, |acc, n| Ok(acc + n as u64)).unwrap(), expected);
+
+    let truncated: TryLazyTransducer<&[u8], u16, scroll::Error> = TryLazyTransducer::new(&bytes, 129, |input, idx| {
+        input.pread_with::<u16>(idx * 2, LE)
+    });
+    assert!(truncated.try_par_for_each(|_| Ok(())).is_err());
+    assert!(truncated.get(129).is_none());
+    assert!(!truncated.is_empty());
+    let empty: TryLazyTransducer<&[u8], u16, scroll::Error> = TryLazyTransducer::new(&bytes, 0, |input, idx| {
+        input.pread_with::<u16>(idx * 2, LE)
+    });
+    assert!(empty.is_empty() && empty.into_inner().is_empty());
+}
+
+#[test]
+fn partitioned_results() {
+    let data: Vec<u32> = (0..1000).collect();
+    let lt: TryLazyTransducer<&[u32], u32, usize> = TryLazyTransducer::new(&data, data.len(), |input, idx| {
+        if input[idx] % 7 == 0 { Err(idx) } else { Ok(input[idx]) }
+    });
+    let (oks, errs) = lt.partition_results();
+    let (par_oks, par_errs) = lt.par_partition_results();
+    assert_eq!(oks, par_oks);
+    assert_eq!(errs, par_errs);
+    assert_eq!(oks.len() + errs.len(), 1000);
+    assert!(errs.iter().all(|&(idx, err)| idx == err && idx % 7 == 0));
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn scroll_transducer_size_overflow() {
+    let bytes = [0u8; 16];
+    let res: Result<ScrollTransducer<u64>, _> = ScrollTransducer::parse_with(&bytes, usize::MAX / 4, LE);
+    match res {
+        Err(lazy_transducer::TransducerError::ArithmeticOverflow{ .. }) => (),
+        _ => panic!("expected an arithmetic overflow"),
+    }
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn scroll_transducer_stride_overflow() {
+    let bytes = [0u8; 16];
+    let res: Result<ScrollTransducer<u32>, _> = ScrollTransducer::parse_strided(&bytes, 3, usize::MAX / 2, LE);
+    match res {
+        Err(err @ lazy_transducer::TransducerError::ArithmeticOverflow{ nelements: 3, sizeof_element: 4, .. }) => {
+            assert!(err.to_string().contains("bytes apart"));
+        },
+        res => panic!("expected an arithmetic overflow, got {:?}", res.map(|lt| lt.len())),
+    }
+}
+
+#[test]
+fn checked_transducer_bounds() {
+    let bytes = [1u8, 0, 2, 0, 3, 0];
+    let read = |input: &[u8], idx| input[idx * 2] as u16 | (input[idx * 2 + 1] as u16) << 8;
+    let lt: LazyTransducer<&[u8], u16> = LazyTransducer::new_checked(&bytes[..], 3, 2, read).unwrap();
+    assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    let empty: LazyTransducer<&[u8], u16> = LazyTransducer::new_checked(&bytes[..0], 0, 2, read).unwrap();
+    assert!(empty.is_empty());
+    match LazyTransducer::new_checked(&bytes[..], 4, 2, read) {
+        Err(lazy_transducer::TransducerError::ElementOverflow{ nelements: 4, sizeof_element: 2, stride: 2, src_size: 6 }) => (),
+        res => panic!("expected an element overflow, got {:?}", res.map(|lt| lt.len())),
+    }
+    match LazyTransducer::new_checked(&bytes[..], usize::MAX, 2, read) {
+        Err(lazy_transducer::TransducerError::ArithmeticOverflow{ sizeof_element: 2, .. }) => (),
+        res => panic!("expected an arithmetic overflow, got {:?}", res.map(|lt| lt.len())),
+    }
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn builder_offset_scroll_transducer() {
+    let derp = Derp { one: 0xdeadbeef, two: 7 };
+    let mut bytes = Cursor::new(vec![0xff; 3]);
+    bytes.set_position(3);
+    bytes.iowrite_with(derp, BE).unwrap();
+    bytes.iowrite_with(derp, BE).unwrap();
+    let bytes = bytes.into_inner();
+    let lt: ScrollTransducer<Derp, _> = lazy_transducer::Builder::new(&bytes[..]).offset(3).count(2).parse_with(BE).unwrap();
+    assert_eq!(lt.len(), 2);
+    for derp in lt {
+        assert_eq!(derp.one, 0xdeadbeef);
+        assert_eq!(derp.two, 7);
+    }
+    let too_many: Result<ScrollTransducer<Derp, _>, _> = lazy_transducer::Builder::new(&bytes[..]).offset(4).count(2).parse_with(BE);
+    assert!(too_many.is_err());
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn builder_strided_scroll_transducer() {
+    // interleaved `u16` and `u32` records, of which we only want the `u32`s
+    let mut bytes = Cursor::new(Vec::new());
+    for i in 0..4u32 {
+        bytes.iowrite_with(i as u16, LE).unwrap();
+        bytes.iowrite_with(i * 0x1000, LE).unwrap();
+    }
+    let bytes = bytes.into_inner();
+    let lt: ScrollTransducer<u32, _> = lazy_transducer::Builder::new(&bytes[..]).offset(2).stride(6).count(4).parse_with(LE).unwrap();
+    assert_eq!(lt.into_iter().collect::<Vec<_>>(), vec![0, 0x1000, 0x2000, 0x3000]);
+    let too_many: Result<ScrollTransducer<u32, _>, _> = lazy_transducer::Builder::new(&bytes[..]).offset(2).stride(6).count(5).parse_with(LE);
+    match too_many {
+        Err(err @ lazy_transducer::TransducerError::ElementOverflow{ nelements: 5, sizeof_element: 4, stride: 6, src_size: 22 }) => {
+            assert_eq!(err.to_string(), "Too many elements (size = 5 * 4, 6 bytes apart) requested from src of size: 22");
+        },
+        res => panic!("expected an element overflow, got {:?}", res.map(|lt| lt.len())),
+    }
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn builder_truncation_policy() {
+    use lazy_transducer::{Builder, TransducerError, TruncationPolicy};
+    // a header claiming 4 `u32`s, of which only 2 and a half fit
+    let bytes = [1u8, 0, 0, 0, 2, 0, 0, 0, 3, 0];
+    assert_eq!(TruncationPolicy::default(), TruncationPolicy::Strict);
+    let strict: Result<ScrollTransducer<u32, _>, _> = Builder::new(&bytes[..]).count(4).parse_with(LE);
+    match strict {
+        Err(TransducerError::ElementOverflow{ nelements: 4, sizeof_element: 4, stride: 4, src_size: 10 }) => (),
+        res => panic!("expected an element overflow, got {:?}", res.map(|lt| lt.len())),
+    }
+    let clamped: ScrollTransducer<u32, _> = Builder::new(&bytes[..]).count(4).policy(TruncationPolicy::ClampToSource).parse_with(LE).unwrap();
+    assert_eq!(clamped.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    // the policy accounts for the offset and stride, and never clamps up
+    let strided: ScrollTransducer<u16, _> = Builder::new(&bytes[..]).offset(4).stride(4).count(4).policy(TruncationPolicy::ClampToSource).parse_with(LE).unwrap();
+    assert_eq!(strided.into_iter().collect::<Vec<_>>(), vec![2, 3]);
+    let fits: ScrollTransducer<u16, _> = Builder::new(&bytes[..]).count(1).policy(TruncationPolicy::ClampToSource).parse_with(LE).unwrap();
+    assert_eq!(fits.len(), 1);
+}
+
+/// A byte which must be a valid boolean
+#[cfg(feature = "scroll")]
+#[derive(Debug)]
+struct Flag(bool);
+
+#[cfg(feature = "scroll")]
+impl<'a> scroll::ctx::TryFromCtx<'a, scroll::Endian> for Flag {
+    type Error = scroll::Error;
+    fn try_from_ctx(src: &'a [u8], _: scroll::Endian) -> Result<(Self, usize), Self::Error> {
+        match src.pread::<u8>(0)? {
+            0 => Ok((Flag(false), 1)),
+            1 => Ok((Flag(true), 1)),
+            _ => Err(scroll::Error::BadInput{ size: 1, msg: "not a boolean" }),
+        }
+    }
+}
+
+#[cfg(feature = "scroll")]
+impl scroll::ctx::SizeWith<scroll::Endian> for Flag {
+    fn size_with(_: &scroll::Endian) -> usize {
+        1
+    }
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn scroll_transducer_parse_error() {
+    let bytes = [1u8, 0, 7, 1];
+    let lt: ScrollTransducer<Flag> = ScrollTransducer::parse_with_at(&bytes[..], 1, 3, LE).unwrap();
+    assert!(!lt.try_get(0).unwrap().0);
+    match lt.try_get(1) {
+        Err(lazy_transducer::TransducerError::Parse{ idx: 1, offset: 2, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert_eq!(lt.validate().unwrap_err().errors().len(), 1);
+}
+
+/// A zero-sized element
+#[cfg(feature = "scroll")]
+#[derive(Debug)]
+struct Marker;
+
+#[cfg(feature = "scroll")]
+impl<'a> scroll::ctx::TryFromCtx<'a, scroll::Endian> for Marker {
+    type Error = scroll::Error;
+    fn try_from_ctx(_: &'a [u8], _: scroll::Endian) -> Result<(Self, usize), Self::Error> {
+        Ok((Marker, 0))
+    }
+}
+
+#[cfg(feature = "scroll")]
+impl scroll::ctx::SizeWith<scroll::Endian> for Marker {
+    fn size_with(_: &scroll::Endian) -> usize {
+        0
+    }
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn zero_sized_scroll_transducer_is_empty() {
+    use lazy_transducer::{Builder, TruncationPolicy};
+
+    let bytes = [0u8; 4];
+    let lt: ScrollTransducer<Marker> = ScrollTransducer::parse(&bytes[..], LE).unwrap();
+    assert!(lt.is_empty());
+    let lt: ScrollTransducer<Marker> = ScrollTransducer::parse_with(&bytes[..], 3, LE).unwrap();
+    assert!(lt.get(0).is_none());
+    let lt: ScrollTransducer<Marker> = ScrollTransducer::parse_with_at(&bytes[..], 2, 3, LE).unwrap();
+    assert!(lt.is_empty());
+    let lt: ScrollTransducer<Marker> = ScrollTransducer::parse_with_policy(&bytes[..], 3, LE, TruncationPolicy::ClampToSource).unwrap();
+    assert!(lt.is_empty());
+    let lt: ScrollTransducer<Marker, _> = Builder::new(&bytes[..]).count(3).parse_with(LE).unwrap();
+    assert!(lt.is_empty());
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn fallible_scroll_transducer() {
+    // every third byte is not a boolean
+    let bytes: Vec<u8> = (0..30).map(|i| if i % 3 == 2 { 7 } else { (i % 2) as u8 }).collect();
+    let lt: ScrollTransducer<Flag> = ScrollTransducer::parse_with(&bytes[..], bytes.len(), LE).unwrap();
+    assert!(lt.try_get(1).unwrap().0);
+    match lt.try_get(30) {
+        Err(lazy_transducer::TransducerError::OutOfBounds{ idx: 30, len: 30 }) => (),
+        res => panic!("expected an out of bounds error, got {:?}", res),
+    }
+    let fallible = lt.into_fallible();
+    let results: Vec<_> = fallible.into_iter().collect();
+    assert_eq!(results.len(), 30);
+    for (idx, result) in results.iter().enumerate() {
+        match *result {
+            Err(lazy_transducer::TransducerError::Parse{ idx: err_idx, offset, .. }) => {
+                assert_eq!(idx % 3, 2);
+                assert_eq!((err_idx, offset), (idx, idx));
+            },
+            Ok(ref flag) => assert_eq!(flag.0, idx % 2 == 1),
+            Err(ref err) => panic!("unexpected error {}", err),
+        }
+    }
+    assert_eq!(fallible.into_par_iter().filter(|result| result.is_err()).count(), 10);
+}
+
+#[test]
+fn validation_reports_every_failure_in_order() {
+    let data: Vec<u32> = (0..100_000).collect();
+    let lt: TryLazyTransducer<&[u32], u32, u32> = TryLazyTransducer::new(&data, data.len(), |input, idx| {
+        if input[idx] % 1000 == 999 { Err(input[idx]) } else { Ok(input[idx]) }
+    });
+    let report = lt.validate().unwrap_err();
+    assert_eq!(report.errors().len(), 100);
+    // the report is in ascending index order, however the work was split
+    assert!(report.errors().iter().enumerate().all(|(i, &(idx, err))| idx == i * 1000 + 999 && err == idx as u32));
+    assert!(report.to_string().starts_with("100 element(s) failed validation\n  999: 999\n  1999: 1999"));
+    assert_eq!(report.clone().into_errors(), report.errors().to_vec());
+
+    let valid: TryLazyTransducer<&[u32], u32, u32> = TryLazyTransducer::new(&data, data.len(), |input, idx| Ok(input[idx]));
+    assert_eq!(valid.validate(), Ok(()));
+    let empty: TryLazyTransducer<&[u32], u32, u32> = TryLazyTransducer::new(&data, 0, |input, idx| Err(input[idx]));
+    assert_eq!(empty.validate(), Ok(()));
+}
+
+#[test]
+fn fail_fast_validation_reports_the_lowest_failure() {
+    let data: Vec<u32> = (0..100_000).collect();
+    // every element from the middle on fails, so the workers on the later halves fail first
+    let lt: TryLazyTransducer<&[u32], u32, u32> = TryLazyTransducer::new(&data, data.len(), |input, idx| {
+        if input[idx] >= 50_000 || input[idx] == 40_000 { Err(input[idx]) } else { Ok(input[idx]) }
+    });
+    for _ in 0..10 {
+        assert_eq!(lt.validate_fail_fast(), Err((40_000, 40_000)));
+    }
+    let valid: TryLazyTransducer<&[u32], u32, u32> = TryLazyTransducer::new(&data, data.len(), |input, idx| Ok(input[idx]));
+    assert_eq!(valid.validate_fail_fast(), Ok(()));
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn transducer_errors_chain_their_sources() {
+    use std::error::Error;
+    use lazy_transducer::TransducerError;
+    let bytes = [7u8];
+    let lt: ScrollTransducer<Flag> = ScrollTransducer::parse_with(&bytes[..], 1, LE).unwrap();
+    let err = lt.try_get(0).unwrap_err();
+    assert!(err.to_string().starts_with("Failed to parse element 0 at offset 0x0: "));
+    assert_eq!(err.source().map(|source| source.to_string()), Some(scroll::Error::BadInput{ size: 1, msg: "not a boolean" }.to_string()));
+    let io = TransducerError::from(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "short read"));
+    assert_eq!(io.to_string(), "I/O error: short read");
+    assert_eq!(io.source().unwrap().to_string(), "short read");
+    let oob = lt.try_get(1).unwrap_err();
+    assert_eq!(oob.to_string(), "Index 1 is out of bounds for 1 elements");
+    assert!(oob.source().is_none());
+}
+
+#[test]
+fn lenient_view_logs_each_failure_once() {
+    let data: Vec<u32> = (0..1000).collect();
+    let lt: TryLazyTransducer<&[u32], u32, u32> = TryLazyTransducer::new(&data, data.len(), |input, idx| {
+        if input[idx] % 100 == 0 { Err(input[idx]) } else { Ok(input[idx]) }
+    });
+    let lenient = lt.lenient();
+    assert_eq!(lenient.len(), 1000);
+    assert!(!lenient.is_empty());
+    assert_eq!(lenient.get(100), None);
+    assert_eq!(lenient.get(101), Some(101));
+    assert_eq!(lenient.skipped(), vec![100]);
+    assert_eq!(lenient.par_iter().count(), 990);
+    assert_eq!(lenient.iter().count(), 990);
+    // accessing the failures again doesn't log them twice
+    let errors = lenient.take_errors();
+    assert_eq!(errors, (0..10).map(|i| (i * 100, i as u32 * 100)).collect::<Vec<_>>());
+    assert!(lenient.skipped().is_empty());
+    assert_eq!(lenient.iter_or(7).nth(200), Some(7));
+    assert_eq!(lenient.skipped(), vec![0, 100, 200]);
+    assert!(TryLazyTransducer::<&[u32], u32, u32>::new(&data, 0, |input, idx| Ok(input[idx])).lenient().is_empty());
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn reencode_scroll_transducer() {
+    let mut bytes = Cursor::new(Vec::new());
+    for i in 0..1000 {
+        bytes.iowrite_with(Derp { one: i, two: i as u16 }, LE).unwrap();
+    }
+    let bytes = bytes.into_inner();
+    let lt: ScrollTransducer<Derp> = ScrollTransducer::parse(&bytes[..], LE).unwrap();
+    let mut seq = Vec::new();
+    let mut par = Vec::new();
+    lt.write_all_into(&mut seq, BE).unwrap();
+    lt.par_write_all_into(&mut par, BE).unwrap();
+    assert_eq!(seq, par);
+    let reencoded: ScrollTransducer<Derp> = ScrollTransducer::parse(&par[..], BE).unwrap();
+    assert_eq!(reencoded.len(), 1000);
+    assert_eq!(reencoded.get(999).unwrap().one, 999);
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn par_write_stops_at_the_first_error() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    let data: Vec<u32> = (0..100_000).collect();
+    let lt: LazyTransducer<&[u32], u32> = LazyTransducer::new(&data, data.len(), |input, idx| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        input[idx]
+    });
+    // room for two elements, after which the sink returns `WriteZero`
+    let mut buf = [0u8; 8];
+    match lt.par_write_all_into(&mut &mut buf[..], LE) {
+        Err(lazy_transducer::TransducerError::Io(err)) => assert_eq!(err.kind(), std::io::ErrorKind::WriteZero),
+        res => panic!("expected a write error, got {:?}", res),
+    }
+    assert_eq!(buf, [0, 0, 0, 0, 1, 0, 0, 0]);
+    // only the first batch was transduced
+    assert!(CALLS.load(Ordering::SeqCst) < data.len() / 2, "{} calls", CALLS.load(Ordering::SeqCst));
+}
+
+#[test]
+fn flat_map_empty_sections() {
+    static ENTRIES: [u32; 3] = [1, 2, 3];
+    let sections = [(0, 0), (0, 2), (2, 0), (2, 0), (2, 1), (3, 0)];
+    let lt: LazyTransducer<&[(usize, usize)], (usize, usize)> = LazyTransducer::new(&sections, sections.len(), |input, idx| input[idx]);
+    let flat = lt.flat_map_lazy(|(start, count)| LazyTransducer::new(&ENTRIES[start..start + count], count, |input, idx| input[idx]));
+    assert_eq!(flat.sections(), 6);
+    assert_eq!(flat.len(), 3);
+    assert_eq!(flat.locate(0), Some((1, 0)));
+    assert_eq!(flat.locate(2), Some((4, 0)));
+    assert_eq!(flat.locate(3), None);
+    assert_eq!(flat.into_par_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn paged_source_straddling_elements() {
+    use lazy_transducer::{PagedSource, Source, TransducerError};
+    let mut bytes = Cursor::new(Vec::new());
+    for i in 0..100 {
+        bytes.iowrite_with(Derp { one: i, two: i as u16 }, LE).unwrap();
+    }
+    // 10 byte elements in 16 byte pages, with only 2 cached pages
+    let source = PagedSource::with_capacity(Cursor::new(bytes.into_inner()), 16, 2).unwrap();
+    let lt: ScrollTransducer<Derp, _, _> = ScrollTransducer::parse_with(&source, 100, LE).unwrap();
+    for (i, derp) in lt.into_iter().enumerate() {
+        assert_eq!(derp.one, i as u64);
+        assert_eq!(derp.two, i as u16);
+    }
+    assert_eq!(source.page_reads(), 63);
+    match source.pread_with::<Derp, _, _>(99, 995, LE) {
+        Err(TransducerError::ElementOverflow{ sizeof_element: 10, src_size: 5, .. }) => (),
+        res => panic!("expected an element overflow, got {:?}", res),
+    }
+    assert_eq!(source.pages(), 63);
+    for n in &[63, u64::MAX] {
+        match source.page(*n) {
+            Err(TransducerError::OutOfBounds{ len: 63, .. }) => (),
+            res => panic!("expected an out of bounds error, got {:?}", res),
+        }
+    }
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn paged_source_memory_budget() {
+    use lazy_transducer::PagedSource;
+    let mut bytes = Cursor::new(Vec::new());
+    for i in 0..100 {
+        bytes.iowrite_with(Derp { one: i, two: i as u16 }, LE).unwrap();
+    }
+    // room for 3 of the 16 byte pages
+    let source = PagedSource::new(Cursor::new(bytes.into_inner()), 16).unwrap().with_memory_budget(48);
+    assert_eq!(source.memory_budget(), 48);
+    let lt: ScrollTransducer<Derp, _, _> = ScrollTransducer::parse_with(&source, 100, LE).unwrap();
+    for derp in lt.into_iter() {
+        assert!(source.resident_bytes() <= 48);
+        assert_eq!(derp.one, derp.two as u64);
+    }
+    assert_eq!(source.page_reads(), 63);
+
+    // the least recently used page is evicted, not the oldest
+    for n in &[0, 1, 2, 0, 3, 0] {
+        source.page(*n).unwrap();
+    }
+    assert_eq!(source.page_reads(), 67);
+    assert_eq!(source.resident_bytes(), 48);
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn chained_source_boundaries() {
+    use lazy_transducer::{ChainedSource, Source, TransducerError};
+    let (first, second) = ([0u8; 6], [1u8; 4]);
+    let source = ChainedSource::new(vec![&first[..], &[], &second[..]]);
+    assert_eq!(source.locate(6), Some((2, 0)));
+    assert_eq!(source.read(6, 4).unwrap(), &second[..]);
+    match source.pread_with::<u32, _, _>(1, 4, LE) {
+        Err(TransducerError::StraddlesBoundary{ offset: 4, len: 4, boundary: 6 }) => (),
+        res => panic!("expected a straddling error, got {:?}", res),
+    }
+    match source.read(8, 4) {
+        Err(TransducerError::ElementOverflow{ .. }) => (),
+        res => panic!("expected an overflow error, got {:?}", res),
+    }
+}
+
+#[test]
+fn chained_source_trailing_empty_segments() {
+    use lazy_transducer::{ChainedSource, TransducerError};
+    let bytes = [0u8; 6];
+    let source = ChainedSource::new(vec![&bytes[..], &[], &[]]);
+    assert_eq!(source.read(2, 4).unwrap(), &bytes[2..]);
+    // running into the empty segments is running off the end, not straddling a boundary
+    match source.read(4, 4) {
+        Err(TransducerError::ElementOverflow{ nelements: 1, sizeof_element: 4, src_size: 2, .. }) => (),
+        res => panic!("expected an overflow error, got {:?}", res),
+    }
+    assert!(source.read(6, 0).unwrap().is_empty());
+    let source = ChainedSource::new(vec![&[], &bytes[..4], &[], &bytes[4..], &[]]);
+    match source.read(2, 4) {
+        Err(TransducerError::StraddlesBoundary{ offset: 2, len: 4, boundary: 4 }) => (),
+        res => panic!("expected a straddling error, got {:?}", res),
+    }
+    match source.read(5, 2) {
+        Err(TransducerError::ElementOverflow{ .. }) => (),
+        res => panic!("expected an overflow error, got {:?}", res),
+    }
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn sparse_source_holes() {
+    use lazy_transducer::{Source, SparseSource, TransducerError};
+    let (first, second, third) = ([1u8; 4], [2u8; 4], [3u8; 2]);
+    // the segments are out of order, and the first two are adjacent
+    let source = SparseSource::new(32, vec![(20, &third[..]), (0, &first[..]), (4, &second[..])]).unwrap();
+    assert!(source.is_mapped(7) && !source.is_mapped(8) && !source.is_mapped(31));
+    assert_eq!(&*source.read(2, 4).unwrap(), &[1, 1, 2, 2]);
+    match source.pread_with::<u32, _, _>(4, 16, LE) {
+        Err(TransducerError::Hole{ offset: 8, len: 12 }) => (),
+        res => panic!("expected a hole error, got {:?}", res),
+    }
+    match source.read(21, 4) {
+        Err(TransducerError::Hole{ offset: 22, len: 10 }) => (),
+        res => panic!("expected a hole error, got {:?}", res),
+    }
+    let filled = source.clone().with_fill(0xff);
+    assert_eq!(&*filled.read(6, 16).unwrap(), &[2, 2, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 3, 3]);
+    assert_eq!(&*filled.read(28, 4).unwrap(), &[0xff; 4]);
+    assert!(filled.read(30, 4).is_err());
+    assert!(SparseSource::new(32, vec![(0, &first[..]), (2, &second[..])]).is_err());
+    assert!(SparseSource::new(4, vec![(2, &third[..]), (3, &third[..])]).is_err());
+}
+
+#[cfg(all(feature = "bgzf", feature = "scroll"))]
+#[test]
+fn bgzf_source_with_index() {
+    extern crate flate2;
+    use lazy_transducer::{BgzfSource, Source};
+    use std::io::Write;
+
+    fn bgzf_block(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::GzBuilder::new().extra(vec![b'B', b'C', 2, 0, 0, 0]).write(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        let mut block = encoder.finish().unwrap();
+        let bsize = block.len() - 1;
+        block[16] = bsize as u8;
+        block[17] = (bsize >> 8) as u8;
+        block
+    }
+
+    // 10 byte elements in 64 byte blocks, so that most of them straddle blocks
+    let mut data = Cursor::new(Vec::new());
+    for i in 0..100 {
+        data.iowrite_with(Derp { one: i, two: i as u16 }, LE).unwrap();
+    }
+    let data = data.into_inner();
+    let (mut bytes, mut gzi) = (Vec::new(), Vec::new());
+    for (n, chunk) in data.chunks(64).enumerate() {
+        if n > 0 {
+            gzi.iowrite_with(bytes.len() as u64, LE).unwrap();
+            gzi.iowrite_with(n as u64 * 64, LE).unwrap();
+        }
+        bytes.extend(bgzf_block(chunk));
+    }
+    let mut index = Vec::new();
+    index.iowrite_with(gzi.len() as u64 / 16, LE).unwrap();
+    index.extend(gzi);
+
+    let scanned = BgzfSource::new(&bytes).unwrap();
+    let indexed = BgzfSource::with_index(&bytes, &index).unwrap();
+    assert_eq!(scanned.blocks(), 16);
+    assert_eq!(indexed.len(), 1000);
+    for source in &[scanned, indexed] {
+        let lt: ScrollTransducer<Derp, _, _> = ScrollTransducer::parse_with(source, 100, LE).unwrap();
+        assert_eq!(lt.into_par_iter().map(|derp| derp.one).sum::<u64>(), 4950);
+        assert_eq!(source.pread_with::<Derp, _, _>(99, 990, LE).unwrap().two, 99);
+        assert!(source.pread_with::<Derp, _, _>(99, 995, LE).is_err());
+    }
+}
+
+#[cfg(all(feature = "zstd", feature = "scroll"))]
+#[test]
+fn zstd_source_with_seek_table() {
+    extern crate zstd;
+    use lazy_transducer::{Source, ZstdSource};
+
+    fn skippable_frame(magic: u32, data: &[u8]) -> Vec<u8> {
+        let mut frame = Vec::new();
+        frame.iowrite_with(magic, LE).unwrap();
+        frame.iowrite_with(data.len() as u32, LE).unwrap();
+        frame.extend_from_slice(data);
+        frame
+    }
+
+    // 10 byte elements in 64 byte frames, so that most of them straddle frames, with an empty
+    // frame and a skippable frame in between
+    let mut data = Cursor::new(Vec::new());
+    for i in 0..100 {
+        data.iowrite_with(Derp { one: i, two: i as u16 }, LE).unwrap();
+    }
+    let data = data.into_inner();
+    let mut frames: Vec<Vec<u8>> = data.chunks(64).map(|chunk| zstd::bulk::compress(chunk, 3).unwrap()).collect();
+    frames.insert(4, zstd::bulk::compress(&[], 3).unwrap());
+    frames.insert(8, skippable_frame(0x184D_2A50, b"metadata"));
+    let bytes = frames.concat();
+
+    let scanned = ZstdSource::new(&bytes).unwrap();
+    assert_eq!(scanned.frames(), 18);
+    assert_eq!(scanned.len(), 1000);
+    // the element at 60 spans the first two frames
+    assert_eq!(scanned.pread_with::<Derp, _, _>(6, 60, LE).unwrap().one, 6);
+    assert_eq!(scanned.decompressions(), 2);
+    // the element at 250 spans the empty frame
+    assert_eq!(scanned.pread_with::<Derp, _, _>(25, 250, LE).unwrap().two, 25);
+
+    let mut entries = Vec::new();
+    for frame in &frames {
+        let size = zstd::zstd_safe::get_frame_content_size(frame).unwrap().unwrap_or(0);
+        entries.iowrite_with(frame.len() as u32, LE).unwrap();
+        entries.iowrite_with(size as u32, LE).unwrap();
+    }
+    entries.iowrite_with(frames.len() as u32, LE).unwrap();
+    entries.push(0);
+    entries.iowrite_with(0x8F92_EAB1u32, LE).unwrap();
+    let seekable = [bytes.clone(), skippable_frame(0x184D_2A5E, &entries)].concat();
+    let indexed = ZstdSource::new(&seekable).unwrap();
+    // the frames come from the seek table, which isn't a frame itself
+    assert_eq!(indexed.frames(), 18);
+    assert_eq!(indexed.len(), 1000);
+
+    for source in &[scanned, indexed] {
+        let lt: ScrollTransducer<Derp, _, _> = ScrollTransducer::parse_with(source, 100, LE).unwrap();
+        assert_eq!(lt.into_par_iter().map(|derp| derp.one).sum::<u64>(), 4950);
+        assert_eq!(source.pread_with::<Derp, _, _>(99, 990, LE).unwrap().two, 99);
+        assert!(source.pread_with::<Derp, _, _>(99, 995, LE).is_err());
+    }
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn owned_transducer_outlives_its_bytes() {
+    use lazy_transducer::OwnedTransducer;
+
+    fn derps(n: u64) -> OwnedTransducer<Derp, scroll::Endian> {
+        let mut bytes = Cursor::new(Vec::new());
+        for i in 0..n {
+            bytes.iowrite_with(Derp { one: i, two: i as u16 }, LE).unwrap();
+        }
+        ScrollTransducer::from_vec(bytes.into_inner(), n as usize, LE).unwrap()
+    }
+
+    let lt = derps(10);
+    assert_eq!(lt.len(), 10);
+    assert_eq!(lt.get(9).unwrap().one, 9);
+    assert!(lt.get(10).is_none());
+    assert_eq!(lt.into_par_iter().map(|derp| derp.two as u64).sum::<u64>(), 45);
+    assert!(ScrollTransducer::<Derp>::from_vec(lt.into_bytes(), 11, LE).is_err());
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn streaming_transducer_over_a_pipe() {
+    use lazy_transducer::{StreamingTransducer, TransducerError};
+    use std::io::Read;
+    use std::sync::mpsc;
+
+    /// A reader which yields whatever the other end of the channel sends, like a pipe
+    struct Pipe(mpsc::Receiver<Vec<u8>>);
+    impl Read for Pipe {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let chunk = self.0.recv().unwrap_or_default();
+            buf[..chunk.len()].copy_from_slice(&chunk);
+            Ok(chunk.len())
+        }
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let mut lt: StreamingTransducer<_, u32, _> = ScrollTransducer::from_stream(Pipe(receiver), BE);
+    sender.send(vec![0, 0, 0, 1, 0, 0]).unwrap();
+    assert_eq!(lt.fill().unwrap(), 6);
+    assert_eq!(lt.available_len(), 1);
+    assert_eq!(lt.get(0).unwrap(), 1);
+    match lt.get(1) {
+        Err(TransducerError::NotYetAvailable{ idx: 1, available: 1 }) => (),
+        res => panic!("expected NotYetAvailable, got {:?}", res),
+    }
+
+    sender.send(vec![0, 2]).unwrap();
+    drop(sender);
+    lt.fill().unwrap();
+    assert_eq!(lt.get(1).unwrap(), 2);
+    assert_eq!(lt.fill().unwrap(), 0);
+    assert!(lt.is_finished());
+    match lt.get(2) {
+        Err(TransducerError::OutOfBounds{ idx: 2, len: 2 }) => (),
+        res => panic!("expected OutOfBounds, got {:?}", res),
+    }
+    assert_eq!(lt.transducer().into_par_iter().map(Result::unwrap).sum::<u32>(), 3);
+
+    // a malformed element is a parse error, not a panic
+    let mut flags: StreamingTransducer<_, Flag, _> = ScrollTransducer::from_stream(&[1u8, 7][..], LE);
+    flags.fill().unwrap();
+    assert!(flags.get(0).unwrap().0);
+    match flags.get(1) {
+        Err(TransducerError::Parse{ idx: 1, offset: 1, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn growable_source_picks_up_appended_records() {
+    use lazy_transducer::{GrowableSource, GrowableTransducer, Source};
+    use std::fs::{self, OpenOptions};
+
+    let path = std::env::temp_dir().join("lazy_transducer_growable_test");
+    fs::write(&path, []).unwrap();
+    let source = GrowableSource::open(&path).unwrap();
+    let lt: GrowableTransducer<_, Derp, _> = GrowableTransducer::parse_with(&source, LE);
+    assert!(lt.is_empty());
+
+    let mut log = OpenOptions::new().append(true).open(&path).unwrap();
+    for i in 0..5 {
+        log.iowrite_with(Derp { one: i, two: 0 }, LE).unwrap();
+    }
+    let snapshot = lt.transducer();
+    assert_eq!(source.refresh().unwrap(), 50);
+    assert_eq!(snapshot.len(), 0);
+    assert_eq!(lt.len(), 5);
+    assert_eq!(lt.transducer().into_par_iter().map(|derp| derp.one).sum::<u64>(), 10);
+
+    // a partially written record isn't visible until the rest of it arrives
+    log.iowrite_with(5u64, LE).unwrap();
+    source.refresh().unwrap();
+    assert_eq!(lt.len(), 5);
+    assert!(source.pread_with::<Derp, _, _>(5, 50, LE).is_err());
+    log.iowrite_with(5u16, LE).unwrap();
+    source.refresh().unwrap();
+    assert_eq!(lt.get(5).unwrap().two, 5);
+    fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn mut_lazy_transducer_swaps_endianness_in_parallel() {
+    use lazy_transducer::MutLazyTransducer;
+
+    let mut bytes = Cursor::new(Vec::new());
+    for i in 0..1000 {
+        bytes.iowrite_with(Derp { one: i, two: i as u16 }, LE).unwrap();
+    }
+    use scroll::Pwrite;
+    let mut lt = MutLazyTransducer::new(bytes.into_inner(), 1000, 10, (), |(window, ()), _| {
+        let derp: Derp = window.pread_with(0, LE).unwrap();
+        window.pwrite_with(derp, 0, BE).unwrap();
+        derp.one
+    }).unwrap();
+    assert_eq!(lt.par_iter_mut().sum::<u64>(), 499500);
+    let bytes = lt.into_inner();
+    let lt: ScrollTransducer<Derp> = ScrollTransducer::parse_with(&bytes[..], 1000, BE).unwrap();
+    assert!(lt.into_par_iter().enumerate().all(|(i, derp)| derp.one == i as u64 && derp.two == i as u16));
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn shared_transducers_free_their_bytes_with_the_last_clone() {
+    use lazy_transducer::OwnedTransducer;
+    use std::rc::Rc;
+
+    let mut bytes = Cursor::new(Vec::new());
+    for i in 0..10 {
+        bytes.iowrite_with(Derp { one: i, two: i as u16 }, LE).unwrap();
+    }
+    let bytes: Rc<[u8]> = bytes.into_inner().into();
+    let weak = Rc::downgrade(&bytes);
+    let derps: OwnedTransducer<Derp, scroll::Endian, Rc<[u8]>> = ScrollTransducer::from_shared(bytes.clone(), 10, LE).unwrap();
+    let ones: OwnedTransducer<u64, scroll::Endian, Rc<[u8]>> = ScrollTransducer::from_shared(bytes, 1, LE).unwrap();
+    let copy = derps.clone();
+    assert_eq!(copy.into_par_iter().map(|derp| derp.one).sum::<u64>(), 45);
+    drop(derps);
+    drop(copy);
+    assert!(weak.upgrade().is_some());
+    assert_eq!(ones.get(0), Some(0));
+    drop(ones);
+    assert!(weak.upgrade().is_none());
+}
+
+#[cfg(all(feature = "checksum", feature = "scroll"))]
+#[test]
+fn checksummed_tables() {
+    use lazy_transducer::{Digest, TransducerError};
+
+    assert_eq!(Digest::crc32(b"123456789"), Digest::Crc32(0xcbf43926));
+    assert_eq!(Digest::sha256(b"abc").to_string(), "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+
+    // large enough to be checksummed in parallel chunks
+    let bytes: Vec<u8> = (0..(3 << 20) + 7).map(|i| (i * 31) as u8).collect();
+    let mut serial = 0xffff_ffffu32;
+    for byte in &bytes {
+        serial ^= *byte as u32;
+        for _ in 0..8 {
+            serial = if serial & 1 == 1 { serial >> 1 ^ 0xedb8_8320 } else { serial >> 1 };
+        }
+    }
+    assert_eq!(Digest::crc32(&bytes), Digest::Crc32(!serial));
+
+    let expected = Digest::sha256(&bytes[..1 << 20]);
+    let lt: ScrollTransducer<u64> = ScrollTransducer::parse_with_checksum(&bytes, 1 << 17, LE, expected).unwrap();
+    assert_eq!(lt.len(), 1 << 17);
+    match ScrollTransducer::<u64>::parse_with_checksum(&bytes, 1 << 17, LE, Digest::Sha256([0; 32])) {
+        Err(TransducerError::ChecksumMismatch{ actual, .. }) => assert_eq!(actual, expected),
+        res => panic!("expected a checksum mismatch, got {:?}", res),
+    }
+}
+
+#[test]
+fn base64_source_reads_any_range() {
+    use lazy_transducer::{Base64Source, Source};
+
+    let decoded = b"lazy transducers!";
+    for encoded in &["bGF6eSB0cmFuc2R1Y2VycyE=", "bGF6eSB0cmFuc2R1Y2VycyE"] {
+        let source = Base64Source::new(encoded.as_bytes()).unwrap();
+        assert_eq!(source.len(), decoded.len());
+        for offset in 0..=decoded.len() {
+            for len in 0..=decoded.len() - offset {
+                assert_eq!(&source.read(offset, len).unwrap()[..], &decoded[offset..offset + len]);
+            }
+        }
+        assert!(source.read_bytes(10, 8).is_err());
+    }
+    assert!(Base64Source::new(b"bGF6e").is_err());
+    let invalid = Base64Source::new(b"bGF6eSB0c!Fu").unwrap();
+    // only the groups which are read are decoded
+    assert_eq!(&invalid.read(0, 5).unwrap()[..], b"lazy ");
+    assert!(invalid.read(6, 1).is_err());
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn hex_source_parses_pasted_tables() {
+    use lazy_transducer::HexSource;
+
+    // two Derps, as they'd be pasted from a hex dump
+    let source = HexSource::new(b"0100000000000000ffff0200000000000000FeFf").unwrap();
+    let lt: ScrollTransducer<Derp, _, _> = ScrollTransducer::parse_with(&source, 2, LE).unwrap();
+    assert_eq!(lt.into_iter().map(|derp| (derp.one, derp.two)).collect::<Vec<_>>(), vec![(1, 0xffff), (2, 0xfffe)]);
+    assert!(ScrollTransducer::<Derp, _, _>::parse_with(&source, 3, LE).is_err());
+
+    // parse errors report the index of the element, not the offset
+    let flags = HexSource::new(b"000107").unwrap();
+    let lt: ScrollTransducer<Flag, _, _> = ScrollTransducer::parse_with_at(&flags, 1, 2, LE).unwrap();
+    assert!(lt.try_get(0).unwrap().0);
+    match lt.try_get(1) {
+        Err(lazy_transducer::TransducerError::Parse{ idx: 1, offset: 2, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+
+    assert!(HexSource::new(b"abc").is_err());
+    let invalid = HexSource::new(b"00zz").unwrap();
+    assert_eq!(invalid.read(0, 1).unwrap(), vec![0]);
+    assert!(invalid.read(1, 1).is_err());
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn encrypted_source_decrypts_blocks_once() {
+    use lazy_transducer::EncryptedSource;
+
+    // a keystream cipher, keyed by the block index, so blocks can only be decrypted independently
+    fn keystream(key: u8, block: usize, bytes: &mut [u8]) {
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte ^= key.wrapping_mul(block as u8 + 1).wrapping_add(i as u8);
+        }
+    }
+    let plaintext: Vec<u8> = (0..20u64).flat_map(|n| {
+        let mut derp = n.to_le_bytes().to_vec();
+        derp.extend_from_slice(&(n as u16 * 3).to_le_bytes());
+        derp
+    }).collect();
+    let mut ciphertext = plaintext.clone();
+    for (block, bytes) in ciphertext.chunks_mut(16).enumerate() {
+        keystream(0x3b, block, bytes);
+    }
+
+    // the derps are 10 bytes, so many straddle blocks
+    let source = EncryptedSource::new(&ciphertext, 16, |block, bytes: &mut [u8]| keystream(0x3b, block, bytes));
+    assert_eq!(source.blocks(), 13);
+    let lt: ScrollTransducer<Derp, _, _> = ScrollTransducer::parse_with(&source, 20, LE).unwrap();
+    assert_eq!(lt.get(7).map(|derp| (derp.one, derp.two)), Some((7, 21)));
+    assert_eq!(source.decryptions(), 1);
+    let derps: Vec<_> = lt.into_iter().map(|derp| (derp.one, derp.two)).collect();
+    assert_eq!(derps, (0..20).map(|n| (n, n as u16 * 3)).collect::<Vec<_>>());
+    assert_eq!(source.decryptions(), 13);
+    assert_eq!(source.resident_bytes(), ciphertext.len());
+    assert!(source.block(13).is_err());
+
+    let bounded = EncryptedSource::new(&ciphertext, 16, |block, bytes: &mut [u8]| keystream(0x3b, block, bytes)).with_memory_budget(32);
+    assert!(bounded.with_bytes(0, plaintext.len(), |bytes| bytes == &plaintext[..]).unwrap());
+    assert!(bounded.resident_bytes() <= 32);
+}
+
+#[cfg(feature = "pod")]
+#[test]
+fn pod_transducer_views_structs_in_place() {
+    use bytemuck::{Pod, Zeroable};
+    use lazy_transducer::{PodTransducer, Endian, TransducerError};
+    use rayon::prelude::*;
+
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+    struct Sym {
+        name: u32,
+        value: u32,
+        size: u64,
+    }
+    let syms: Vec<Sym> = (0..100).map(|n| Sym { name: n, value: n * 4, size: n as u64 * 8 }).collect();
+    let bytes: &[u8] = bytemuck::cast_slice(&syms);
+
+    let lt = PodTransducer::<Sym>::new(bytes, Endian::default()).unwrap();
+    assert_eq!(lt.len(), 100);
+    assert!(std::ptr::eq(lt.get(42).unwrap(), &syms[42]));
+    assert_eq!(lt.into_par_iter().map(|sym| sym.size).sum::<u64>(), 39600);
+
+    match PodTransducer::<Sym>::new(&bytes[4..], Endian::default()) {
+        Err(TransducerError::Misaligned{ offset: 0, align: 8 }) => (),
+        res => panic!("expected a misaligned error, got {:?}", res),
+    }
+    assert!(PodTransducer::<Sym>::new(&bytes[..20], Endian::default()).is_err());
+    let foreign = if Endian::default() == Endian::Little { Endian::Big } else { Endian::Little };
+    assert!(PodTransducer::<Sym>::new(bytes, foreign).is_err());
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn numeric_transducers() {
+    use lazy_transducer::{U64s, I16s, F64s, NumTransducer, Endian};
+
+    let bytes: Vec<u8> = (1..=4u64).flat_map(|n| n.to_be_bytes().to_vec()).collect();
+    let longs = U64s::new(&bytes, Endian::Big);
+    assert_eq!(longs.len(), 4);
+    assert_eq!(longs.into_par_iter().sum::<u64>(), 10);
+    assert_eq!(longs.get(4), None);
+    assert_eq!(I16s::new(&[0xfe, 0xff, 0xff], Endian::Little).into_iter().collect::<Vec<_>>(), vec![-2]);
+    assert_eq!(F64s::new(&2.5f64.to_le_bytes(), Endian::Little).get(0), Some(2.5));
+    assert!(NumTransducer::<u128>::new(&bytes[..15], Endian::Little).is_empty());
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn utf16_string_table() {
+    use lazy_transducer::{Utf16Transducer, LengthPrefix, Endian, TransducerError};
+
+    // a PE style resource string table: u16 lengths in code units, then little endian UTF-16
+    let names = ["ICON", "Ünïcödé", "😀"];
+    let mut bytes = Vec::new();
+    for name in names.iter() {
+        let units: Vec<u16> = name.encode_utf16().collect();
+        bytes.extend_from_slice(&(units.len() as u16).to_le_bytes());
+        bytes.extend(units.iter().flat_map(|unit| unit.to_le_bytes().to_vec()));
+    }
+    let lt = Utf16Transducer::parse_prefixed(&bytes, 3, LengthPrefix::U16(Endian::Little), Endian::Little).unwrap();
+    assert_eq!(lt.offset_of(1), Some(12));
+    let strings: Vec<String> = lt.into_par_iter().map(Result::unwrap).collect();
+    assert_eq!(strings, names);
+    assert!(lt.get(3).is_none());
+
+    match Utf16Transducer::parse_prefixed(&bytes, 4, LengthPrefix::U16(Endian::Little), Endian::Little) {
+        Err(TransducerError::Parse{ idx: 3, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert!(Utf16Transducer::parse_terminated(&bytes, &[2], Endian::Little).is_err());
+}
+
+#[test]
+fn strtab_lookups_by_offset() {
+    use lazy_transducer::{StrTableTransducer, TransducerError};
+
+    let strtab = b"\0libc.so.6\0printf\0_ITM_deregisterTMCloneTable\0caf\xc3\xa9\0";
+    let lt = StrTableTransducer::new(strtab).unwrap();
+    assert_eq!(lt.len(), 5);
+    assert_eq!(lt.get_at_offset(0), Some(""));
+    assert_eq!(lt.get_at_offset(11), Some("printf"));
+    assert_eq!(lt.get_at_offset(14), Some("ntf"));
+    assert_eq!(lt.get_at_offset(17), Some(""));
+    assert_eq!(lt.index_of(11), Some(2));
+    assert_eq!(lt.index_of(12), None);
+    // in the middle of the é
+    assert_eq!(lt.get_at_offset(strtab.len() - 2), None);
+    assert_eq!(lt.get_at_offset(strtab.len()), None);
+    assert_eq!(lt.into_par_iter().map(str::len).sum::<usize>(), strtab.len() - 5);
+
+    match StrTableTransducer::new(b"ok\0bad\xff\0") {
+        Err(TransducerError::Parse{ idx: 1, offset: 3, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert!(StrTableTransducer::new(b"ok\0unterminated").is_err());
+}
+
+#[test]
+fn strtab_never_yields_invalid_utf8() {
+    use lazy_transducer::StrTableTransducer;
+
+    // a lone continuation byte, a truncated sequence, and an overlong encoding
+    for strtab in &[&b"\0\x80\0"[..], b"ok\0caf\xc3\0", b"\xc0\xaf\0"] {
+        assert!(StrTableTransducer::new(strtab).is_err());
+    }
+    let lt = StrTableTransducer::new(b"ok\0caf\xc3\xa9\0").unwrap();
+    assert_eq!(lt.get(1), Some("caf\u{e9}"));
+    assert_eq!(lt.get(2), None);
+    // the tail of the é is not valid UTF-8 on its own
+    assert_eq!(lt.get_at_offset(7), None);
+}
+
+#[test]
+fn varints_across_index_blocks() {
+    use lazy_transducer::{VarintTransducer, TransducerError};
+
+    fn uleb128(mut n: u64, bytes: &mut Vec<u8>) {
+        loop {
+            let byte = (n & 0x7f) as u8;
+            n >>= 7;
+            if n == 0 {
+                bytes.push(byte);
+                return
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+    // enough large values that the relative offsets of a block approach their limit
+    let values: Vec<u64> = (0..5000u64).map(|n| n.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (n % 64)).collect();
+    let mut bytes = Vec::new();
+    for value in &values {
+        uleb128(*value, &mut bytes);
+    }
+    let lt = VarintTransducer::<u64>::new(&bytes).unwrap();
+    assert_eq!(lt.len(), values.len());
+    assert_eq!(lt.offset_of(values.len()), Some(bytes.len()));
+    assert_eq!(lt.into_par_iter().collect::<Vec<_>>(), values);
+    assert_eq!(lt.get(1024), Some(values[1024]));
+    assert_eq!(VarintTransducer::<u64>::with_count(&bytes, 3).unwrap().len(), 3);
+
+    assert_eq!(VarintTransducer::<i32>::new(&[0x80, 0x80, 0x80, 0x80, 0x78]).unwrap().get(0), Some(i32::MIN));
+    match VarintTransducer::<u32>::new(&[0x01, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]) {
+        Err(TransducerError::Parse{ idx: 1, offset: 1, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert!(VarintTransducer::<u64>::new(&[0x01, 0x80]).is_err());
+}
+
+#[test]
+fn delimited_records() {
+    use lazy_transducer::Delimited;
+
+    let lines = Delimited::lines(b"one\r\n\ntwo\nthree");
+    assert_eq!(lines.into_iter().collect::<Vec<_>>(), vec![&b"one"[..], b"", b"two", b"three"]);
+    assert_eq!(lines.offset_of(3), Some(10));
+    assert_eq!(lines.get(4), None);
+    assert!(Delimited::lines(b"").is_empty());
+    assert_eq!(Delimited::lines(b"\n").len(), 1);
+
+    // a carriage return is only stripped from lines
+    let records = Delimited::new(b"a\r;b\xff;", b';');
+    assert_eq!(records.get(0), Some(&b"a\r"[..]));
+    assert!(records.get_str(1).unwrap().is_err());
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn csv_rows_with_quoted_newlines() {
+    use lazy_transducer::{CsvTransducer, TransducerError};
+
+    let mut csv = String::from("id\tnote\n");
+    for id in 0..500 {
+        csv.push_str(&format!("{}\t\"line one\nline \"\"{}\"\"\"\n", id, id));
+    }
+    let lt = CsvTransducer::with_config(csv.as_bytes(), b'\t', true).unwrap();
+    assert_eq!(lt.len(), 500);
+    assert_eq!(lt.headers().map(|headers| headers.len()), Some(2));
+    assert_eq!(&lt.get(321).unwrap().unwrap()[1], "line one\nline \"321\"");
+    let ids: Vec<(u32, String)> = lt.deserialized().into_par_iter().map(Result::unwrap).collect();
+    assert_eq!(ids[499].0, 499);
+
+    let headless = CsvTransducer::with_config(b"1,2\n3,4\n", b',', false).unwrap();
+    assert_eq!(headless.deserialize::<(u8, u8)>(1).unwrap().unwrap(), (3, 4));
+    match CsvTransducer::new(b"a,b\n1,2\n3\n") {
+        Err(TransducerError::Parse{ idx: 1, offset: 8, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert!(CsvTransducer::new(b"n\nx\n").unwrap().deserialize::<(u8,)>(0).unwrap().is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_lines_report_bad_lines() {
+    use lazy_transducer::{JsonLines, TransducerError};
+    use std::collections::HashMap;
+
+    let log = b"{\"a\":1}\r\n{\"a\":2,\"b\":3}\r\n{\"a\":\n[1,2,3]\r\n";
+    let lt: JsonLines<HashMap<String, u32>> = JsonLines::new(log);
+    assert_eq!(lt.len(), 4);
+    assert_eq!(lt.get(1).unwrap().unwrap()["b"], 3);
+    assert_eq!(lt.line(3), Some(&b"[1,2,3]"[..]));
+    match lt.get(2) {
+        Some(Err(TransducerError::Parse{ idx: 2, offset: 24, .. })) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    let ok = lt.into_iter().filter(Result::is_ok).count();
+    assert_eq!(ok, 2);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn bincode_fixed_and_offsets() {
+    use bincode::serialize;
+    use lazy_transducer::{BincodeTransducer, TransducerError};
+
+    let mut data = Vec::new();
+    for pair in &[(1u32, -1i16), (2, -2), (3, -3)] {
+        data.extend_from_slice(&serialize(pair).unwrap());
+    }
+    // a truncated trailing element is ignored
+    data.push(0xff);
+    let lt: BincodeTransducer<(u32, i16)> = BincodeTransducer::fixed(&data).unwrap();
+    assert_eq!(lt.len(), 3);
+    assert_eq!(lt.offset_of(2), Some(12));
+    assert_eq!(lt.into_iter().map(Result::unwrap).collect::<Vec<_>>(), vec![(1, -1), (2, -2), (3, -3)]);
+
+    let mut data = Vec::new();
+    let mut offsets = Vec::new();
+    for v in &[vec![1u8, 2, 3], vec![], vec![4]] {
+        offsets.push(data.len());
+        data.extend_from_slice(&serialize(v).unwrap());
+    }
+    let lt: BincodeTransducer<Vec<u8>> = BincodeTransducer::with_offsets(&data[..data.len() - 1], offsets.clone()).unwrap();
+    assert_eq!(lt.get(0).unwrap().unwrap(), vec![1, 2, 3]);
+    assert!(lt.get(1).unwrap().unwrap().is_empty());
+    match lt.get(2) {
+        Some(Err(TransducerError::Parse{ idx: 2, offset, .. })) => assert_eq!(offset, offsets[2]),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    offsets.swap(0, 1);
+    assert!(BincodeTransducer::<Vec<u8>>::with_offsets(&data, offsets).is_err());
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn msgpack_stream_boundaries() {
+    use lazy_transducer::{MsgPackTransducer, TransducerError};
+    use std::collections::BTreeMap;
+
+    // {"a": [1, -1]}, {"b": <bin 3>}, {} and a float64 where a map is expected
+    let stream = [
+        0x81, 0xa1, b'a', 0x92, 0x01, 0xff,
+        0x81, 0xa1, b'b', 0xc4, 0x03, 1, 2, 3,
+        0x80,
+        0xcb, 0, 0, 0, 0, 0, 0, 0, 0,
+    ];
+    let lt: MsgPackTransducer<BTreeMap<String, Vec<i8>>> = MsgPackTransducer::new(&stream).unwrap();
+    assert_eq!(lt.len(), 4);
+    assert_eq!(lt.get(0).unwrap().unwrap()["a"], vec![1, -1]);
+    assert_eq!(lt.value(1), Some(&stream[6..14]));
+    assert!(lt.get(2).unwrap().unwrap().is_empty());
+    match lt.get(3) {
+        Some(Err(TransducerError::Parse{ idx: 3, offset: 15, .. })) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+
+    // the second array claims more elements than there are bytes
+    match MsgPackTransducer::<Vec<u8>>::new(&[0x90, 0xdd, 0xff, 0xff, 0xff, 0xff, 0x01]) {
+        Err(TransducerError::Parse{ idx: 1, offset: 1, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    // deeply nested arrays don't overflow the stack
+    let mut nested = vec![0x91; 1_000_000];
+    nested.push(0xc0);
+    assert_eq!(MsgPackTransducer::<()>::new(&nested).unwrap().len(), 1);
+}
+
+#[test]
+fn length_delimited_frames() {
+    use lazy_transducer::{LengthDelimited, TransducerError};
+
+    // a 200 byte message, whose length prefix takes two bytes
+    let mut stream = vec![0xc8, 0x01];
+    stream.extend_from_slice(&[7; 200]);
+    stream.extend_from_slice(&[0x01, 42]);
+    let lt = LengthDelimited::new(&stream).unwrap();
+    assert_eq!(lt.len(), 2);
+    assert_eq!(lt.offset_of(1), Some(203));
+    assert_eq!(lt.into_iter().map(|message| message.len()).collect::<Vec<_>>(), vec![200, 1]);
+
+    stream.extend_from_slice(&[0x05, 1, 2]);
+    match LengthDelimited::new(&stream) {
+        Err(TransducerError::Parse{ idx: 2, offset: 204, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+}
+
+#[cfg(feature = "prost")]
+#[test]
+fn length_delimited_prost_messages() {
+    use lazy_transducer::{LengthDelimited, TransducerError};
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, Message)]
+    struct Named {
+        #[prost(string, tag = "1")]
+        name: String,
+    }
+
+    let mut stream = Vec::new();
+    Named { name: "a".to_string() }.encode_length_delimited(&mut stream).unwrap();
+    // a string field which is not valid UTF-8
+    stream.extend_from_slice(&[0x03, 0x0a, 0x01, 0xff]);
+    let lt = LengthDelimited::new(&stream).unwrap();
+    assert_eq!(lt.decode::<Named>(0).unwrap().unwrap().name, "a");
+    match lt.decode::<Named>(1) {
+        Some(Err(TransducerError::Parse{ idx: 1, offset: 5, .. })) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert_eq!(lt.messages::<Named>().into_par_iter().filter(Result::is_ok).count(), 1);
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn tlv_records_with_inclusive_lengths() {
+    use lazy_transducer::{Endian, TlvSpec, TlvTransducer, TransducerError};
+
+    // a little endian two byte tag, and a four byte length which counts the header
+    let spec = TlvSpec { tag_width: 2, len_width: 4, endian: Endian::Little, len_includes_header: true };
+    let bytes = [0x34, 0x12, 8, 0, 0, 0, b'o', b'k', 0x01, 0x00, 6, 0, 0, 0];
+    let lt = TlvTransducer::new(&bytes, spec).unwrap();
+    assert_eq!(lt.into_par_iter().collect::<Vec<_>>(), vec![(0x1234, &b"ok"[..]), (1, &[][..])]);
+
+    // a length smaller than the header would never advance
+    match TlvTransducer::new(&[0x01, 0x00, 0, 0, 0, 0], spec) {
+        Err(TransducerError::Parse{ idx: 0, offset: 0, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    match TlvTransducer::new(&bytes[..13], spec) {
+        Err(TransducerError::Parse{ idx: 1, offset: 8, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert!(TlvTransducer::new(&bytes, TlvSpec { tag_width: 9, ..spec }).is_err());
+}
+
+#[test]
+fn bit_fields_straddle_bytes() {
+    use lazy_transducer::{BitOrder, BitTransducer};
+
+    // 64-bit fields starting mid-byte straddle nine bytes
+    let mut bytes = vec![0b1010_0000];
+    bytes.extend_from_slice(&[0xff; 8]);
+    assert!(BitTransducer::new(&bytes, 65, BitOrder::MsbFirst).is_err());
+    let msb = BitTransducer::new(&bytes, 64, BitOrder::MsbFirst).unwrap();
+    assert_eq!(msb.len(), 1);
+    assert_eq!(msb.get(0), Some(0xa0ff_ffff_ffff_ffff));
+    let lsb = BitTransducer::new(&bytes, 5, BitOrder::LsbFirst).unwrap();
+    assert_eq!(lsb.len(), 14);
+    assert_eq!(lsb.bit_offset_of(1), Some(5));
+    // bits 5 to 9 are 1, 0, 1, 1, 1
+    assert_eq!(lsb.get(1), Some(0b11101));
+    let ones = lsb.into_par_iter().filter(|field| *field == 0b11111).count();
+    assert_eq!(ones, 12);
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn arrow_column_from_ipc_file() {
+    use arrow_array::{ArrayRef, Float64Array, RecordBatch};
+    use arrow_array::types::{Float64Type, Int64Type};
+    use arrow_ipc::writer::FileWriter;
+    use lazy_transducer::ArrowColumn;
+    use std::sync::Arc;
+
+    let batches: Vec<RecordBatch> = [vec![Some(0.5), None], vec![], vec![Some(2.0)]].iter().map(|values| {
+        let temps: ArrayRef = Arc::new(Float64Array::from(values.clone()));
+        RecordBatch::try_from_iter(vec![("temp", temps)]).unwrap()
+    }).collect();
+    let mut bytes = Vec::new();
+    {
+        let mut writer = FileWriter::try_new(&mut bytes, &batches[0].schema()).unwrap();
+        for batch in &batches {
+            writer.write(batch).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+    let lt: ArrowColumn<Float64Type> = ArrowColumn::from_ipc(&bytes, "temp").unwrap();
+    assert_eq!(lt.decoded_batches(), 0);
+    // the empty batch is skipped
+    assert_eq!(lt.get(2).unwrap().unwrap(), Some(2.0));
+    assert_eq!(lt.decoded_batches(), 1);
+    assert_eq!(lt.into_iter().collect::<Result<Vec<_>, _>>().unwrap(), vec![Some(0.5), None, Some(2.0)]);
+    assert!(lt.get(3).is_none());
+
+    assert!(ArrowColumn::<Float64Type>::from_ipc(&bytes, "humidity").is_err());
+    assert!(ArrowColumn::<Int64Type>::from_ipc(&bytes, "temp").is_err());
+    assert!(ArrowColumn::<Int64Type>::from_batches(&batches, "temp").is_err());
+    assert!(ArrowColumn::<Float64Type>::from_ipc(&bytes[..bytes.len() / 2], "temp").is_err());
+}
+
+#[test]
+fn fixed_width_records() {
+    use lazy_transducer::{FixedField, FixedValue, FixedWidthTransducer, TransducerError};
+
+    // 8 byte records without line terminators
+    let bytes = b"-12x 1.5 7 y    ?? z .25AB";
+    let layout = vec![FixedField::int(0, 3), FixedField::text(3, 2), FixedField::float(5, 3)];
+    let lt = FixedWidthTransducer::new(bytes, 8, layout.clone()).unwrap();
+    // the trailing 2 bytes are too few for the fields
+    assert_eq!(lt.len(), 3);
+    assert_eq!(lt.offset_of(2), Some(16));
+    assert_eq!(lt.get(1).unwrap().unwrap(), vec![FixedValue::Int(7), FixedValue::Text("y"), FixedValue::Null]);
+    assert_eq!(lt.field(2, 2).unwrap().unwrap(), FixedValue::Float(0.25));
+    match lt.get(2) {
+        Some(Err(TransducerError::Parse{ idx: 2, offset: 16, .. })) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert_eq!(lt.into_par_iter().filter(Result::is_ok).count(), 2);
+    assert!(FixedWidthTransducer::new(bytes, 7, layout).is_err());
+    // the end of this field wraps around to 1
+    assert!(FixedWidthTransducer::new(bytes, 8, vec![FixedField::text(usize::MAX, 2)]).is_err());
+}
+
+#[test]
+fn char_index_across_blocks() {
+    use lazy_transducer::CharTransducer;
+
+    // 3000 chars of 1 to 4 bytes, spanning three index blocks
+    let text: String = "aé€😀".chars().cycle().take(3000).collect();
+    let lt = CharTransducer::new(&text);
+    assert_eq!(lt.len(), 3000);
+    assert_eq!(lt.get(2051), Some('😀'));
+    assert_eq!(lt.offset_of(2048), Some(512 * 10));
+    assert_eq!(lt.offset_of(3000), Some(text.len()));
+    assert_eq!(lt.slice(1023..1026), Some("😀aé"));
+    assert_eq!(lt.slice(2999..3001), None);
+    assert_eq!(lt.into_par_iter().collect::<String>(), text);
+    assert!(CharTransducer::new("").is_empty());
+}
+
+#[test]
+fn chunks_of_png_and_iff_files() {
+    use lazy_transducer::{ChunkFormat, ChunkTransducer, TransducerError};
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    for &(fourcc, payload) in &[(b"IHDR", &b"hdr"[..]), (b"IDAT", b"ab"), (b"IDAT", b"c"), (b"IEND", b"")] {
+        png.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        png.extend_from_slice(fourcc);
+        png.extend_from_slice(payload);
+        png.extend_from_slice(&[0; 4]);
+    }
+    let lt = ChunkTransducer::from_file(&png).unwrap();
+    assert_eq!(lt.format(), ChunkFormat::Png);
+    assert_eq!(lt.len(), 4);
+    assert_eq!(lt.offset_of(1), Some(23));
+    let idat: Vec<u8> = lt.of_type(*b"IDAT").into_iter().flat_map(|(_, payload)| payload.to_vec()).collect();
+    assert_eq!(idat, b"abc");
+
+    // the second chunk claims more bytes than the form has
+    let aiff = b"FORM\0\0\0\x16AIFFCOMM\0\0\0\x01x\0SSND\0\0\0\x09";
+    match ChunkTransducer::from_file(aiff) {
+        Err(TransducerError::Parse{ idx: 1, offset: 22, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert!(ChunkTransducer::from_file(b"RIFF\x02\0\0\0WA").is_err());
+    assert!(ChunkTransducer::from_file(b"GIF89a").is_err());
+}
+
+#[test]
+fn tar_long_names_and_prefixes() {
+    use lazy_transducer::{TarTransducer, TransducerError};
+
+    fn entry(tar: &mut Vec<u8>, name: &[u8], prefix: &[u8], kind: u8, data: &[u8]) {
+        let mut header = [0; 512];
+        header[..name.len()].copy_from_slice(name);
+        header[100..107].copy_from_slice(b"0000755");
+        header[124..135].copy_from_slice(format!("{:011o}", data.len()).as_bytes());
+        header[156] = kind;
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[345..345 + prefix.len()].copy_from_slice(prefix);
+        header[148..156].copy_from_slice(b"        ");
+        let checksum: u32 = header.iter().map(|byte| *byte as u32).sum();
+        header[148..155].copy_from_slice(format!("{:06o}\0", checksum).as_bytes());
+        tar.extend_from_slice(&header);
+        tar.extend_from_slice(data);
+        tar.resize(tar.len().div_ceil(512) * 512, 0);
+    }
+
+    let long = vec![b'a'; 300];
+    let mut tar = Vec::new();
+    entry(&mut tar, b"dir/", b"", b'5', b"");
+    entry(&mut tar, b"././@LongLink", b"", b'L', &[&long[..], b"\0"].concat());
+    entry(&mut tar, b"truncated", b"", b'0', b"1");
+    entry(&mut tar, b"file", b"some/prefix", b'0', b"2");
+    entry(&mut tar, b"PaxHeaders/x", b"", b'x', b"30 mtime=1350244992.023960108\n16 path=pax.txt\n");
+    entry(&mut tar, b"ignored", b"", b'0', &[3; 600]);
+    tar.extend_from_slice(&[0; 1024]);
+
+    let lt = TarTransducer::new(&tar).unwrap();
+    let entries: Vec<_> = lt.into_par_iter().map(Result::unwrap).collect();
+    let paths: Vec<&[u8]> = entries.iter().map(|entry| &*entry.path).collect();
+    assert_eq!(paths, vec![&b"dir/"[..], &long[..], b"some/prefix/file", b"pax.txt"]);
+    assert_eq!(entries[0].kind, b'5');
+    assert_eq!(entries[1].data, b"1");
+    assert_eq!(lt.data(3).unwrap().len(), 600);
+    assert_eq!(lt.offset_of(1), Some(1536));
+
+    tar[1536 + 148] ^= 1;
+    match TarTransducer::new(&tar) {
+        Err(TransducerError::Parse{ idx: 1, offset: 1536, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+}
+
+#[test]
+fn zip_central_directory_with_zip64_fields() {
+    use lazy_transducer::{TransducerError, ZipTransducer};
+
+    fn u16le(n: u16) -> [u8; 2] { n.to_le_bytes() }
+    fn u32le(n: u32) -> [u8; 4] { n.to_le_bytes() }
+
+    // (name, data, whether its sizes and offset are in a ZIP64 extra field)
+    let files: [(&[u8], &[u8], bool); 3] = [(b"dir/", b"", false), (b"dir/a.txt", b"hello", false), (b"dir/b.bin", b"\x00\x01\x02", true)];
+    let mut zip = Vec::new();
+    let mut central = Vec::new();
+    for &(name, data, zip64) in files.iter() {
+        let local = zip.len() as u32;
+        zip.extend_from_slice(b"PK\x03\x04\x14\0\0\0\0\0\0\0\0\0\0\0\0\0");
+        zip.extend_from_slice(&u32le(data.len() as u32));
+        zip.extend_from_slice(&u32le(data.len() as u32));
+        zip.extend_from_slice(&u16le(name.len() as u16));
+        // an extra field of 4 bytes in the local header, which the central one doesn't have
+        zip.extend_from_slice(&u16le(4));
+        zip.extend_from_slice(name);
+        zip.extend_from_slice(b"\xfe\xca\0\0");
+        zip.extend_from_slice(data);
+
+        let (sizes, offset) = if zip64 { (u32::MAX, u32::MAX) } else { (data.len() as u32, local) };
+        let mut extra = b"UT\x01\0\x07".to_vec();
+        if zip64 {
+            extra.extend_from_slice(b"\x01\0\x18\0");
+            extra.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            extra.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            extra.extend_from_slice(&(local as u64).to_le_bytes());
+        }
+        central.extend_from_slice(b"PK\x01\x02\x1e\x03\x14\0\0\0\0\0\0\0\0\0\0\0\0\0");
+        central.extend_from_slice(&u32le(sizes));
+        central.extend_from_slice(&u32le(sizes));
+        central.extend_from_slice(&u16le(name.len() as u16));
+        central.extend_from_slice(&u16le(extra.len() as u16));
+        central.extend_from_slice(&u16le(2));
+        central.extend_from_slice(&[0; 4]);
+        central.extend_from_slice(&u32le(0o100644 << 16));
+        central.extend_from_slice(&u32le(offset));
+        central.extend_from_slice(name);
+        central.extend_from_slice(&extra);
+        central.extend_from_slice(b"hi");
+    }
+    let start = zip.len();
+    zip.extend_from_slice(&central);
+    zip.extend_from_slice(b"PK\x05\x06\0\0\0\0\x03\0\x03\0");
+    zip.extend_from_slice(&u32le(central.len() as u32));
+    zip.extend_from_slice(&u32le(start as u32));
+    // an archive comment, which contains a decoy signature
+    zip.extend_from_slice(&u16le(8));
+    zip.extend_from_slice(b"PK\x05\x06 :-)");
+
+    let lt = ZipTransducer::new(&zip).unwrap();
+    assert_eq!(lt.len(), 3);
+    assert_eq!(lt.offset_of(0), Some(start));
+    let entries = lt.into_par_iter().collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(entries[0].is_dir());
+    assert_eq!(entries[1].name, b"dir/a.txt");
+    assert_eq!(entries[1].comment, b"hi");
+    assert_eq!(entries[1].external_attributes >> 16, 0o100644);
+    assert_eq!(entries[2].compressed_size, 3);
+    assert_eq!(entries[2].local_header_offset, 86);
+    let data = (0..lt.len()).map(|idx| lt.compressed_data(idx).unwrap().unwrap()).collect::<Vec<_>>();
+    assert_eq!(data, vec![&b""[..], b"hello", b"\x00\x01\x02"]);
+
+    // the central directory claims one more entry than it has
+    zip[start + central.len() + 8] = 4;
+    zip[start + central.len() + 10] = 4;
+    match ZipTransducer::new(&zip) {
+        Err(TransducerError::Parse{ idx: 3, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert!(ZipTransducer::new(&zip[..start]).is_err());
+}
+
+#[test]
+fn fasta_and_fastq_records() {
+    use lazy_transducer::{SeqFormat, SeqTransducer, TransducerError};
+
+    let fasta = b">seq1  chromosome 1\r\nACGT\r\n\r\nGG\r\n>seq2\r\n>seq3\tlast\r\nTTT";
+    let lt = SeqTransducer::new(fasta).unwrap();
+    assert_eq!(lt.len(), 3);
+    let records = lt.into_par_iter().collect::<Vec<_>>();
+    assert_eq!(records[0].description, b"chromosome 1");
+    assert_eq!(&*records[0].sequence, b"ACGTGG");
+    assert!(records[1].sequence.is_empty());
+    assert_eq!((records[2].id, &*records[2].sequence), (&b"seq3"[..], &b"TTT"[..]));
+    assert!(records.iter().all(|record| record.quality.is_none()));
+
+    // a quality line may start with '@'
+    let fastq = b"@r1 lane=1\nACGTN\n+\n@II#!\n@r2\nGG\n+r2\n@@\n\n";
+    let lt = SeqTransducer::new(fastq).unwrap();
+    assert_eq!(lt.format(), SeqFormat::Fastq);
+    assert_eq!(lt.len(), 2);
+    assert_eq!(lt.offset_of(1), Some(25));
+    let r2 = lt.get(1).unwrap();
+    assert_eq!((r2.id, &*r2.sequence, r2.quality), (&b"r2"[..], &b"GG"[..], Some(&b"@@"[..])));
+
+    match SeqTransducer::new(&b"@r1\nACGT\n+\nIII\n"[..]) {
+        Err(TransducerError::Parse{ idx: 0, offset: 0, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    match SeqTransducer::new(&fastq[..fastq.len() - 5]) {
+        Err(TransducerError::Parse{ idx: 1, offset: 25, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert!(SeqTransducer::new(b"ACGT").is_err());
+}
+
+#[cfg(feature = "nom")]
+#[test]
+fn nom_length_prefixed_records() {
+    use lazy_transducer::{NomTransducer, TransducerError};
+    use nom::bytes::complete::{tag, take};
+    use nom::combinator::opt;
+    use nom::number::complete::{be_u16, u8 as byte};
+    use nom::IResult;
+
+    // a tag byte, then a big endian u16 length, then the payload
+    fn record(input: &[u8]) -> IResult<&[u8], (u8, &[u8])> {
+        let (input, tag) = byte(input)?;
+        let (input, len) = be_u16(input)?;
+        let (input, payload) = take(len)(input)?;
+        Ok((input, (tag, payload)))
+    }
+
+    let bytes = b"\x01\x00\x03abc\x02\x00\x00\x07\x00\x02hi";
+    let lt = NomTransducer::new(&bytes[..], record).unwrap();
+    assert_eq!(lt.len(), 3);
+    assert_eq!(lt.offset_of(2), Some(9));
+    assert_eq!(lt.record(1), Some(&b"\x02\x00\x00"[..]));
+    let records = lt.into_par_iter().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(records, vec![(1, &b"abc"[..]), (2, &b""[..]), (7, &b"hi"[..])]);
+    assert_eq!(lt.into_iter().map(|record| record.unwrap().0 as u32).sum::<u32>(), 10);
+
+    // records are parsed out of their own bytes, so a parser which looks past the end of a record
+    // fails on it, rather than panicking
+    fn lookahead(input: &[u8]) -> IResult<&[u8], usize> {
+        if input.len() < 2 && input != b"z" {
+            return Err(nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Eof)))
+        }
+        take(1usize)(input).map(|(rest, _)| (rest, input.len()))
+    }
+    let lt = NomTransducer::new(&b"abz"[..], lookahead).unwrap();
+    assert_eq!(lt.get(2).unwrap().unwrap(), 1);
+    match lt.get(1) {
+        Some(Err(TransducerError::Parse{ idx: 1, offset: 1, .. })) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+
+    match NomTransducer::new(&bytes[..bytes.len() - 1], record) {
+        Err(TransducerError::Parse{ idx: 2, offset: 9, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res.map(|lt| lt.len())),
+    }
+    // a parser which succeeds without consuming anything
+    match NomTransducer::new(&bytes[..], |input| opt(tag("#"))(input)) {
+        Err(TransducerError::Parse{ idx: 0, offset: 0, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res.map(|lt| lt.len())),
+    }
+}
+
+#[test]
+fn uuid_tables_in_both_layouts() {
+    use lazy_transducer::{UuidLayout, UuidTransducer};
+
+    // the nil UUID, then 00112233-4455-6677-8899-aabbccddeeff, then a trailing partial UUID
+    let mut rfc = vec![0; 16];
+    rfc.extend((0..16).map(|n| n * 0x11));
+    rfc.extend_from_slice(&[0xff; 5]);
+    let lt = UuidTransducer::new(&rfc, UuidLayout::Rfc4122);
+    assert_eq!(lt.len(), 2);
+    assert_eq!(lt.get(2), None);
+    assert_eq!(lt.get(1).unwrap()[..], rfc[16..32]);
+
+    let guid = [0x33, 0x22, 0x11, 0x00, 0x55, 0x44, 0x77, 0x66, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff];
+    let lt = UuidTransducer::new(&guid, UuidLayout::Guid);
+    assert_eq!(lt.into_par_iter().collect::<Vec<_>>(), vec![UuidTransducer::new(&rfc, UuidLayout::Rfc4122).get(1).unwrap()]);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn uuid_tables_as_uuids() {
+    use lazy_transducer::{UuidLayout, UuidTransducer};
+    use uuid::Uuid;
+
+    // GUIDs are written little endian by Uuid::to_bytes_le
+    let uuids = ["c12a7328-f81f-11d2-ba4b-00a0c93ec93b", "ebd0a0a2-b9e5-4433-87c0-68b6b72699c7"];
+    let uuids = uuids.iter().map(|uuid| Uuid::parse_str(uuid).unwrap()).collect::<Vec<_>>();
+    let bytes = uuids.iter().flat_map(|uuid| uuid.to_bytes_le().to_vec()).collect::<Vec<_>>();
+    let lt = UuidTransducer::new(&bytes, UuidLayout::Guid);
+    assert_eq!(lt.get_uuid(1), Some(uuids[1]));
+    assert_eq!(lt.uuids().into_par_iter().collect::<Vec<_>>(), uuids);
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn elf_notes_with_8_byte_alignment() {
+    use lazy_transducer::{NoteTransducer, Endian, TransducerError};
+
+    fn note(bytes: &mut Vec<u8>, name: &[u8], n_type: u32, desc: &[u8], align: usize) {
+        bytes.extend_from_slice(&(name.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&(desc.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&n_type.to_be_bytes());
+        bytes.extend_from_slice(name);
+        bytes.resize(bytes.len().div_ceil(align) * align, 0);
+        bytes.extend_from_slice(desc);
+        bytes.resize(bytes.len().div_ceil(align) * align, 0);
+    }
+
+    let mut bytes = Vec::new();
+    // a GNU property note, a build id, and a note with an empty name and descriptor
+    note(&mut bytes, b"GNU\0", 5, &[0xc0, 0, 0, 0xc0, 4, 0, 0, 0, 3, 0, 0, 0], 8);
+    note(&mut bytes, b"GNU\0", 3, &[0x5a; 20], 8);
+    note(&mut bytes, b"", 0x100, b"", 8);
+    let lt = NoteTransducer::new(&bytes, Endian::Big, 8).unwrap();
+    assert_eq!(lt.len(), 3);
+    assert_eq!(lt.offset_of(1), Some(32));
+    let build_id = lt.into_par_iter().find_first(|note| note.name == b"GNU" && note.n_type == 3).unwrap();
+    assert_eq!(build_id.desc, &[0x5a; 20][..]);
+    assert_eq!(lt.get(2).map(|note| (note.name, note.desc)), Some((&b""[..], &b""[..])));
+
+    // the padding of the last note may be missing, but not its descriptor
+    assert_eq!(NoteTransducer::new(&bytes[..68], Endian::Big, 8).unwrap().len(), 2);
+    match NoteTransducer::new(&bytes[..67], Endian::Big, 8) {
+        Err(TransducerError::Parse{ idx: 1, offset: 32, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    assert!(NoteTransducer::new(&bytes, Endian::Big, 6).is_err());
+    // a name size which would wrap around the address space
+    let huge = [0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 1];
+    match NoteTransducer::new(&huge, Endian::Big, 4) {
+        Err(TransducerError::Parse{ idx: 0, offset: 0, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+}
+
+#[test]
+fn cached_transducer_transduces_once() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    let data: Vec<u64> = (0..1000).collect();
+    let lt: LazyTransducer<&[u64], u64> = LazyTransducer::new(&data, data.len(), |input, idx| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        input[idx] * 3
+    });
+    let mut cached = lt.cached();
+    assert_eq!(cached.len(), 1000);
+    for _ in 0..100 {
+        assert_eq!(cached.get(0), Some(&0));
+    }
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+    assert!(cached.get(1000).is_none());
+
+    // hammer every element from many threads, several times over
+    for _ in 0..4 {
+        let sum: u64 = cached.into_par_iter().sum();
+        assert_eq!(sum, 3 * 999 * 1000 / 2);
+    }
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1000);
+    assert_eq!(cached.into_iter().nth(10), Some(&30));
+
+    cached.clear();
+    assert!(!cached.is_cached(0));
+    assert_eq!(cached.get(5), Some(&15));
+    assert_eq!(CALLS.load(Ordering::SeqCst), 1001);
+    assert_eq!(cached.into_inner().get(7), Some(21));
+}
+
+#[test]
+fn lru_transducer_bounds_its_cache() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+    let data: Vec<u64> = (0..10_000).collect();
+    let lt: LazyTransducer<&[u64], Vec<u64>> = LazyTransducer::new(&data, data.len(), |input, idx| {
+        CALLS.fetch_add(1, Ordering::SeqCst);
+        vec![input[idx]; 4]
+    });
+    let lru = lt.cached_lru(64);
+    assert_eq!(lru.capacity(), 64);
+
+    // a skewed, parallel workload, in which the hot elements stay cached
+    let sum: u64 = (0..100_000usize).into_par_iter().map(|n| {
+        let idx = if n % 10 == 0 { n % data.len() } else { n % 16 };
+        lru.get(idx).unwrap()[3]
+    }).sum();
+    let expected: u64 = (0..100_000u64).map(|n| if n % 10 == 0 { n % 10_000 } else { n % 16 }).sum();
+    assert_eq!(sum, expected);
+    assert_eq!(lru.hits() + lru.misses(), 100_000);
+    assert_eq!(lru.misses(), CALLS.load(Ordering::SeqCst));
+    assert!(lru.misses() < 20_000, "{} misses", lru.misses());
+    assert!((0..data.len()).filter(|idx| lru.is_cached(*idx)).count() <= 64);
+
+    // an evicted element outlives the cache for as long as it is held
+    let held = lru.get(9_999).unwrap();
+    (0..64).for_each(|idx| { lru.get(idx); });
+    assert!(!lru.is_cached(9_999));
+    assert_eq!(*held, vec![9_999; 4]);
+    let again = lru.get(9_999).unwrap();
+    assert!(!Arc::ptr_eq(&held, &again));
+
+    let lt: LazyTransducer<&[u64], u64> = LazyTransducer::new(&data, data.len(), |input, idx| input[idx]);
+    let uncached = lt.cached_lru(0);
+    assert_eq!(uncached.into_iter().map(|n| *n).sum::<u64>(), 9_999 * 10_000 / 2);
+    assert_eq!((uncached.hits(), uncached.is_cached(0)), (0, false));
+    assert!(uncached.get(10_000).is_none());
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn scroll_transducer_mut_get_and_set() {
+    use lazy_transducer::ScrollTransducerMut;
+    let mut bytes = vec![0u8; 6 * 3];
+    {
+        let mut lt: ScrollTransducerMut<u32> = ScrollTransducerMut::parse_strided(&mut bytes, 3, 6, LE).unwrap();
+        for idx in 0..3 {
+            lt.set(idx, &(idx as u32 + 0xf0)).unwrap();
+            assert_eq!(lt.get(idx), Some(idx as u32 + 0xf0));
+        }
+        assert_eq!(lt.get(3), None);
+        lt.set(1, &0xdeadbeef).unwrap();
+        assert_eq!(lt.get(1), Some(0xdeadbeef));
+        assert_eq!(lt.transducer().into_iter().collect::<Vec<_>>(), vec![0xf0, 0xdeadbeef, 0xf2]);
+    }
+    // the padding between the strided elements is untouched
+    assert_eq!(&bytes[4..6], &[0, 0]);
+    assert_eq!(&bytes[6..10], &[0xef, 0xbe, 0xad, 0xde]);
+}
+
+#[cfg(feature = "scroll")]
+#[test]
+fn variant_parsers_are_bounded_by_their_records() {
+    use lazy_transducer::{TransducerError, Variants};
+    let variants: Variants<u32> = Variants::new()
+        .register(1, |body| Ok((body.pread::<u8>(0)? as u32, 1)))
+        .register(2, |body| Ok((body.pread_with(0, LE)?, 4)))
+        // claims more bytes than it was given
+        .register(3, |body| Ok((0, body.len() + 1)));
+
+    // a word does not fit in a 3 byte record, even though the bytes of the next record follow it
+    let bytes = [1, 7, 0, 2, 1, 0, 0, 0];
+    let lt = variants.fixed(&bytes, 2, 3).unwrap();
+    assert_eq!(lt.get(0), Some(7));
+    match lt.try_get(1) {
+        Err(TransducerError::Parse{ idx: 1, offset: 3, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res),
+    }
+    let lt = variants.fixed(&[3, 0, 0, 0], 2, 2).unwrap();
+    assert!(lt.try_get(1).is_err());
+    let lt = variants.fixed(&[1, 7], 2, 0).unwrap();
+    assert!(lt.try_get(0).is_err());
+
+    // the word which follows is truncated
+    match variants.variable(&[1, 7, 2, 1, 0, 0], 2) {
+        Err(TransducerError::Parse{ idx: 1, offset: 2, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res.map(|lt| lt.len())),
+    }
+    match variants.variable(&[1, 7, 3, 0, 0], 2) {
+        Err(TransducerError::Parse{ idx: 1, offset: 2, .. }) => (),
+        res => panic!("expected a parse error, got {:?}", res.map(|lt| lt.len())),
+    }
+    let lt = variants.variable(&[1, 7, 2, 1, 0, 0, 0], 2).unwrap();
+    assert_eq!((lt.get(1), lt.offset_of(1)), (Some(1), Some(2)));
+}
+
+#[test]
+fn joined_transducer_names_records() {
+    use lazy_transducer::JoinedTransducer;
+    // (name offset, value) records, the last of which names a string past the end of the table
+    let records = [(1u32, 10u32), (0, 20), (6, 30), (64, 40)];
+    let strtab = b"\0main\0_start\0";
+    let lt: LazyTransducer<&[(u32, u32)], (u32, u32)> = LazyTransducer::new(&records, records.len(), |input, idx| input[idx]);
+    let named = JoinedTransducer::new(lt, strtab, |(name, value), strtab| {
+        strtab.pread::<&str>(name as usize).map(|name| (name, value))
+    });
+    assert_eq!(named.len(), 4);
+    assert!(!named.is_empty());
+    assert_eq!(named.get(0).unwrap().unwrap(), ("main", 10));
+    assert_eq!(named.get(1).unwrap().unwrap(), ("", 20));
+    assert_eq!(named.get(2).unwrap().unwrap(), ("_start", 30));
+    // a bad name is up to the join function, not a panic
+    assert!(named.get(3).unwrap().is_err());
+    assert!(named.get(4).is_none());
+
+    let seq: Vec<_> = named.transducer().into_iter().map(|joined| joined.ok()).collect();
+    let par: Vec<_> = named.into_par_iter().map(|joined| joined.ok()).collect();
+    assert_eq!(seq, par);
+    assert_eq!(seq, vec![Some(("main", 10)), Some(("", 20)), Some(("_start", 30)), None]);
+
+    let empty: LazyTransducer<&[(u32, u32)], (u32, u32)> = LazyTransducer::new(&records, 0, |input, idx| input[idx]);
+    let empty = JoinedTransducer::new(empty, strtab, |(name, _), strtab| strtab.len() as u32 + name);
+    assert!(empty.is_empty());
+    assert_eq!(empty.get(0), None);
+    assert_eq!((&empty).into_iter().count(), 0);
+}